@@ -1,6 +1,8 @@
 use anyhow::{bail, Result};
 use bitvec::prelude::*;
 
+use crate::error::{ParseError, Position};
+
 // Inspired from https://github.com/apocalyptech/bl3-cli-saveedit/blob/master/bl3save/datalib.py
 // Thanks apocalyptech
 
@@ -9,11 +11,15 @@ pub struct ArbitraryBits<'a> {
     // We are using the bitvec library here instead of parsing with nom as the bit lengths do not
     // always fit into chunks of 8 and nom will drop any bits that do not fit into chunks of 8
     bitslice: &'a BitSlice<Lsb0, u8>,
+    total_len: usize,
 }
 
 impl<'a> ArbitraryBits<'a> {
     pub fn new(bitslice: &'a BitSlice<Lsb0, u8>) -> Self {
-        ArbitraryBits { bitslice }
+        ArbitraryBits {
+            bitslice,
+            total_len: bitslice.len(),
+        }
     }
 
     pub fn bitslice(&self) -> &'a BitSlice<Lsb0, u8> {
@@ -28,6 +34,11 @@ impl<'a> ArbitraryBits<'a> {
         self.bitslice.len()
     }
 
+    /// How many bits have been eaten so far.
+    pub fn position(&self) -> usize {
+        self.total_len - self.bitslice.len()
+    }
+
     pub fn eat(&mut self, num_bits: usize) -> Result<usize> {
         if num_bits > self.bitslice.len() {
             bail!("Invalid range passed to bit slice.")
@@ -37,6 +48,29 @@ impl<'a> ArbitraryBits<'a> {
             Ok(res)
         }
     }
+
+    /// Same as `eat` but any error says which field was being read and where it starts.
+    pub fn eat_field<F: ToString>(
+        &mut self,
+        field: F,
+        num_bits: usize,
+    ) -> Result<usize, ParseError> {
+        let position = Position::Bit(self.position());
+
+        if num_bits > self.bitslice.len() {
+            return Err(ParseError::unexpected(
+                field,
+                position,
+                format!("{} bit(s)", num_bits),
+                format!("{} bit(s) left", self.bitslice.len()),
+            ));
+        }
+
+        let res = self.bitslice[0..num_bits].load_le::<usize>();
+        self.bitslice = &self.bitslice[num_bits..];
+
+        Ok(res)
+    }
 }
 
 #[derive(Debug, Default)]
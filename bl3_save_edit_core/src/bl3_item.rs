@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::Formatter;
 use std::str::FromStr;
 
@@ -7,11 +8,12 @@ use bitvec::prelude::*;
 use byteorder::{BigEndian, WriteBytesExt};
 use encoding_rs::mem::decode_latin1;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 use tracing::warn;
 
 use crate::arbitrary_bits::{ArbitraryBitVec, ArbitraryBits};
+use crate::error::{ParseError, Position};
 use crate::game_data::{BALANCE_NAME_MAPPING, BALANCE_TO_INV_KEY};
 use crate::parser::read_be_signed_int;
 use crate::resources::{
@@ -75,7 +77,7 @@ impl Bl3ItemParts {
     }
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct BalancePart {
     pub ident: String,
     pub short_ident: Option<String>,
@@ -99,7 +101,7 @@ impl std::fmt::Display for BalancePart {
     }
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct InvDataPart {
     pub ident: String,
     pub idx: usize,
@@ -117,7 +119,7 @@ impl std::fmt::Display for InvDataPart {
     }
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct ManufacturerPart {
     pub ident: String,
     pub short_ident: Option<String>,
@@ -136,13 +138,43 @@ impl std::fmt::Display for ManufacturerPart {
     }
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Serialize)]
 pub struct Bl3Part {
     pub ident: String,
     pub short_ident: Option<String>,
     pub idx: usize,
 }
 
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct Bl3ItemDiff {
+    pub balance: Option<(BalancePart, BalancePart)>,
+    pub manufacturer: Option<(ManufacturerPart, ManufacturerPart)>,
+    pub level: Option<(usize, usize)>,
+    pub rarity: Option<(ItemRarity, ItemRarity)>,
+    pub parts: Vec<Bl3ItemCategoryDiff>,
+    pub anointments_added: Vec<Bl3Part>,
+    pub anointments_removed: Vec<Bl3Part>,
+}
+
+impl Bl3ItemDiff {
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.manufacturer.is_none()
+            && self.level.is_none()
+            && self.rarity.is_none()
+            && self.parts.is_empty()
+            && self.anointments_added.is_empty()
+            && self.anointments_removed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct Bl3ItemCategoryDiff {
+    pub category: String,
+    pub added: Vec<Bl3Part>,
+    pub removed: Vec<Bl3Part>,
+}
+
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Display, EnumString)]
 pub enum ItemType {
     #[strum(serialize = "BPInvPart_Artifact_C", to_string = "Artifact")]
@@ -165,7 +197,7 @@ impl std::default::Default for ItemType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Display, EnumString)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Display, EnumString, Serialize)]
 pub enum ItemRarity {
     #[strum(
         serialize = "01/Common",
@@ -216,18 +248,53 @@ pub enum WeaponType {
     Heavy,
 }
 
+pub(crate) fn item_type_from_balance(
+    balance: &str,
+    part_inv_key: &str,
+) -> (ItemType, Option<WeaponType>) {
+    let weapon_type = match balance {
+        b if b.contains("_PS_") => Some(WeaponType::Pistol),
+        b if b.contains("_SG_") => Some(WeaponType::Shotgun),
+        b if b.contains("_SM_") => Some(WeaponType::Smg),
+        b if b.contains("_AR_") => Some(WeaponType::Ar),
+        b if b.contains("_SR_") => Some(WeaponType::Sniper),
+        b if b.contains("_HW_") => Some(WeaponType::Heavy),
+        _ => None,
+    };
+
+    let item_type = if weapon_type.is_some() {
+        ItemType::Weapon
+    } else {
+        ItemType::from_str(part_inv_key).unwrap_or_default()
+    };
+
+    (item_type, weapon_type)
+}
+
 impl Bl3Item {
     pub fn from_serial_bytes(serial: &[u8], flags: Option<ItemFlags>) -> Result<Self> {
         let serial = serial;
 
         if serial.len() < 5 {
-            bail!("Serial length must be longer than 4 characters.");
+            return Err(ParseError::unexpected(
+                "serial",
+                Position::Byte(0),
+                "more than 4 bytes",
+                format!("{} byte(s)", serial.len()),
+            )
+            .into());
         }
 
         let initial_byte = serial[0];
 
         if initial_byte != 3 && initial_byte != 4 {
-            bail!("Serial version was not 3 or 4 so we do not know how to decrypt this item.");
+            return Err(ParseError::unexpected(
+                "serial_version",
+                Position::Byte(0),
+                "3 or 4",
+                initial_byte,
+            )
+            .into());
         }
 
         let serial_version = initial_byte;
@@ -252,7 +319,13 @@ impl Bl3Item {
             .write_u16::<BigEndian>((((computed_crc >> 16) ^ computed_crc) & 0xFFFF) as u16)?;
 
         if orig_checksum != computed_checksum {
-            bail!("The expected checksum when deserializing this item does not match the original checksum");
+            return Err(ParseError::unexpected(
+                "checksum",
+                Position::Byte(5),
+                format!("{:02x?}", computed_checksum),
+                format!("{:02x?}", orig_checksum),
+            )
+            .into());
         }
 
         // What we will actually store
@@ -261,20 +334,29 @@ impl Bl3Item {
         // parse the serial data
         let mut bits = ArbitraryBits::new(decrypted_serial.view_bits::<Lsb0>());
 
-        let ident = bits.eat(8)?;
+        let ident = bits.eat_field("ident", 8)?;
 
         // Ident will be 0 if is item is not obfuscated
         if ident != 128 && ident != 0 {
-            bail!(
-                "The 'ident' header of this item should be 128 or 0, but instead it is: {}",
-                ident
-            )
+            return Err(
+                ParseError::unexpected("ident", Position::Bit(0), "128 or 0", ident).into(),
+            );
         }
 
-        let data_version = bits.eat(7)?;
+        let data_version_position = Position::Bit(bits.position());
+        let data_version = bits.eat_field("data_version", 7)?;
 
         if data_version > INVENTORY_SERIAL_DB.max_version {
-            bail!("Cannot parse item as it is newer than the version of this item parser, expected: {}, found: {}", INVENTORY_SERIAL_DB.max_version, data_version);
+            return Err(ParseError::unexpected(
+                "data_version",
+                data_version_position,
+                format!(
+                    "{} or lower, this item is newer than this editor",
+                    INVENTORY_SERIAL_DB.max_version
+                ),
+                data_version,
+            )
+            .into());
         }
 
         let (balance, balance_bits, balance_idx) =
@@ -288,7 +370,7 @@ impl Bl3Item {
 
         let manufacturer_short = manufacturer.rsplit('.').next().map(|s| s.to_owned());
 
-        let level = bits.eat(7)?;
+        let level = bits.eat_field("level", 7)?;
 
         let balance_short_name = balance.rsplit('.').next().map(|s| s.to_owned());
 
@@ -348,13 +430,13 @@ impl Bl3Item {
                 4,
             )?;
 
-            let additional_count = bits.eat(8)?;
+            let additional_count = bits.eat_field("additional_count", 8)?;
 
             let additional_data = (0..additional_count)
-                .map(|_| bits.eat(8))
-                .collect::<Result<Vec<_>>>()?;
+                .map(|i| bits.eat_field(format!("additional_data[{}]", i), 8))
+                .collect::<Result<Vec<_>, _>>()?;
 
-            let num_customs = bits.eat(4)?;
+            let num_customs = bits.eat_field("num_customs", 4)?;
 
             if num_customs != 0 {
                 warn!(
@@ -364,35 +446,33 @@ impl Bl3Item {
                 should_not_allow_parts_parsing = true;
             }
 
-            let rerolled = if serial_version >= 4 { bits.eat(8)? } else { 0 };
+            let rerolled = if serial_version >= 4 {
+                bits.eat_field("rerolled", 8)?
+            } else {
+                0
+            };
 
             if bits.len() > 7 {
                 warn!("Remaining data length for item was more than expected. Expected length of 7 or less but found length of: {}.", bits.len());
             }
 
             if bits.bitslice().count_ones() > 0 {
-                bail!("Could not fully parse the item data, there was unexpected data left.")
+                return Err(ParseError::unexpected(
+                    "remaining data",
+                    Position::Bit(bits.position()),
+                    "only padding",
+                    format!("{} bit(s) that were set", bits.bitslice().count_ones()),
+                )
+                .into());
             }
 
             let rarity = item_part_info
                 .and_then(|info| ItemRarity::from_str(&info.rarity).ok())
                 .unwrap_or_default();
 
-            let weapon_type = match &balance {
-                b if b.contains("_PS_") => Some(WeaponType::Pistol),
-                b if b.contains("_SG_") => Some(WeaponType::Shotgun),
-                b if b.contains("_SM_") => Some(WeaponType::Smg),
-                b if b.contains("_AR_") => Some(WeaponType::Ar),
-                b if b.contains("_SR_") => Some(WeaponType::Sniper),
-                b if b.contains("_HW_") => Some(WeaponType::Heavy),
-                _ => None,
-            };
+            let (balance_item_type, weapon_type) = item_type_from_balance(&balance, &part_inv_key);
 
-            item_type = if weapon_type.is_some() {
-                ItemType::Weapon
-            } else {
-                ItemType::from_str(&part_inv_key).unwrap_or_default()
-            };
+            item_type = balance_item_type;
 
             let item_parts = Bl3ItemParts {
                 part_inv_key,
@@ -756,6 +836,133 @@ impl Bl3Item {
         Ok(())
     }
 
+    /// Compare this item against `other`, anything "added" exists on `other` but not on this item.
+    pub fn diff(&self, other: &Bl3Item) -> Bl3ItemDiff {
+        let balance = if self.balance_part.ident != other.balance_part.ident {
+            Some((self.balance_part.clone(), other.balance_part.clone()))
+        } else {
+            None
+        };
+
+        let manufacturer = if self.manufacturer_part.ident != other.manufacturer_part.ident {
+            Some((
+                self.manufacturer_part.clone(),
+                other.manufacturer_part.clone(),
+            ))
+        } else {
+            None
+        };
+
+        let level = if self.level != other.level {
+            Some((self.level, other.level))
+        } else {
+            None
+        };
+
+        let self_rarity = self.rarity();
+        let other_rarity = other.rarity();
+
+        let rarity = if self_rarity != other_rarity {
+            Some((self_rarity, other_rarity))
+        } else {
+            None
+        };
+
+        let (self_inv_key, self_parts, self_generic_parts) = self.parts_for_diff();
+        let (other_inv_key, other_parts, other_generic_parts) = other.parts_for_diff();
+
+        let (parts_added, parts_removed) = Self::diff_parts(self_parts, other_parts);
+
+        let mut categorized_parts: BTreeMap<String, Bl3ItemCategoryDiff> = BTreeMap::new();
+
+        for part in parts_added {
+            let category = Self::part_category(other_inv_key, &part);
+
+            categorized_parts
+                .entry(category.clone())
+                .or_insert_with(|| Bl3ItemCategoryDiff {
+                    category,
+                    ..Default::default()
+                })
+                .added
+                .push(part);
+        }
+
+        for part in parts_removed {
+            let category = Self::part_category(self_inv_key, &part);
+
+            categorized_parts
+                .entry(category.clone())
+                .or_insert_with(|| Bl3ItemCategoryDiff {
+                    category,
+                    ..Default::default()
+                })
+                .removed
+                .push(part);
+        }
+
+        let (anointments_added, anointments_removed) =
+            Self::diff_parts(self_generic_parts, other_generic_parts);
+
+        Bl3ItemDiff {
+            balance,
+            manufacturer,
+            level,
+            rarity,
+            parts: categorized_parts.into_values().collect(),
+            anointments_added,
+            anointments_removed,
+        }
+    }
+
+    fn rarity(&self) -> ItemRarity {
+        self.item_parts
+            .as_ref()
+            .map(|ip| ip.rarity.clone())
+            .unwrap_or_default()
+    }
+
+    fn parts_for_diff(&self) -> (&str, &[Bl3Part], &[Bl3Part]) {
+        match &self.item_parts {
+            Some(ip) => (ip.part_inv_key.as_str(), &ip.parts, &ip.generic_parts),
+            None => ("", &[], &[]),
+        }
+    }
+
+    // Items can contain the same part more than once so we only match each part a single time
+    fn diff_parts(old: &[Bl3Part], new: &[Bl3Part]) -> (Vec<Bl3Part>, Vec<Bl3Part>) {
+        let mut added = new.to_vec();
+        let mut removed = Vec::new();
+
+        for part in old {
+            if let Some(pos) = added.iter().position(|p| p.ident == part.ident) {
+                added.remove(pos);
+            } else {
+                removed.push(part.clone());
+            }
+        }
+
+        (added, removed)
+    }
+
+    fn part_category(part_inv_key: &str, part: &Bl3Part) -> String {
+        INVENTORY_SERIAL_DB_PARTS_CATEGORIZED
+            .get(part_inv_key)
+            .and_then(|categories| {
+                categories.iter().find(|c| {
+                    c.parts.iter().any(|p| match &part.short_ident {
+                        Some(short_ident) => p.name.eq_ignore_ascii_case(short_ident),
+                        None => part
+                            .ident
+                            .to_lowercase()
+                            .contains(&format!("{}.", p.name.to_lowercase())),
+                    })
+                })
+            })
+            .map(|c| c.category.to_owned())
+            .unwrap_or_else(|| "Unknown Parts".to_owned())
+    }
+
     pub fn update_weapon_serial(&mut self) -> Result<()> {
         let serial_db = &*INVENTORY_SERIAL_DB;
 
@@ -865,7 +1072,7 @@ impl Bl3Item {
     ) -> Result<(String, usize, usize)> {
         let num_bits = INVENTORY_SERIAL_DB.get_num_bits(category, version)?;
 
-        let part_idx = bits.eat(num_bits)?;
+        let part_idx = bits.eat_field(category, num_bits)?;
 
         let part = INVENTORY_SERIAL_DB
             .get_part_ident(category, part_idx)
@@ -881,12 +1088,12 @@ impl Bl3Item {
         count_bits: usize,
     ) -> Result<(usize, Vec<Bl3Part>)> {
         let num_bits = INVENTORY_SERIAL_DB.get_num_bits(category, version)?;
-        let num_parts = bits.eat(count_bits)?;
+        let num_parts = bits.eat_field(format!("{} count", category), count_bits)?;
 
         let mut parts = Vec::with_capacity(num_parts);
 
-        for _ in 0..num_parts {
-            let part_idx = bits.eat(num_bits)?;
+        for i in 0..num_parts {
+            let part_idx = bits.eat_field(format!("{}[{}]", category, i), num_bits)?;
 
             let ident = INVENTORY_SERIAL_DB
                 .get_part_ident(category, part_idx)
@@ -976,6 +1183,66 @@ mod tests {
         assert_eq!(decrypted, encrypted_from_base64);
     }
 
+    #[test]
+    fn test_serial_errors() {
+        let serial_number: Vec<u8> = vec![
+            3, 7, 104, 235, 106, 81, 127, 63, 184, 231, 198, 167, 96, 179, 97, 24, 224, 171, 102,
+            232, 245, 72, 182, 213, 98,
+        ];
+
+        let mut bad_version = serial_number.clone();
+        bad_version[0] = 5;
+
+        let error = Bl3Item::from_serial_bytes(&bad_version, None).unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+
+        assert_eq!(error.field, "serial_version");
+        assert_eq!(error.position, Position::Byte(0));
+
+        let mut bad_data = serial_number;
+        bad_data[10] ^= 0xFF;
+
+        let error = Bl3Item::from_serial_bytes(&bad_data, None).unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+
+        assert_eq!(error.field, "checksum");
+        assert_eq!(error.position, Position::Byte(5));
+    }
+
+    #[test]
+    fn test_diff() {
+        let item =
+            Bl3Item::from_serial_base64("BL3(AwAAAABmboC7I9xAEzwShMJVX8nPYwsAAA==)").unwrap();
+
+        assert!(item.diff(&item).is_empty());
+
+        let mut other = item.clone();
+
+        let removed_part = other.item_parts.as_ref().unwrap().parts[3].clone();
+        let removed_anointment = other.item_parts.as_ref().unwrap().generic_parts[0].clone();
+
+        other.remove_part(&removed_part).unwrap();
+        other.remove_generic_part(&removed_anointment).unwrap();
+        other.set_level(72).unwrap();
+
+        let diff = item.diff(&other);
+
+        assert_eq!(diff.level, Some((item.level(), 72)));
+        assert_eq!(diff.balance, None);
+        assert_eq!(diff.manufacturer, None);
+        assert_eq!(diff.rarity, None);
+        assert_eq!(diff.parts.len(), 1);
+        assert!(diff.parts[0].added.is_empty());
+        assert_eq!(diff.parts[0].removed, vec![removed_part]);
+        assert!(diff.anointments_added.is_empty());
+        assert_eq!(diff.anointments_removed, vec![removed_anointment]);
+
+        let reverse_diff = other.diff(&item);
+
+        assert_eq!(reverse_diff.parts[0].added, diff.parts[0].removed);
+        assert_eq!(reverse_diff.anointments_added, diff.anointments_removed);
+    }
+
     #[test]
     fn test_decrypt_base64() {
         Bl3Item::from_serial_base64("bl3(BMo1YGLGQ0MGYsI1/FbX0bJzzEAlJV/zmj/7qVR3P7k=)").unwrap();
@@ -3,20 +3,24 @@ use std::fmt::Formatter;
 use std::io::Write;
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use byteorder::{LittleEndian, WriteBytesExt};
+use derivative::Derivative;
+use once_cell::sync::OnceCell;
 
 use crate::bl3_profile::profile_data::ProfileData;
+use crate::file_diff::FileDiff;
 use crate::file_helper::FileData;
 use crate::game_data::{
     PROFILE_ECHO_THEMES, PROFILE_ECHO_THEMES_DEFAULTS, PROFILE_EMOTES, PROFILE_EMOTES_DEFAULTS,
     PROFILE_HEADS, PROFILE_HEADS_DEFAULTS, PROFILE_ROOM_DECORATIONS, PROFILE_SKINS,
     PROFILE_SKINS_DEFAULTS, PROFILE_WEAPON_SKINS, PROFILE_WEAPON_TRINKETS,
 };
-use crate::models::CustomFormatData;
-use crate::parser::{decrypt, encrypt, HeaderType};
+use crate::models::{CustomFormatData, GvasHeader};
+use crate::parser::{decrypt_bytes, encrypt, HeaderType, PayloadFormat};
 use crate::protos::oak_profile::Profile;
-use crate::{file_helper, parser};
+use crate::round_trip::RoundTripReport;
+use crate::{file_diff, file_helper, parser, round_trip};
 
 pub mod guardian_reward;
 pub mod profile_currency;
@@ -26,7 +30,8 @@ pub mod sdu;
 pub mod skins;
 pub mod util;
 
-#[derive(Debug, Clone, Default, Eq, Ord, PartialOrd)]
+#[derive(Derivative)]
+#[derivative(Debug, Clone, Default, Eq, Ord, PartialOrd)]
 pub struct Bl3Profile {
     pub file_name: String,
     pub save_game_version: u32,
@@ -41,6 +46,11 @@ pub struct Bl3Profile {
     pub custom_format_data: Vec<CustomFormatData>,
     pub save_game_type: String,
     pub header_type: HeaderType,
+    pub payload_format: PayloadFormat,
+    /// Only worked out when it's needed, as re-encoding every file would slow down loading a
+    /// saves folder.
+    #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore")]
+    round_trip: OnceCell<RoundTripReport>,
     pub profile_data: ProfileData,
 }
 
@@ -52,9 +62,19 @@ impl std::cmp::PartialEq for Bl3Profile {
 
 impl Bl3Profile {
     pub fn from_file_data(file_data: &FileData, header_type: HeaderType) -> Result<Self> {
-        let remaining_data = file_data.remaining_data;
+        let data = decrypt_bytes(file_data.remaining_data, &header_type)?;
 
-        let profile: Profile = decrypt(remaining_data, &header_type)?;
+        Self::from_payload(file_data, &data, header_type, PayloadFormat::Encrypted)
+    }
+
+    /// Reads the already decrypted protobuf `data`, `file_data` is only used for its header.
+    pub fn from_payload(
+        file_data: &FileData,
+        data: &[u8],
+        header_type: HeaderType,
+        payload_format: PayloadFormat,
+    ) -> Result<Self> {
+        let profile: Profile = protobuf::Message::parse_from_bytes(data)?;
 
         let profile_data = ProfileData::from_profile(profile)?;
 
@@ -93,6 +113,8 @@ impl Bl3Profile {
             custom_format_data,
             save_game_type,
             header_type,
+            payload_format,
+            round_trip: OnceCell::new(),
             profile_data,
         })
     }
@@ -103,37 +125,152 @@ impl Bl3Profile {
         Self::from_file_data(&file_data, header_type)
     }
 
-    pub fn as_bytes(&self) -> Result<(Vec<u8>, Bl3Profile)> {
-        let mut output = Vec::new();
-
-        output.write_all(b"GVAS")?;
-        output.write_u32::<LittleEndian>(self.save_game_version)?;
-        output.write_u32::<LittleEndian>(self.package_version)?;
-        output.write_u16::<LittleEndian>(self.engine_major)?;
-        output.write_u16::<LittleEndian>(self.engine_minor)?;
-        output.write_u16::<LittleEndian>(self.engine_patch)?;
-        output.write_u32::<LittleEndian>(self.engine_build)?;
-        parser::write_str(&mut output, &self.build_id)?;
-        output.write_u32::<LittleEndian>(self.custom_format_version)?;
-        output.write_u32::<LittleEndian>(self.custom_format_data_count)?;
-
-        for cfd in &self.custom_format_data {
-            output.write_all(&cfd.guid)?;
-            output.write_u32::<LittleEndian>(cfd.entry)?;
+    /// Reads a file written in `payload_format`, a raw payload has no header so it is left empty.
+    pub fn from_bytes_as(
+        file_location: &Path,
+        data: &[u8],
+        header_type: HeaderType,
+        payload_format: PayloadFormat,
+    ) -> Result<Self> {
+        match payload_format {
+            PayloadFormat::Encrypted => Self::from_bytes(file_location, data, header_type),
+            PayloadFormat::Decrypted => {
+                let file_data = file_helper::read_bytes(file_location, data)?;
+
+                Self::from_payload(
+                    &file_data,
+                    file_data.remaining_data,
+                    header_type,
+                    payload_format,
+                )
+            }
+            PayloadFormat::Raw => {
+                let file_data = file_helper::raw_file_data(file_location, data, header_type);
+
+                Self::from_payload(&file_data, data, header_type, payload_format)
+            }
+        }
+    }
+
+    /// Compare this file against `other`, anything "added" exists on `other` but not on this file.
+    pub fn diff(&self, other: &Bl3Profile) -> FileDiff {
+        file_diff::diff_profiles(self, other)
+    }
+
+    pub fn header(&self) -> GvasHeader {
+        GvasHeader {
+            save_game_version: self.save_game_version,
+            package_version: self.package_version,
+            engine_major: self.engine_major,
+            engine_minor: self.engine_minor,
+            engine_patch: self.engine_patch,
+            engine_build: self.engine_build,
+            build_id: self.build_id.clone(),
+            custom_format_version: self.custom_format_version,
+            custom_format_data: self.custom_format_data.clone(),
         }
+    }
+
+    /// Replaces the header, such as with one copied from a file written by a newer game build.
+    pub fn set_header(&mut self, header: GvasHeader) {
+        self.save_game_version = header.save_game_version;
+        self.package_version = header.package_version;
+        self.engine_major = header.engine_major;
+        self.engine_minor = header.engine_minor;
+        self.engine_patch = header.engine_patch;
+        self.engine_build = header.engine_build;
+        self.build_id = header.build_id;
+        self.custom_format_version = header.custom_format_version;
+        self.custom_format_data_count = header.custom_format_data.len() as u32;
+        self.custom_format_data = header.custom_format_data;
+    }
+
+    /// Checks that `data` reads back as it was written without reading it into a profile.
+    pub fn verify_bytes(
+        file_name: &Path,
+        data: &[u8],
+        header_type: HeaderType,
+        payload_format: PayloadFormat,
+    ) -> Result<()> {
+        file_helper::verify_bytes::<Profile>(file_name, data, header_type, payload_format)
+    }
+
+    /// Checks whether anything read from the file would be lost by saving it. This is worked out
+    /// the first time it's asked for, which is normally when the file is opened, so edits made
+    /// since then aren't part of it.
+    pub fn round_trip(&self) -> Result<&RoundTripReport> {
+        self.round_trip
+            .get_or_try_init(|| RoundTripReport::new(&self.profile_data.profile))
+    }
 
-        parser::write_str(&mut output, &self.save_game_type)?;
+    pub fn as_bytes(&self) -> Result<(Vec<u8>, Bl3Profile)> {
+        let round_trip = self.round_trip()?;
+
+        if !round_trip.is_lossless() {
+            bail!(
+                "refusing to save as this file could not be fully read, {}",
+                round_trip.describe_lost_fields()
+            );
+        }
 
         let mut data = protobuf::Message::write_to_bytes(&self.profile_data.profile)?;
 
-        encrypt(&mut data, self.header_type)?;
+        round_trip::verify_encoded(&self.profile_data.profile, &data)?;
+
+        let output = if self.payload_format == PayloadFormat::Raw {
+            data
+        } else {
+            if self.build_id.is_empty() {
+                bail!(
+                    "{} has no header, copy one from another file before saving it as a .sav",
+                    self.file_name
+                );
+            }
+
+            let mut output = Vec::new();
+
+            output.write_all(b"GVAS")?;
+            output.write_u32::<LittleEndian>(self.save_game_version)?;
+            output.write_u32::<LittleEndian>(self.package_version)?;
+            output.write_u16::<LittleEndian>(self.engine_major)?;
+            output.write_u16::<LittleEndian>(self.engine_minor)?;
+            output.write_u16::<LittleEndian>(self.engine_patch)?;
+            output.write_u32::<LittleEndian>(self.engine_build)?;
+            parser::write_str(&mut output, &self.build_id)?;
+            output.write_u32::<LittleEndian>(self.custom_format_version)?;
+            output.write_u32::<LittleEndian>(self.custom_format_data_count)?;
+
+            for cfd in &self.custom_format_data {
+                output.write_all(&cfd.guid)?;
+                output.write_u32::<LittleEndian>(cfd.entry)?;
+            }
+
+            parser::write_str(&mut output, &self.save_game_type)?;
+
+            if self.payload_format == PayloadFormat::Encrypted {
+                encrypt(&mut data, self.header_type)?;
+            }
+
+            output.write_u32::<LittleEndian>(data.len() as u32)?;
+            output.append(&mut data);
+
+            output
+        };
+
+        //Now check it can be read back also - there's no point making an invalid save
+        Self::verify_bytes(
+            Path::new(&self.file_name),
+            &output,
+            self.header_type,
+            self.payload_format,
+        )?;
 
-        output.write_u32::<LittleEndian>(data.len() as u32)?;
-        output.append(&mut data);
+        // The payload reads back as exactly what we have, only the header is dropped for raw files
+        let mut new_profile = self.clone();
 
-        //Now try re-reading it also - there's no point making an invalid save
-        let file_name = Path::new(&self.file_name);
-        let new_profile = Self::from_bytes(file_name, &output, self.header_type)?;
+        if self.payload_format == PayloadFormat::Raw {
+            new_profile.set_header(GvasHeader::default());
+        }
 
         Ok((output, new_profile))
     }
@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use derivative::Derivative;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use once_cell::sync::OnceCell;
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use strum::{EnumMessage, IntoEnumIterator};
 
 use crate::bl3_item::{Bl3Item, ItemFlags};
@@ -36,6 +37,9 @@ use crate::vehicle_data::{VehicleData, VehicleSubType, VehicleType};
 
 pub const MAX_CHARACTER_LEVEL: usize = 72;
 
+/// A character from a save, only the summary shown when picking a file is read up front. The
+/// rest is worked out from `character` the first time it's used, which is normally when the save
+/// is opened.
 #[derive(Derivative)]
 #[derivative(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
 pub struct CharacterData {
@@ -45,11 +49,21 @@ pub struct CharacterData {
     player_level: i32,
     ability_points: i32,
     guardian_rank: i32,
+    money: i32,
+    eridium: i32,
+    #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore")]
+    details: OnceCell<CharacterDetails>,
+    #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore")]
+    details_error: OnceCell<String>,
+}
+
+/// The parts of a character that are only needed for editing, decoding every item is the most
+/// expensive part of reading a save.
+#[derive(Debug, Clone, Default)]
+struct CharacterDetails {
     head_skin_selected: GameDataKv,
     character_skin_selected: GameDataKv,
     echo_theme_selected: GameDataKv,
-    money: i32,
-    eridium: i32,
     playthroughs: Vec<Playthrough>,
     unlockable_inventory_slots: Vec<InventorySlotData>,
     sdu_slots: Vec<SaveSduSlotData>,
@@ -57,6 +71,8 @@ pub struct CharacterData {
     challenge_milestones: Vec<ChallengeData>,
     vehicle_data: [VehicleData; 12],
     inventory_items: Vec<Bl3Item>,
+    /// Each item with its slot on the rack, slots with items that can't be read are skipped.
+    gun_rack_items: Vec<(usize, Bl3Item)>,
 }
 
 impl CharacterData {
@@ -83,6 +99,58 @@ impl CharacterData {
             .map(|g| g.guardian_rank)
             .unwrap_or(0);
 
+        let money = currency_amount_from_character(&character, &Currency::Money);
+        let eridium = currency_amount_from_character(&character, &Currency::Eridium);
+
+        Ok(Self {
+            character,
+            player_class,
+            player_level,
+            ability_points,
+            guardian_rank,
+            money,
+            eridium,
+            details: OnceCell::new(),
+            details_error: OnceCell::new(),
+        })
+    }
+
+    /// Reads the rest of the character now rather than when it's first used, failing if it can't
+    /// be read. This should be done before the save is edited.
+    pub fn load_details(&self) -> Result<()> {
+        self.details();
+
+        match self.details_error.get() {
+            Some(e) => bail!("{}", e),
+            None => Ok(()),
+        }
+    }
+
+    /// A character whose details can't be read is treated as having none, `load_details` reports
+    /// why when the save is opened.
+    fn details(&self) -> &CharacterDetails {
+        self.details.get_or_init(|| {
+            CharacterDetails::from_character(&self.character, self.player_class).unwrap_or_else(
+                |e| {
+                    let _ = self.details_error.set(format!("{:#}", e));
+
+                    CharacterDetails::default()
+                },
+            )
+        })
+    }
+
+    fn details_mut(&mut self) -> &mut CharacterDetails {
+        self.details();
+
+        self.details
+            .get_mut()
+            .expect("character details were just read")
+    }
+}
+
+impl CharacterDetails {
+    fn from_character(character: &Character, player_class: PlayerClass) -> Result<Self> {
         let available_head_skins = PROFILE_HEADS_DEFAULTS
             .par_iter()
             .chain(PROFILE_HEADS.par_iter())
@@ -131,9 +199,7 @@ impl CharacterData {
             .map(|s| s.to_owned())
             .unwrap_or(PROFILE_ECHO_THEMES_DEFAULTS[0]);
 
-        let money = currency_amount_from_character(&character, &Currency::Money);
-        let eridium = currency_amount_from_character(&character, &Currency::Eridium);
-        let playthroughs = Playthrough::playthroughs_from_character(&character)?;
+        let playthroughs = Playthrough::playthroughs_from_character(character)?;
 
         let mut unlockable_inventory_slots = character
             .equipped_inventory_list
@@ -349,17 +415,22 @@ impl CharacterData {
             })
             .collect::<Vec<_>>();
 
+        let gun_rack_items = character
+            .get_crew_quarters_gun_rack()
+            .rack_save_data
+            .par_iter()
+            .enumerate()
+            .filter_map(|(slot, i)| {
+                Bl3Item::from_serial_bytes(&i.encrypted_serial_number, None)
+                    .ok()
+                    .map(|item| (slot, item))
+            })
+            .collect::<Vec<_>>();
+
         Ok(Self {
-            character,
-            player_class,
-            player_level,
-            ability_points,
-            guardian_rank,
             head_skin_selected,
             character_skin_selected,
             echo_theme_selected,
-            money,
-            eridium,
             playthroughs,
             unlockable_inventory_slots,
             sdu_slots,
@@ -367,9 +438,12 @@ impl CharacterData {
             challenge_milestones,
             vehicle_data,
             inventory_items,
+            gun_rack_items,
         })
     }
+}
 
+impl CharacterData {
     pub fn player_class(&self) -> PlayerClass {
         self.player_class
     }
@@ -561,11 +635,11 @@ impl CharacterData {
     }
 
     pub fn head_skin_selected(&self) -> GameDataKv {
-        self.head_skin_selected
+        self.details().head_skin_selected
     }
 
     pub fn set_head_skin_selected(&mut self, head_skin_selected: &GameDataKv) {
-        let curr_head_skin_selected = self.head_skin_selected.ident;
+        let curr_head_skin_selected = self.details().head_skin_selected.ident;
         let head_skin_selected_id = head_skin_selected.ident.to_owned();
 
         if let Some(curr_head) = self
@@ -581,15 +655,15 @@ impl CharacterData {
                 .push(head_skin_selected_id)
         }
 
-        self.head_skin_selected = head_skin_selected.to_owned();
+        self.details_mut().head_skin_selected = head_skin_selected.to_owned();
     }
 
     pub fn character_skin_selected(&self) -> GameDataKv {
-        self.character_skin_selected
+        self.details().character_skin_selected
     }
 
     pub fn set_character_skin_selected(&mut self, character_skin_selected: &GameDataKv) {
-        let curr_character_skin_selected = self.character_skin_selected.ident;
+        let curr_character_skin_selected = self.details().character_skin_selected.ident;
         let character_skin_selected_id = character_skin_selected.ident.to_owned();
 
         if let Some(curr_skin) = self
@@ -605,15 +679,15 @@ impl CharacterData {
                 .push(character_skin_selected_id)
         }
 
-        self.character_skin_selected = character_skin_selected.to_owned();
+        self.details_mut().character_skin_selected = character_skin_selected.to_owned();
     }
 
     pub fn echo_theme_selected(&self) -> GameDataKv {
-        self.echo_theme_selected
+        self.details().echo_theme_selected
     }
 
     pub fn set_echo_theme_selected(&mut self, echo_theme_selected: &GameDataKv) {
-        let curr_echo_theme_selected = self.echo_theme_selected.ident;
+        let curr_echo_theme_selected = self.details().echo_theme_selected.ident;
         let echo_theme_selected_id = echo_theme_selected.ident.to_owned();
 
         if let Some(echo_theme) = self
@@ -629,7 +703,7 @@ impl CharacterData {
                 .push(echo_theme_selected_id)
         }
 
-        self.echo_theme_selected = echo_theme_selected.to_owned();
+        self.details_mut().echo_theme_selected = echo_theme_selected.to_owned();
     }
 
     pub fn money(&self) -> i32 {
@@ -675,11 +749,11 @@ impl CharacterData {
     }
 
     pub fn playthroughs(&self) -> &Vec<Playthrough> {
-        &self.playthroughs
+        &self.details().playthroughs
     }
 
     pub fn unlockable_inventory_slots(&self) -> &Vec<InventorySlotData> {
-        &self.unlockable_inventory_slots
+        &self.details().unlockable_inventory_slots
     }
 
     pub fn remove_inventory_slot_if_exists(
@@ -699,16 +773,19 @@ impl CharacterData {
         }
 
         if let Some(current_slot) = self
+            .details_mut()
             .unlockable_inventory_slots
             .iter_mut()
             .find(|i| i.slot == *inventory_slot)
         {
             current_slot.unlocked = false;
         } else {
-            self.unlockable_inventory_slots.push(InventorySlotData {
-                slot: inventory_slot.to_owned(),
-                unlocked: false,
-            });
+            self.details_mut()
+                .unlockable_inventory_slots
+                .push(InventorySlotData {
+                    slot: inventory_slot.to_owned(),
+                    unlocked: false,
+                });
         }
 
         Ok(())
@@ -752,23 +829,26 @@ impl CharacterData {
         }
 
         if let Some(current_slot) = self
+            .details_mut()
             .unlockable_inventory_slots
             .iter_mut()
             .find(|i| i.slot == *inventory_slot)
         {
             current_slot.unlocked = true;
         } else {
-            self.unlockable_inventory_slots.push(InventorySlotData {
-                slot: inventory_slot.to_owned(),
-                unlocked: true,
-            });
+            self.details_mut()
+                .unlockable_inventory_slots
+                .push(InventorySlotData {
+                    slot: inventory_slot.to_owned(),
+                    unlocked: true,
+                });
         }
 
         Ok(())
     }
 
     pub fn sdu_slots(&self) -> &Vec<SaveSduSlotData> {
-        &self.sdu_slots
+        &self.details().sdu_slots
     }
 
     pub fn set_sdu_slot(&mut self, sdu_slot: &SaveSduSlot, level: i32) {
@@ -790,10 +870,12 @@ impl CharacterData {
             })
         }
 
-        if let Some(current_slot) = self.sdu_slots.iter_mut().find(|i| i.sdu == *sdu_slot) {
+        let details = self.details_mut();
+
+        if let Some(current_slot) = details.sdu_slots.iter_mut().find(|i| i.sdu == *sdu_slot) {
             current_slot.current = level;
         } else {
-            self.sdu_slots.push(SaveSduSlotData {
+            details.sdu_slots.push(SaveSduSlotData {
                 sdu: sdu_slot.to_owned(),
                 current: level,
                 max: sdu_slot.maximum(),
@@ -802,7 +884,7 @@ impl CharacterData {
     }
 
     pub fn ammo_pools(&self) -> &Vec<AmmoPoolData> {
-        &self.ammo_pools
+        &self.details().ammo_pools
     }
 
     pub fn set_ammo_pool(&mut self, ammo_pool: &AmmoPool, amount: i32) -> Result<()> {
@@ -817,10 +899,12 @@ impl CharacterData {
 
         pool.amount = amount as f32;
 
-        if let Some(current_pool) = self.ammo_pools.iter_mut().find(|i| i.pool == *ammo_pool) {
+        let details = self.details_mut();
+
+        if let Some(current_pool) = details.ammo_pools.iter_mut().find(|i| i.pool == *ammo_pool) {
             current_pool.current = amount;
         } else {
-            self.ammo_pools.push(AmmoPoolData {
+            details.ammo_pools.push(AmmoPoolData {
                 pool: ammo_pool.to_owned(),
                 current: amount,
                 max: ammo_pool.maximum(),
@@ -831,11 +915,11 @@ impl CharacterData {
     }
 
     pub fn challenge_milestones(&self) -> &Vec<ChallengeData> {
-        &self.challenge_milestones
+        &self.details().challenge_milestones
     }
 
     pub fn vehicle_data(&self) -> &[VehicleData; 12] {
-        &self.vehicle_data
+        &self.details().vehicle_data
     }
 
     pub fn unlock_vehicle_data(&mut self, vehicle_type: &VehicleType) {
@@ -875,6 +959,7 @@ impl CharacterData {
         }
 
         let existing_vd = self
+            .details_mut()
             .vehicle_data
             .iter_mut()
             .find(|vd| vd.vehicle_type == *vehicle_type);
@@ -885,11 +970,16 @@ impl CharacterData {
     }
 
     pub fn inventory_items(&self) -> &Vec<Bl3Item> {
-        &self.inventory_items
+        &self.details().inventory_items
     }
 
     pub fn inventory_items_mut(&mut self) -> &mut Vec<Bl3Item> {
-        &mut self.inventory_items
+        &mut self.details_mut().inventory_items
+    }
+
+    /// The items on the gun rack and the slot each one is in.
+    pub fn gun_rack_items(&self) -> &Vec<(usize, Bl3Item)> {
+        &self.details().gun_rack_items
     }
 
     pub fn create_inventory_item(
@@ -929,8 +1019,10 @@ impl CharacterData {
             self.character.inventory_items.remove(index);
         }
 
-        if index < self.inventory_items.len() {
-            self.inventory_items.remove(index);
+        let inventory_items = &mut self.details_mut().inventory_items;
+
+        if index < inventory_items.len() {
+            inventory_items.remove(index);
         }
     }
 
@@ -939,7 +1031,7 @@ impl CharacterData {
 
         self.character.inventory_items.push(new_oak_item);
 
-        self.inventory_items.push(item.to_owned());
+        self.details_mut().inventory_items.push(item.to_owned());
 
         Ok(())
     }
@@ -956,7 +1048,9 @@ impl CharacterData {
             .inventory_items
             .insert(item_index, new_oak_item);
 
-        self.inventory_items.insert(item_index, item.to_owned());
+        self.details_mut()
+            .inventory_items
+            .insert(item_index, item.to_owned());
 
         Ok(())
     }
@@ -1,16 +1,21 @@
 use std::io::Write;
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use byteorder::{LittleEndian, WriteBytesExt};
+use derivative::Derivative;
+use once_cell::sync::OnceCell;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::bl3_save::character_data::CharacterData;
 use crate::bl3_save::inventory_slot::InventorySlot;
+use crate::file_diff::FileDiff;
 use crate::file_helper::FileData;
-use crate::models::CustomFormatData;
-use crate::parser::{decrypt, encrypt, HeaderType};
-use crate::{file_helper, parser};
+use crate::models::{CustomFormatData, GvasHeader};
+use crate::parser::{decrypt_bytes, encrypt, HeaderType, PayloadFormat};
+use crate::protos::oak_save::Character;
+use crate::round_trip::RoundTripReport;
+use crate::{file_diff, file_helper, parser, round_trip};
 
 pub mod ammo;
 pub mod challenge_data;
@@ -24,7 +29,8 @@ pub mod playthrough;
 pub mod sdu;
 pub mod util;
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Derivative)]
+#[derivative(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Bl3Save {
     pub file_name: String,
     pub save_game_version: u32,
@@ -39,14 +45,29 @@ pub struct Bl3Save {
     pub custom_format_data: Vec<CustomFormatData>,
     pub save_game_type: String,
     pub header_type: HeaderType,
+    pub payload_format: PayloadFormat,
+    /// Only worked out when it's needed, as re-encoding every file would slow down loading a
+    /// saves folder.
+    #[derivative(PartialEq = "ignore", Ord = "ignore", PartialOrd = "ignore")]
+    round_trip: OnceCell<RoundTripReport>,
     pub character_data: CharacterData,
 }
 
 impl Bl3Save {
     pub fn from_file_data(file_data: &FileData, header_type: HeaderType) -> Result<Self> {
-        let remaining_data = file_data.remaining_data;
+        let data = decrypt_bytes(file_data.remaining_data, &header_type)?;
 
-        let character = decrypt(remaining_data, &header_type)?;
+        Self::from_payload(file_data, &data, header_type, PayloadFormat::Encrypted)
+    }
+
+    /// Reads the already decrypted protobuf `data`, `file_data` is only used for its header.
+    pub fn from_payload(
+        file_data: &FileData,
+        data: &[u8],
+        header_type: HeaderType,
+        payload_format: PayloadFormat,
+    ) -> Result<Self> {
+        let character: Character = protobuf::Message::parse_from_bytes(data)?;
 
         let character_data = CharacterData::from_character(character)?;
 
@@ -85,6 +106,8 @@ impl Bl3Save {
             custom_format_data,
             save_game_type,
             header_type,
+            payload_format,
+            round_trip: OnceCell::new(),
             character_data,
         })
     }
@@ -95,37 +118,165 @@ impl Bl3Save {
         Self::from_file_data(&file_data, header_type)
     }
 
-    pub fn as_bytes(&self) -> Result<(Vec<u8>, Bl3Save)> {
-        let mut output = Vec::new();
-
-        output.write_all(b"GVAS")?;
-        output.write_u32::<LittleEndian>(self.save_game_version)?;
-        output.write_u32::<LittleEndian>(self.package_version)?;
-        output.write_u16::<LittleEndian>(self.engine_major)?;
-        output.write_u16::<LittleEndian>(self.engine_minor)?;
-        output.write_u16::<LittleEndian>(self.engine_patch)?;
-        output.write_u32::<LittleEndian>(self.engine_build)?;
-        parser::write_str(&mut output, &self.build_id)?;
-        output.write_u32::<LittleEndian>(self.custom_format_version)?;
-        output.write_u32::<LittleEndian>(self.custom_format_data_count)?;
-
-        for cfd in &self.custom_format_data {
-            output.write_all(&cfd.guid)?;
-            output.write_u32::<LittleEndian>(cfd.entry)?;
+    /// Reads a file written in `payload_format`, a raw payload has no header so it is left empty.
+    pub fn from_bytes_as(
+        file_name: &Path,
+        data: &[u8],
+        header_type: HeaderType,
+        payload_format: PayloadFormat,
+    ) -> Result<Self> {
+        match payload_format {
+            PayloadFormat::Encrypted => Self::from_bytes(file_name, data, header_type),
+            PayloadFormat::Decrypted => {
+                let file_data = file_helper::read_bytes(file_name, data)?;
+
+                Self::from_payload(
+                    &file_data,
+                    file_data.remaining_data,
+                    header_type,
+                    payload_format,
+                )
+            }
+            PayloadFormat::Raw => {
+                let file_data = file_helper::raw_file_data(file_name, data, header_type);
+
+                Self::from_payload(&file_data, data, header_type, payload_format)
+            }
+        }
+    }
+
+    /// Compare this file against `other`, anything "added" exists on `other` but not on this file.
+    pub fn diff(&self, other: &Bl3Save) -> FileDiff {
+        file_diff::diff_saves(self, other)
+    }
+
+    /// A copy of this save in slot `save_game_id`, named the way the game expects for that slot.
+    pub fn with_slot(&self, save_game_id: u32) -> Bl3Save {
+        let mut save = self.clone();
+
+        save.character_data.character.save_game_id = save_game_id;
+        save.file_name = Path::new(&self.header_type.platform().save_file_name(save_game_id))
+            .with_extension(self.payload_format.extension())
+            .to_string_lossy()
+            .to_string();
+
+        save
+    }
+
+    pub fn header(&self) -> GvasHeader {
+        GvasHeader {
+            save_game_version: self.save_game_version,
+            package_version: self.package_version,
+            engine_major: self.engine_major,
+            engine_minor: self.engine_minor,
+            engine_patch: self.engine_patch,
+            engine_build: self.engine_build,
+            build_id: self.build_id.clone(),
+            custom_format_version: self.custom_format_version,
+            custom_format_data: self.custom_format_data.clone(),
         }
+    }
 
-        parser::write_str(&mut output, &self.save_game_type)?;
+    /// Replaces the header, such as with one copied from a file written by a newer game build.
+    pub fn set_header(&mut self, header: GvasHeader) {
+        self.save_game_version = header.save_game_version;
+        self.package_version = header.package_version;
+        self.engine_major = header.engine_major;
+        self.engine_minor = header.engine_minor;
+        self.engine_patch = header.engine_patch;
+        self.engine_build = header.engine_build;
+        self.build_id = header.build_id;
+        self.custom_format_version = header.custom_format_version;
+        self.custom_format_data_count = header.custom_format_data.len() as u32;
+        self.custom_format_data = header.custom_format_data;
+    }
+
+    /// Checks that `data` reads back as it was written without reading it into a save.
+    pub fn verify_bytes(
+        file_name: &Path,
+        data: &[u8],
+        header_type: HeaderType,
+        payload_format: PayloadFormat,
+    ) -> Result<()> {
+        file_helper::verify_bytes::<Character>(file_name, data, header_type, payload_format)
+    }
+
+    /// Checks whether anything read from the file would be lost by saving it. This is worked out
+    /// the first time it's asked for, which is normally when the file is opened, so edits made
+    /// since then aren't part of it.
+    pub fn round_trip(&self) -> Result<&RoundTripReport> {
+        self.round_trip
+            .get_or_try_init(|| RoundTripReport::new(&self.character_data.character))
+    }
+
+    pub fn as_bytes(&self) -> Result<(Vec<u8>, Bl3Save)> {
+        let round_trip = self.round_trip()?;
+
+        if !round_trip.is_lossless() {
+            bail!(
+                "refusing to save as this file could not be fully read, {}",
+                round_trip.describe_lost_fields()
+            );
+        }
 
         let mut data = protobuf::Message::write_to_bytes(&self.character_data.character)?;
 
-        encrypt(&mut data, self.header_type)?;
+        round_trip::verify_encoded(&self.character_data.character, &data)?;
+
+        let output = if self.payload_format == PayloadFormat::Raw {
+            data
+        } else {
+            if self.build_id.is_empty() {
+                bail!(
+                    "{} has no header, copy one from another file before saving it as a .sav",
+                    self.file_name
+                );
+            }
 
-        output.write_u32::<LittleEndian>(data.len() as u32)?;
-        output.append(&mut data);
+            let mut output = Vec::new();
+
+            output.write_all(b"GVAS")?;
+            output.write_u32::<LittleEndian>(self.save_game_version)?;
+            output.write_u32::<LittleEndian>(self.package_version)?;
+            output.write_u16::<LittleEndian>(self.engine_major)?;
+            output.write_u16::<LittleEndian>(self.engine_minor)?;
+            output.write_u16::<LittleEndian>(self.engine_patch)?;
+            output.write_u32::<LittleEndian>(self.engine_build)?;
+            parser::write_str(&mut output, &self.build_id)?;
+            output.write_u32::<LittleEndian>(self.custom_format_version)?;
+            output.write_u32::<LittleEndian>(self.custom_format_data_count)?;
+
+            for cfd in &self.custom_format_data {
+                output.write_all(&cfd.guid)?;
+                output.write_u32::<LittleEndian>(cfd.entry)?;
+            }
+
+            parser::write_str(&mut output, &self.save_game_type)?;
+
+            if self.payload_format == PayloadFormat::Encrypted {
+                encrypt(&mut data, self.header_type)?;
+            }
+
+            output.write_u32::<LittleEndian>(data.len() as u32)?;
+            output.append(&mut data);
+
+            output
+        };
+
+        //Now check it can be read back also - there's no point making an invalid save
+        Self::verify_bytes(
+            Path::new(&self.file_name),
+            &output,
+            self.header_type,
+            self.payload_format,
+        )?;
 
-        //Now try re-reading it also - there's no point making an invalid save
-        let file_name = Path::new(&self.file_name);
-        let new_save = Self::from_bytes(file_name, &output, self.header_type)?;
+        // The payload reads back as exactly what we have, only the header is dropped for raw files
+        let mut new_save = self.clone();
+
+        if self.payload_format == PayloadFormat::Raw {
+            new_save.set_header(GvasHeader::default());
+        }
 
         Ok((output, new_save))
     }
@@ -282,6 +433,75 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_with_slot() {
+        let filename = Path::new("./test_files/310pc.sav");
+
+        let save_file_data = fs::read(filename).expect("failed to read test_file");
+
+        let bl3_save = Bl3Save::from_bytes(filename, &save_file_data, HeaderType::PcSave)
+            .expect("failed to read test save");
+
+        let moved = bl3_save.with_slot(26);
+
+        assert_eq!(moved.file_name, "1a.sav");
+        assert_eq!(moved.character_data.character.save_game_id, 26);
+
+        let (output, _) = moved.as_bytes().expect("failed to write moved save");
+
+        let reloaded =
+            Bl3Save::from_bytes(Path::new(&moved.file_name), &output, HeaderType::PcSave)
+                .expect("failed to read moved save");
+
+        assert_eq!(reloaded.character_data.character.save_game_id, 26);
+        assert_eq!(
+            reloaded.character_data.character.save_game_guid,
+            bl3_save.character_data.character.save_game_guid
+        );
+    }
+
+    #[test]
+    fn test_load_details() {
+        let filename = Path::new("./test_files/19.sav");
+
+        let save_file_data = fs::read(filename).expect("failed to read test_file");
+
+        let bl3_save = Bl3Save::from_bytes(filename, &save_file_data, HeaderType::PcSave)
+            .expect("failed to read test save");
+
+        let mut character = bl3_save.character_data.character.clone();
+
+        let pool = character
+            .resource_pools
+            .iter_mut()
+            .find(|rp| !rp.resource_path.contains("Eridium"))
+            .expect("save has no ammo pools");
+
+        pool.resource_path = "/Game/NotAnAmmoPool".to_owned();
+
+        // Only the summary is read up front so a save with unknown ammo can still be listed
+        let character_data =
+            CharacterData::from_character(character).expect("failed to read character summary");
+
+        assert_eq!(
+            character_data.player_level(),
+            bl3_save.character_data.player_level()
+        );
+
+        let error = character_data.load_details().unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("failed to read ammo: /Game/NotAnAmmoPool"));
+        assert!(character_data.ammo_pools().is_empty());
+
+        bl3_save
+            .character_data
+            .load_details()
+            .expect("failed to read character details");
+        assert!(!bl3_save.character_data.inventory_items().is_empty());
+    }
+
     #[test]
     fn test_from_data_pc_1() {
         let filename = Path::new("./test_files/19.sav");
@@ -2,17 +2,107 @@ use std::fmt::Debug;
 
 use thiserror::Error;
 
+use crate::parser::HeaderType;
+
 #[derive(Debug, Error)]
 pub enum BL3ParserError<I: Debug> {
     #[error("failed to read {0:?} as could not read - {1:?}")]
     NomError(I, nom::error::ErrorKind),
+    #[error("could not read binary data with {remaining} byte(s) left - {kind:?}")]
+    BinaryData {
+        remaining: usize,
+        kind: nom::error::ErrorKind,
+    },
+    #[error("could not read bit data with {remaining} bit(s) left - {kind:?}")]
+    BitData {
+        remaining: usize,
+        kind: nom::error::ErrorKind,
+    },
+    #[error("expected {expected} but found {actual}")]
+    Unexpected { expected: String, actual: String },
     #[error("failed to parse data due to - {0}")]
     Other(anyhow::Error),
 }
 
+impl<I: Debug> BL3ParserError<I> {
+    pub fn unexpected<E: ToString, A: ToString>(expected: E, actual: A) -> Self {
+        BL3ParserError::Unexpected {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        }
+    }
+}
+
+/// Where in a file or an item serial something couldn't be read.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Position {
+    Byte(usize),
+    Bit(usize),
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Position::Byte(offset) => write!(f, "byte {}", offset),
+            Position::Bit(offset) => write!(f, "bit {}", offset),
+        }
+    }
+}
+
+/// A field that couldn't be read, such as `build_id` or `custom_format_data[2]`.
+#[derive(Debug, Error)]
+#[error("failed to read {field} at {position}: {source}")]
+pub struct ParseError {
+    pub field: String,
+    pub position: Position,
+    pub source: BL3ParserError<String>,
+}
+
+impl ParseError {
+    pub fn new<F: ToString>(field: F, position: Position, source: BL3ParserError<String>) -> Self {
+        ParseError {
+            field: field.to_string(),
+            position,
+            source,
+        }
+    }
+
+    pub fn unexpected<F: ToString, E: ToString, A: ToString>(
+        field: F,
+        position: Position,
+        expected: E,
+        actual: A,
+    ) -> Self {
+        Self::new(
+            field,
+            position,
+            BL3ParserError::unexpected(expected, actual),
+        )
+    }
+}
+
+/// A file couldn't be read as any of the types its header says it could be.
+#[derive(Debug, Error)]
+#[error("Could not recognize file type ({save_game_type}): {}", describe_attempts(.attempts))]
+pub struct FileTypeError {
+    pub save_game_type: String,
+    pub attempts: Vec<(HeaderType, anyhow::Error)>,
+}
+
+fn describe_attempts(attempts: &[(HeaderType, anyhow::Error)]) -> String {
+    attempts
+        .iter()
+        .map(|(header_type, e)| format!("not a {} as {}", header_type, e))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 impl nom::error::ParseError<&[u8]> for BL3ParserError<String> {
-    fn from_error_kind(_: &[u8], kind: nom::error::ErrorKind) -> Self {
-        BL3ParserError::NomError("Binary Data".to_owned(), kind)
+    fn from_error_kind(input: &[u8], kind: nom::error::ErrorKind) -> Self {
+        BL3ParserError::BinaryData {
+            remaining: input.len(),
+            kind,
+        }
     }
 
     fn append(_: &[u8], _: nom::error::ErrorKind, other: Self) -> Self {
@@ -30,9 +120,14 @@ impl<I: Debug> nom::error::ParseError<I> for BL3ParserError<I> {
     }
 }
 
-impl<T> nom::ErrorConvert<BL3ParserError<String>> for nom::error::Error<(&[u8], T)> {
+impl nom::ErrorConvert<BL3ParserError<String>> for nom::error::Error<(&[u8], usize)> {
     fn convert(self) -> BL3ParserError<String> {
-        BL3ParserError::NomError("Bit Data".to_owned(), self.code)
+        let (input, bit_offset) = self.input;
+
+        BL3ParserError::BitData {
+            remaining: (input.len() * 8).saturating_sub(bit_offset),
+            kind: self.code,
+        }
     }
 }
 
@@ -0,0 +1,825 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::bl3_item::{Bl3Item, Bl3ItemDiff};
+use crate::bl3_profile::util::get_checksum_hash;
+use crate::bl3_profile::Bl3Profile;
+use crate::bl3_save::Bl3Save;
+use crate::game_data::{
+    GameDataKv, PROFILE_ECHO_THEMES, PROFILE_EMOTES, PROFILE_HEADS, PROFILE_ROOM_DECORATIONS,
+    PROFILE_SKINS, PROFILE_WEAPON_SKINS, PROFILE_WEAPON_TRINKETS,
+};
+use crate::item_index::ItemLocation;
+use crate::models::GvasHeader;
+use crate::protos::oak_profile::Profile;
+use crate::vehicle_data::{VehicleData, VehicleType};
+
+/// The changes between two versions of a save or profile, in the order they appear in the editor.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct FileDiff {
+    pub fields: Vec<FieldChange>,
+    pub lists: Vec<ListChange>,
+    pub items: Vec<ItemChange>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+impl std::fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ➜ {}", self.field, self.old, self.new)
+    }
+}
+
+/// Entries that were added to or removed from a list, such as completed missions.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ListChange {
+    pub field: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl std::fmt::Display for ListChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.field)?;
+
+        if !self.added.is_empty() {
+            write!(f, " Added {}", self.added.join(", "))?;
+        }
+
+        if !self.added.is_empty() && !self.removed.is_empty() {
+            write!(f, " -")?;
+        }
+
+        if !self.removed.is_empty() {
+            write!(f, " Removed {}", self.removed.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ItemChange {
+    pub location: ItemLocation,
+    pub name: String,
+    /// The serial of the item after the change, or before it for removed items.
+    pub serial: String,
+    pub kind: ItemChangeKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub enum ItemChangeKind {
+    Added,
+    Removed,
+    Edited(Box<Bl3ItemDiff>),
+}
+
+impl std::fmt::Display for ItemChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ItemChangeKind::Added => write!(f, "{}: Added {}", self.location, self.name),
+            ItemChangeKind::Removed => write!(f, "{}: Removed {}", self.location, self.name),
+            ItemChangeKind::Edited(diff) => write!(
+                f,
+                "{}: Edited {} ({})",
+                self.location,
+                self.name,
+                describe_item_diff(diff)
+            ),
+        }
+    }
+}
+
+impl FileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.lists.is_empty() && self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len() + self.lists.len() + self.items.len()
+    }
+
+    /// One line for every change.
+    pub fn summary(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .map(|f| f.to_string())
+            .chain(self.lists.iter().map(|l| l.to_string()))
+            .chain(self.items.iter().map(|i| i.to_string()))
+            .collect()
+    }
+
+    fn field<T: Display + PartialEq>(&mut self, field: &str, old: T, new: T) {
+        if old != new {
+            self.fields.push(FieldChange {
+                field: field.to_owned(),
+                old: old.to_string(),
+                new: new.to_string(),
+            });
+        }
+    }
+
+    fn list<T: Display + PartialEq>(&mut self, field: &str, old: &[T], new: &[T]) {
+        let added = new
+            .iter()
+            .filter(|n| !old.contains(n))
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>();
+
+        let removed = old
+            .iter()
+            .filter(|o| !new.contains(o))
+            .map(|o| o.to_string())
+            .collect::<Vec<_>>();
+
+        if !added.is_empty() || !removed.is_empty() {
+            self.lists.push(ListChange {
+                field: field.to_owned(),
+                added,
+                removed,
+            });
+        }
+    }
+
+    fn header(&mut self, old: &GvasHeader, new: &GvasHeader) {
+        let engine_version = |h: &GvasHeader| {
+            format!(
+                "{}.{}.{}.{}",
+                h.engine_major, h.engine_minor, h.engine_patch, h.engine_build
+            )
+        };
+
+        let custom_format_data = |h: &GvasHeader| {
+            h.custom_format_data
+                .iter()
+                .map(|cfd| {
+                    let guid = cfd
+                        .guid
+                        .iter()
+                        .map(|b| format!("{:02X}", b))
+                        .collect::<String>();
+
+                    format!("{}: {}", guid, cfd.entry)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        self.field(
+            "Save Game Version",
+            old.save_game_version,
+            new.save_game_version,
+        );
+        self.field("Package Version", old.package_version, new.package_version);
+        self.field("Engine Version", engine_version(old), engine_version(new));
+        self.field("Build ID", &old.build_id, &new.build_id);
+        self.field(
+            "Custom Format Version",
+            old.custom_format_version,
+            new.custom_format_version,
+        );
+        self.list(
+            "Custom Format Data",
+            &custom_format_data(old),
+            &custom_format_data(new),
+        );
+    }
+
+    fn unlocked(&mut self, field: &str, old: bool, new: bool) {
+        let unlocked_text = |unlocked| if unlocked { "Unlocked" } else { "Locked" };
+
+        self.field(field, unlocked_text(old), unlocked_text(new));
+    }
+
+    /// Items are matched by their serial so that items which only moved aren't listed. An edited
+    /// item looks like it was removed and added again, these are paired up by their balance.
+    fn items<'a>(
+        &mut self,
+        location: ItemLocation,
+        old: impl IntoIterator<Item = &'a Bl3Item>,
+        new: impl IntoIterator<Item = &'a Bl3Item>,
+    ) {
+        let with_serial = |item| (item_serial(item), item);
+
+        let mut added = new.into_iter().map(with_serial).collect::<Vec<_>>();
+        let mut removed = Vec::new();
+
+        for (serial, item) in old.into_iter().map(with_serial) {
+            // Fall back to comparing the items if the serial couldn't be made
+            let pos = added
+                .iter()
+                .position(|(s, i)| *s == serial && (!s.is_empty() || *i == item));
+
+            match pos {
+                Some(pos) => {
+                    added.remove(pos);
+                }
+                None => removed.push((serial, item)),
+            }
+        }
+
+        for (serial, new_item) in added {
+            let edited_pos = removed
+                .iter()
+                .position(|(_, i)| i.balance_part().ident == new_item.balance_part().ident);
+
+            let kind = match edited_pos {
+                Some(pos) => ItemChangeKind::Edited(Box::new(removed.remove(pos).1.diff(new_item))),
+                None => ItemChangeKind::Added,
+            };
+
+            self.items.push(ItemChange {
+                location,
+                name: item_name(new_item),
+                serial,
+                kind,
+            });
+        }
+
+        for (serial, old_item) in removed {
+            self.items.push(ItemChange {
+                location,
+                name: item_name(old_item),
+                serial,
+                kind: ItemChangeKind::Removed,
+            });
+        }
+    }
+}
+
+/// Compare `old` against `new`, anything "added" exists in `new` but not in `old`.
+pub fn diff_saves(old: &Bl3Save, new: &Bl3Save) -> FileDiff {
+    let mut diff = FileDiff::default();
+
+    diff.field("File", &old.file_name, &new.file_name);
+    diff.field(
+        "Save GUID",
+        &old.character_data.character.save_game_guid,
+        &new.character_data.character.save_game_guid,
+    );
+    diff.field(
+        "Save Slot",
+        old.character_data.character.save_game_id,
+        new.character_data.character.save_game_id,
+    );
+    diff.field("Save Type", old.header_type, new.header_type);
+    diff.field("Save Format", old.payload_format, new.payload_format);
+    diff.header(&old.header(), &new.header());
+
+    let old_data = &old.character_data;
+    let new_data = &new.character_data;
+
+    diff.field(
+        "Name",
+        &old_data.character.preferred_character_name,
+        &new_data.character.preferred_character_name,
+    );
+    diff.field("Class", old_data.player_class(), new_data.player_class());
+    diff.field("Level", old_data.player_level(), new_data.player_level());
+    diff.field(
+        "Experience",
+        old_data.character.experience_points,
+        new_data.character.experience_points,
+    );
+    diff.field(
+        "Skill Points",
+        old_data.ability_points(),
+        new_data.ability_points(),
+    );
+    diff.field(
+        "Guardian Rank",
+        old_data.guardian_rank(),
+        new_data.guardian_rank(),
+    );
+    diff.field(
+        "Head Skin",
+        old_data.head_skin_selected(),
+        new_data.head_skin_selected(),
+    );
+    diff.field(
+        "Character Skin",
+        old_data.character_skin_selected(),
+        new_data.character_skin_selected(),
+    );
+    diff.field(
+        "ECHO Theme",
+        old_data.echo_theme_selected(),
+        new_data.echo_theme_selected(),
+    );
+
+    for new_slot in new_data.unlockable_inventory_slots() {
+        let old_unlocked = old_data
+            .unlockable_inventory_slots()
+            .iter()
+            .any(|s| s.slot == new_slot.slot && s.unlocked);
+
+        diff.unlocked(
+            &format!("{} Slot", new_slot.slot),
+            old_unlocked,
+            new_slot.unlocked,
+        );
+    }
+
+    for new_challenge in new_data.challenge_milestones() {
+        let old_unlocked = old_data
+            .challenge_milestones()
+            .iter()
+            .any(|c| c.challenge == new_challenge.challenge && c.unlocked);
+
+        diff.unlocked(
+            &new_challenge.challenge.to_string(),
+            old_unlocked,
+            new_challenge.unlocked,
+        );
+    }
+
+    for new_sdu in new_data.sdu_slots() {
+        let old_level = old_data
+            .sdu_slots()
+            .iter()
+            .find(|s| s.sdu == new_sdu.sdu)
+            .map(|s| s.current)
+            .unwrap_or_default();
+
+        diff.field(&format!("{} SDU", new_sdu.sdu), old_level, new_sdu.current);
+    }
+
+    for new_ammo in new_data.ammo_pools() {
+        let old_amount = old_data
+            .ammo_pools()
+            .iter()
+            .find(|a| a.pool == new_ammo.pool)
+            .map(|a| a.current)
+            .unwrap_or_default();
+
+        diff.field(
+            &format!("{} Ammo", new_ammo.pool),
+            old_amount,
+            new_ammo.current,
+        );
+    }
+
+    diff.field("Money", old_data.money(), new_data.money());
+    diff.field("Eridium", old_data.eridium(), new_data.eridium());
+
+    let playthroughs_len = old_data
+        .playthroughs()
+        .len()
+        .max(new_data.playthroughs().len());
+
+    for i in 0..playthroughs_len {
+        let old_playthrough = old_data.playthroughs().get(i);
+        let new_playthrough = new_data.playthroughs().get(i);
+
+        let field = |name| format!("Playthrough {} {}", i + 1, name);
+
+        diff.field(
+            &field("Mayhem Level"),
+            old_playthrough.map(|p| p.mayhem_level).unwrap_or_default(),
+            new_playthrough.map(|p| p.mayhem_level).unwrap_or_default(),
+        );
+        diff.field(
+            &field("Current Map"),
+            old_playthrough.map(|p| p.current_map).unwrap_or_default(),
+            new_playthrough.map(|p| p.current_map).unwrap_or_default(),
+        );
+        diff.list(
+            &field("Missions Completed"),
+            old_playthrough
+                .map(|p| p.missions_completed.as_slice())
+                .unwrap_or_default(),
+            new_playthrough
+                .map(|p| p.missions_completed.as_slice())
+                .unwrap_or_default(),
+        );
+        diff.list(
+            &field("Active Missions"),
+            old_playthrough
+                .map(|p| p.active_missions.as_slice())
+                .unwrap_or_default(),
+            new_playthrough
+                .map(|p| p.active_missions.as_slice())
+                .unwrap_or_default(),
+        );
+    }
+
+    for new_vehicle in new_data.vehicle_data() {
+        let old_current = old_data
+            .vehicle_data()
+            .iter()
+            .find(|v| v.vehicle_type == new_vehicle.vehicle_type)
+            .map(|v| v.current)
+            .unwrap_or_default();
+
+        diff.field(
+            &format!("{} Unlocked", vehicle_name(new_vehicle)),
+            old_current,
+            new_vehicle.current,
+        );
+    }
+
+    diff.items(
+        ItemLocation::Inventory,
+        old_data.inventory_items(),
+        new_data.inventory_items(),
+    );
+    diff.items(
+        ItemLocation::GunRack,
+        old_data.gun_rack_items().iter().map(|(_, item)| item),
+        new_data.gun_rack_items().iter().map(|(_, item)| item),
+    );
+
+    diff
+}
+
+/// Compare `old` against `new`, anything "added" exists in `new` but not in `old`.
+pub fn diff_profiles(old: &Bl3Profile, new: &Bl3Profile) -> FileDiff {
+    let mut diff = FileDiff::default();
+
+    diff.field("File", &old.file_name, &new.file_name);
+    diff.field("Profile Type", old.header_type, new.header_type);
+    diff.field("Save Format", old.payload_format, new.payload_format);
+    diff.header(&old.header(), &new.header());
+
+    let old_data = &old.profile_data;
+    let new_data = &new.profile_data;
+
+    diff.field(
+        "Guardian Rank",
+        old_data.guardian_rank(),
+        new_data.guardian_rank(),
+    );
+    diff.field(
+        "Guardian Rank Tokens",
+        old_data.guardian_tokens(),
+        new_data.guardian_tokens(),
+    );
+
+    for new_reward in new_data.guardian_rewards() {
+        let old_current = old_data
+            .guardian_rewards()
+            .iter()
+            .find(|r| r.reward == new_reward.reward)
+            .map(|r| r.current)
+            .unwrap_or_default();
+
+        diff.field(
+            &format!("{} Guardian Reward", new_reward.reward),
+            old_current,
+            new_reward.current,
+        );
+    }
+
+    diff.field(
+        "Borderlands Science Level",
+        old_data.borderlands_science_info().science_level,
+        new_data.borderlands_science_info().science_level,
+    );
+    diff.field(
+        "Borderlands Science Tokens",
+        old_data.borderlands_science_info().tokens,
+        new_data.borderlands_science_info().tokens,
+    );
+
+    for new_sdu in new_data.sdu_slots() {
+        let old_level = old_data
+            .sdu_slots()
+            .iter()
+            .find(|s| s.sdu == new_sdu.sdu)
+            .map(|s| s.current)
+            .unwrap_or_default();
+
+        diff.field(&format!("{} SDU", new_sdu.sdu), old_level, new_sdu.current);
+    }
+
+    let customizations: [(&str, &[GameDataKv]); 4] = [
+        ("Character Skins Unlocked", &PROFILE_SKINS),
+        ("Character Heads Unlocked", &PROFILE_HEADS),
+        ("ECHO Themes Unlocked", &PROFILE_ECHO_THEMES),
+        ("Emotes Unlocked", &PROFILE_EMOTES),
+    ];
+
+    for (field, customizations) in customizations {
+        diff.list(
+            field,
+            &unlocked_customizations(&old_data.profile, customizations),
+            &unlocked_customizations(&new_data.profile, customizations),
+        );
+    }
+
+    diff.list(
+        "Room Decorations Unlocked",
+        &unlocked_room_decorations(&old_data.profile),
+        &unlocked_room_decorations(&new_data.profile),
+    );
+
+    let weapon_customizations: [(&str, &[GameDataKv]); 2] = [
+        ("Weapon Skins Unlocked", &PROFILE_WEAPON_SKINS),
+        ("Weapon Trinkets Unlocked", &PROFILE_WEAPON_TRINKETS),
+    ];
+
+    for (field, customizations) in weapon_customizations {
+        diff.list(
+            field,
+            &unlocked_weapon_customizations(&old_data.profile, customizations),
+            &unlocked_weapon_customizations(&new_data.profile, customizations),
+        );
+    }
+
+    diff.field(
+        "Golden Keys",
+        old_data.golden_keys(),
+        new_data.golden_keys(),
+    );
+    diff.field(
+        "Diamond Keys",
+        old_data.diamond_keys(),
+        new_data.diamond_keys(),
+    );
+    diff.field(
+        "Vault Card 1 Keys",
+        old_data.vault_card_1_keys(),
+        new_data.vault_card_1_keys(),
+    );
+    diff.field(
+        "Vault Card 1 Chests",
+        old_data.vault_card_1_chests(),
+        new_data.vault_card_1_chests(),
+    );
+    diff.field(
+        "Vault Card 2 Keys",
+        old_data.vault_card_2_keys(),
+        new_data.vault_card_2_keys(),
+    );
+    diff.field(
+        "Vault Card 2 Chests",
+        old_data.vault_card_2_chests(),
+        new_data.vault_card_2_chests(),
+    );
+    diff.field(
+        "Vault Card 3 Keys",
+        old_data.vault_card_3_keys(),
+        new_data.vault_card_3_keys(),
+    );
+    diff.field(
+        "Vault Card 3 Chests",
+        old_data.vault_card_3_chests(),
+        new_data.vault_card_3_chests(),
+    );
+
+    diff.items(
+        ItemLocation::Bank,
+        old_data.bank_items(),
+        new_data.bank_items(),
+    );
+    diff.items(
+        ItemLocation::LostLoot,
+        old_data.lost_loot_items(),
+        new_data.lost_loot_items(),
+    );
+
+    diff
+}
+
+// Customizations that aren't in the game data can't be named so they are left out
+fn unlocked_customizations(profile: &Profile, customizations: &[GameDataKv]) -> Vec<GameDataKv> {
+    customizations
+        .iter()
+        .filter(|c| {
+            profile
+                .unlocked_customizations
+                .iter()
+                .any(|uc| uc.customization_asset_path.eq_ignore_ascii_case(c.ident))
+        })
+        .copied()
+        .collect()
+}
+
+fn unlocked_room_decorations(profile: &Profile) -> Vec<GameDataKv> {
+    PROFILE_ROOM_DECORATIONS
+        .iter()
+        .filter(|c| {
+            profile
+                .unlocked_crew_quarters_decorations
+                .iter()
+                .any(|ucd| ucd.decoration_item_asset_path.eq_ignore_ascii_case(c.ident))
+        })
+        .copied()
+        .collect()
+}
+
+fn unlocked_weapon_customizations(
+    profile: &Profile,
+    customizations: &[GameDataKv],
+) -> Vec<GameDataKv> {
+    customizations
+        .iter()
+        .filter(|c| {
+            get_checksum_hash(c.ident)
+                .map(|hash| {
+                    profile
+                        .unlocked_inventory_customization_parts
+                        .iter()
+                        .any(|uic| uic.customization_part_hash as usize == hash)
+                })
+                .unwrap_or(false)
+        })
+        .copied()
+        .collect()
+}
+
+fn item_serial(item: &Bl3Item) -> String {
+    item.get_serial_number_base64(true).unwrap_or_default()
+}
+
+fn item_name(item: &Bl3Item) -> String {
+    let balance_part = item.balance_part();
+
+    let name = balance_part
+        .name
+        .as_ref()
+        .or(balance_part.short_ident.as_ref())
+        .map(|n| n.as_str())
+        .unwrap_or_else(|| {
+            balance_part
+                .ident
+                .rsplit('/')
+                .next()
+                .unwrap_or(&balance_part.ident)
+        });
+
+    format!("{} (Level {})", name, item.level())
+}
+
+fn describe_item_diff(diff: &Bl3ItemDiff) -> String {
+    let mut changes = Vec::new();
+
+    if let Some((old, new)) = &diff.manufacturer {
+        changes.push(format!("Manufacturer: {} ➜ {}", old, new));
+    }
+
+    if let Some((old, new)) = &diff.level {
+        changes.push(format!("Level: {} ➜ {}", old, new));
+    }
+
+    if let Some((old, new)) = &diff.rarity {
+        changes.push(format!("Rarity: {} ➜ {}", old, new));
+    }
+
+    let parts_added = diff.parts.iter().map(|c| c.added.len()).sum::<usize>();
+    let parts_removed = diff.parts.iter().map(|c| c.removed.len()).sum::<usize>();
+
+    if parts_added > 0 {
+        changes.push(format!("{} part(s) added", parts_added));
+    }
+
+    if parts_removed > 0 {
+        changes.push(format!("{} part(s) removed", parts_removed));
+    }
+
+    if !diff.anointments_added.is_empty() || !diff.anointments_removed.is_empty() {
+        changes.push("Anointments changed".to_owned());
+    }
+
+    if changes.is_empty() {
+        // Such as the order of the parts
+        changes.push("Serial changed".to_owned());
+    }
+
+    changes.join(", ")
+}
+
+fn vehicle_name(vehicle_data: &VehicleData) -> String {
+    match &vehicle_data.vehicle_type {
+        VehicleType::Outrunner(sub_type) => format!("Outrunner {}", sub_type),
+        VehicleType::Jetbeast(sub_type) => format!("Jetbeast {}", sub_type),
+        VehicleType::Technical(sub_type) => format!("Technical {}", sub_type),
+        VehicleType::Cyclone(sub_type) => format!("Cyclone {}", sub_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::bl3_profile::skins::{ProfileSkinType, SkinSet};
+    use crate::parser::HeaderType;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_saves() {
+        let filename = Path::new("./test_files/19.sav");
+
+        let old = Bl3Save::from_bytes(
+            filename,
+            &std::fs::read(filename).unwrap(),
+            HeaderType::PcSave,
+        )
+        .expect("failed to read test save");
+
+        assert!(diff_saves(&old, &old).is_empty());
+
+        let mut new = old.clone();
+
+        new.character_data
+            .set_money(old.character_data.money() + 1)
+            .unwrap();
+
+        let mut edited_item = new.character_data.inventory_items()[0].clone();
+        edited_item.set_level(edited_item.level() + 1).unwrap();
+
+        new.character_data
+            .replace_inventory_item(0, 0, &edited_item)
+            .unwrap();
+
+        new.character_data.remove_inventory_item(1);
+
+        let diff = diff_saves(&old, &new);
+
+        assert_eq!(
+            diff.fields,
+            vec![FieldChange {
+                field: "Money".to_owned(),
+                old: old.character_data.money().to_string(),
+                new: new.character_data.money().to_string(),
+            }]
+        );
+
+        assert_eq!(diff.items.len(), 2);
+        assert!(matches!(
+            &diff.items[0].kind,
+            ItemChangeKind::Edited(d) if d.level.is_some()
+        ));
+        assert_eq!(diff.items[1].kind, ItemChangeKind::Removed);
+    }
+
+    #[test]
+    fn test_diff_headers() {
+        let filename = Path::new("./test_files/19.sav");
+
+        let old = Bl3Save::from_bytes(
+            filename,
+            &std::fs::read(filename).unwrap(),
+            HeaderType::PcSave,
+        )
+        .expect("failed to read test save");
+
+        let mut header = old.header();
+        header.build_id = "OAK-PATCHWIN64-999999".to_owned();
+        header.engine_build += 1;
+        header.custom_format_data.pop();
+
+        let mut new = old.clone();
+        new.set_header(header);
+
+        let diff = diff_saves(&old, &new);
+
+        assert_eq!(
+            diff.fields
+                .iter()
+                .map(|f| f.field.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Engine Version", "Build ID"]
+        );
+        assert_eq!(diff.lists.len(), 1);
+        assert_eq!(diff.lists[0].field, "Custom Format Data");
+        assert_eq!(diff.lists[0].removed.len(), 1);
+    }
+
+    #[test]
+    fn test_diff_profiles() {
+        let filename = Path::new("./test_files/1prof.sav");
+
+        let old = Bl3Profile::from_bytes(
+            filename,
+            &std::fs::read(filename).unwrap(),
+            HeaderType::PcProfile,
+        )
+        .expect("failed to read test profile");
+
+        assert!(old.diff(&old).is_empty());
+
+        let mut new = old.clone();
+
+        new.profile_data
+            .unlock_skin_set(&ProfileSkinType::Regular(SkinSet::CharacterSkins));
+
+        let diff = old.diff(&new);
+
+        let skins_unlocked = new.profile_data.character_skins_unlocked()
+            - old.profile_data.character_skins_unlocked();
+
+        assert!(diff.fields.is_empty());
+        assert!(diff.items.is_empty());
+        assert_eq!(diff.lists.len(), 1);
+        assert_eq!(diff.lists[0].field, "Character Skins Unlocked");
+        assert_eq!(diff.lists[0].added.len(), skins_unlocked);
+        assert!(diff.lists[0].removed.is_empty());
+    }
+}
@@ -1,15 +1,20 @@
 use std::fmt::Formatter;
 use std::path::Path;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use nom::Finish;
 
 use crate::bl3_profile::Bl3Profile;
 use crate::bl3_save::Bl3Save;
-use crate::models::CustomFormatData;
+use crate::error::{BL3ParserError, FileTypeError, ParseError, Position};
+use crate::models::{CustomFormatData, GvasHeader};
 use crate::parser::{
-    read_custom_format_data, read_header, read_int, read_short, read_str, HeaderType,
+    could_decrypt, decrypt_bytes, looks_like, read_guid, read_header, read_int, read_short,
+    read_str, HeaderType, PayloadFormat, Platform, PROFILE_GAME_TYPE, SAVE_GAME_TYPE,
 };
+use crate::protos::oak_profile::Profile;
+use crate::protos::oak_save::Character;
+use crate::round_trip::RoundTripReport;
 
 #[derive(Debug, Clone)]
 pub struct FileData<'a> {
@@ -29,26 +34,56 @@ pub struct FileData<'a> {
 }
 
 pub fn read_bytes<'a>(file_location: &'a Path, data: &'a [u8]) -> Result<FileData<'a>> {
-    let (r, _) = read_header(data).finish()?;
-    let (r, file_version) = read_int(r).finish()?;
-    let (r, package_version) = read_int(r).finish()?;
-    let (r, engine_major) = read_short(r).finish()?;
-    let (r, engine_minor) = read_short(r).finish()?;
-    let (r, engine_patch) = read_short(r).finish()?;
-    let (r, engine_build) = read_int(r).finish()?;
-    let (r, build_id) = read_str(r).finish()?;
-    let (r, custom_format_version) = read_int(r).finish()?;
-    let (r, custom_format_data_count) = read_int(r).finish()?;
-    let (r, custom_format_data) = read_custom_format_data(r, custom_format_data_count).finish()?;
-    let (r, save_game_type) = read_str(r).finish()?;
-    let (r, remaining_data_len) = read_int(r).finish()?;
-
-    let data_read = data.len() - r.len();
-
-    let remaining_data = &data[data_read..];
+    let mut r = data;
+
+    read_field(data, &mut r, "header", read_header)?;
+    let file_version = read_field(data, &mut r, "file_version", read_int)?;
+    let package_version = read_field(data, &mut r, "package_version", read_int)?;
+    let engine_major = read_field(data, &mut r, "engine_major", read_short)?;
+    let engine_minor = read_field(data, &mut r, "engine_minor", read_short)?;
+    let engine_patch = read_field(data, &mut r, "engine_patch", read_short)?;
+    let engine_build = read_field(data, &mut r, "engine_build", read_int)?;
+    let build_id = read_field(data, &mut r, "build_id", read_str)?;
+    let custom_format_version = read_field(data, &mut r, "custom_format_version", read_int)?;
+    let custom_format_data_count = read_field(data, &mut r, "custom_format_data_count", read_int)?;
+
+    let mut custom_format_data = Vec::new();
+
+    for i in 0..custom_format_data_count {
+        let guid = read_field(
+            data,
+            &mut r,
+            format!("custom_format_data[{}].guid", i),
+            read_guid,
+        )?;
+        let entry = read_field(
+            data,
+            &mut r,
+            format!("custom_format_data[{}].entry", i),
+            read_int,
+        )?;
+
+        custom_format_data.push(CustomFormatData {
+            guid: guid.to_vec(),
+            entry,
+        });
+    }
+
+    let save_game_type = read_field(data, &mut r, "save_game_type", read_str)?;
+
+    let remaining_data_len_position = Position::Byte(data.len() - r.len());
+    let remaining_data_len = read_field(data, &mut r, "remaining_data_len", read_int)?;
+
+    let remaining_data = r;
 
     if remaining_data.len() != remaining_data_len as usize {
-        bail!("Failed to parse the first part of the file.")
+        return Err(ParseError::unexpected(
+            "remaining_data_len",
+            remaining_data_len_position,
+            format!("{} byte(s) of data", remaining_data_len),
+            format!("{} byte(s)", remaining_data.len()),
+        )
+        .into());
     }
 
     Ok(FileData {
@@ -68,6 +103,138 @@ pub fn read_bytes<'a>(file_location: &'a Path, data: &'a [u8]) -> Result<FileDat
     })
 }
 
+/// Header data for a bare protobuf payload, the header is left empty until one is copied from
+/// another file.
+pub fn raw_file_data<'a>(
+    file_location: &'a Path,
+    data: &'a [u8],
+    header_type: HeaderType,
+) -> FileData<'a> {
+    let save_game_type = match header_type {
+        HeaderType::PcSave | HeaderType::Ps4Save => SAVE_GAME_TYPE,
+        HeaderType::PcProfile | HeaderType::Ps4Profile => PROFILE_GAME_TYPE,
+    };
+
+    FileData {
+        file_location,
+        file_version: 0,
+        package_version: 0,
+        engine_major: 0,
+        engine_minor: 0,
+        engine_patch: 0,
+        engine_build: 0,
+        build_id: String::new(),
+        custom_format_version: 0,
+        custom_format_data_count: 0,
+        custom_format_data: Vec::new(),
+        save_game_type: save_game_type.to_owned(),
+        remaining_data: data,
+    }
+}
+
+/// Checks that `data` is a file written in `payload_format` whose payload reads as `M` and
+/// re-encodes to the same bytes. This is much quicker than reading it as a save or profile as
+/// nothing is mapped, so it is used to check files that we have just written.
+pub fn verify_bytes<M: protobuf::Message>(
+    file_location: &Path,
+    data: &[u8],
+    header_type: HeaderType,
+    payload_format: PayloadFormat,
+) -> Result<()> {
+    let payload = match payload_format {
+        PayloadFormat::Encrypted => {
+            let file_data = read_bytes(file_location, data)?;
+
+            decrypt_bytes(file_data.remaining_data, &header_type)?
+        }
+        PayloadFormat::Decrypted => read_bytes(file_location, data)?.remaining_data.to_vec(),
+        PayloadFormat::Raw => data.to_vec(),
+    };
+
+    let message = M::parse_from_bytes(&payload)?;
+
+    if message.write_to_bytes()? != payload {
+        bail!(
+            "{} could not be read back as it was written",
+            file_location.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Reads `field` from the start of `i` and moves `i` past it, errors include where in `data` the
+/// field starts.
+fn read_field<'a, F, T, P>(
+    data: &[u8],
+    i: &mut &'a [u8],
+    field: F,
+    parser: P,
+) -> Result<T, ParseError>
+where
+    F: ToString,
+    P: FnOnce(&'a [u8]) -> nom::IResult<&'a [u8], T, BL3ParserError<String>>,
+{
+    let position = Position::Byte(data.len() - i.len());
+
+    let (r, value) = parser(i)
+        .finish()
+        .map_err(|e| ParseError::new(field, position, e))?;
+
+    *i = r;
+
+    Ok(value)
+}
+
+/// Describes each step of reading a file and where it went wrong, for files that won't load.
+pub fn diagnose(file_location: &Path, data: &[u8]) -> Vec<String> {
+    let mut report = vec![format!("File size: {} bytes", data.len())];
+
+    let file_data = match read_bytes(file_location, data) {
+        Ok(file_data) => file_data,
+        Err(e) => {
+            report.push(format!("Header: {:#}", e));
+
+            return report;
+        }
+    };
+
+    report.push(format!("Build ID: {}", file_data.build_id));
+    report.push(format!("Save game type: {}", file_data.save_game_type));
+    report.push(format!(
+        "Encrypted data: {} bytes",
+        file_data.remaining_data.len()
+    ));
+
+    for header_type in HeaderType::ALL_TYPES {
+        let result = match Bl3FileType::from_file_data(&file_data, header_type) {
+            Ok(file) => {
+                if let Err(e) = file.load_details() {
+                    format!("read, but it can't be opened: {:#}", e)
+                } else {
+                    match file.round_trip() {
+                        Ok(round_trip) if !round_trip.is_lossless() => format!(
+                            "read, but saving it would fail as {}",
+                            round_trip.describe_lost_fields()
+                        ),
+                        Ok(round_trip) if !round_trip.unknown_fields.is_empty() => format!(
+                            "read, with {} unknown field(s)",
+                            round_trip.unknown_fields.len()
+                        ),
+                        Ok(_) => "read successfully".to_owned(),
+                        Err(e) => format!("read, but it can't be re-encoded: {:#}", e),
+                    }
+                }
+            }
+            Err(e) => format!("{:#}", e),
+        };
+
+        report.push(format!("As a {}: {}", header_type, result));
+    }
+
+    report
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Ord, PartialOrd)]
 pub enum Bl3FileType {
     PcSave(Bl3Save),
@@ -106,19 +273,154 @@ impl std::fmt::Display for Bl3FileType {
 }
 
 impl Bl3FileType {
+    /// Works out whether the file is a save or a profile from its header and only fully decrypts
+    /// it with keys that look right, the error lists why each type that was tried failed.
+    ///
+    /// Files that other tools have left decrypted are also read, as are bare protobuf payloads
+    /// without a header.
     pub fn from_unknown_data(file_location: &Path, data: &[u8]) -> Result<Bl3FileType> {
+        if !data.starts_with(b"GVAS") {
+            return Self::from_raw_payload(file_location, data);
+        }
+
         let file_data = read_bytes(file_location, data)?;
 
-        if let Ok(save) = Bl3Save::from_file_data(&file_data, HeaderType::PcSave) {
-            Ok(Bl3FileType::PcSave(save))
-        } else if let Ok(profile) = Bl3Profile::from_file_data(&file_data, HeaderType::PcProfile) {
-            Ok(Bl3FileType::PcProfile(profile))
-        } else if let Ok(save) = Bl3Save::from_file_data(&file_data, HeaderType::Ps4Save) {
-            Ok(Bl3FileType::Ps4Save(save))
-        } else if let Ok(profile) = Bl3Profile::from_file_data(&file_data, HeaderType::Ps4Profile) {
-            Ok(Bl3FileType::Ps4Profile(profile))
-        } else {
-            bail!("Could not recognize file type.")
+        let candidates = HeaderType::candidates(&file_data.save_game_type);
+
+        let mut attempts = Vec::new();
+
+        for header_type in candidates {
+            match Self::from_file_data(&file_data, *header_type) {
+                Ok(file) => return Ok(file),
+                Err(e) => attempts.push((*header_type, e)),
+            }
+        }
+
+        // Decrypted data isn't for any platform so it is read as PC until it is changed
+        for header_type in candidates.iter().filter(|h| h.platform() == Platform::Pc) {
+            if !Self::looks_like(file_data.remaining_data, *header_type) {
+                continue;
+            }
+
+            match Self::from_payload(
+                &file_data,
+                file_data.remaining_data,
+                *header_type,
+                PayloadFormat::Decrypted,
+            ) {
+                Ok(file) => return Ok(file),
+                Err(e) => attempts.push((*header_type, e.context("failed to read decrypted data"))),
+            }
+        }
+
+        Err(FileTypeError {
+            save_game_type: file_data.save_game_type,
+            attempts,
+        }
+        .into())
+    }
+
+    /// Reads a payload without a header, such as one written by `protoc --encode`, trying it as a
+    /// save and then a profile.
+    fn from_raw_payload(file_location: &Path, data: &[u8]) -> Result<Bl3FileType> {
+        let mut candidates = Vec::new();
+
+        for header_type in [HeaderType::PcSave, HeaderType::PcProfile] {
+            if !Self::looks_like(data, header_type) {
+                continue;
+            }
+
+            let file_data = raw_file_data(file_location, data, header_type);
+
+            if let Ok(file) = Self::from_payload(&file_data, data, header_type, PayloadFormat::Raw)
+            {
+                candidates.push(file);
+            }
+        }
+
+        // Both messages start with small field numbers so the one that knows the most fields wins
+        candidates
+            .into_iter()
+            .min_by_key(|f| {
+                f.round_trip()
+                    .map(|r| r.unknown_fields.len())
+                    .unwrap_or(usize::MAX)
+            })
+            .context("file has no GVAS header and is not a protobuf save or profile")
+    }
+
+    fn looks_like(data: &[u8], header_type: HeaderType) -> bool {
+        match header_type {
+            HeaderType::PcSave | HeaderType::Ps4Save => looks_like::<Character>(data),
+            HeaderType::PcProfile | HeaderType::Ps4Profile => looks_like::<Profile>(data),
+        }
+    }
+
+    fn from_payload(
+        file_data: &FileData,
+        data: &[u8],
+        header_type: HeaderType,
+        payload_format: PayloadFormat,
+    ) -> Result<Bl3FileType> {
+        match header_type {
+            HeaderType::PcSave | HeaderType::Ps4Save => {
+                let save = Bl3Save::from_payload(file_data, data, header_type, payload_format)?;
+
+                if header_type == HeaderType::PcSave {
+                    Ok(Bl3FileType::PcSave(save))
+                } else {
+                    Ok(Bl3FileType::Ps4Save(save))
+                }
+            }
+            HeaderType::PcProfile | HeaderType::Ps4Profile => {
+                let profile =
+                    Bl3Profile::from_payload(file_data, data, header_type, payload_format)?;
+
+                if header_type == HeaderType::PcProfile {
+                    Ok(Bl3FileType::PcProfile(profile))
+                } else {
+                    Ok(Bl3FileType::Ps4Profile(profile))
+                }
+            }
+        }
+    }
+
+    fn from_file_data(file_data: &FileData, header_type: HeaderType) -> Result<Bl3FileType> {
+        let remaining_data = file_data.remaining_data;
+
+        match header_type {
+            HeaderType::PcSave | HeaderType::Ps4Save => {
+                if !could_decrypt::<Character>(remaining_data, &header_type) {
+                    bail!(
+                        "data does not look like a save encrypted for {}",
+                        header_type.platform()
+                    );
+                }
+
+                let save = Bl3Save::from_file_data(file_data, header_type)?;
+
+                if header_type == HeaderType::PcSave {
+                    Ok(Bl3FileType::PcSave(save))
+                } else {
+                    Ok(Bl3FileType::Ps4Save(save))
+                }
+            }
+            HeaderType::PcProfile | HeaderType::Ps4Profile => {
+                if !could_decrypt::<Profile>(remaining_data, &header_type) {
+                    bail!(
+                        "data does not look like a profile encrypted for {}",
+                        header_type.platform()
+                    );
+                }
+
+                let profile = Bl3Profile::from_file_data(file_data, header_type)?;
+
+                if header_type == HeaderType::PcProfile {
+                    Ok(Bl3FileType::PcProfile(profile))
+                } else {
+                    Ok(Bl3FileType::Ps4Profile(profile))
+                }
+            }
         }
     }
 
@@ -130,4 +432,342 @@ impl Bl3FileType {
             Bl3FileType::Ps4Profile(p) => &p.file_name,
         }
     }
+
+    /// Re-encrypts this file for `platform`, returning the name it should have on that platform
+    /// along with its data.
+    pub fn convert_to(&self, platform: Platform) -> Result<(String, Vec<u8>)> {
+        match self {
+            Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+                let mut save = save.clone();
+                save.header_type = save.header_type.with_platform(platform);
+                save.payload_format = PayloadFormat::Encrypted;
+
+                let (output, _) = save.as_bytes()?;
+
+                let file_name = platform.save_file_name(save.character_data.character.save_game_id);
+
+                Ok((file_name, output))
+            }
+            Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
+                let mut profile = profile.clone();
+                profile.header_type = profile.header_type.with_platform(platform);
+                profile.payload_format = PayloadFormat::Encrypted;
+
+                let (output, _) = profile.as_bytes()?;
+
+                Ok((platform.profile_file_name(), output))
+            }
+        }
+    }
+
+    pub fn header(&self) -> GvasHeader {
+        match self {
+            Bl3FileType::PcSave(s) | Bl3FileType::Ps4Save(s) => s.header(),
+            Bl3FileType::PcProfile(p) | Bl3FileType::Ps4Profile(p) => p.header(),
+        }
+    }
+
+    /// Reads the parts of a save that are left until it's opened, profiles are read in full.
+    pub fn load_details(&self) -> Result<()> {
+        match self {
+            Bl3FileType::PcSave(s) | Bl3FileType::Ps4Save(s) => s.character_data.load_details(),
+            Bl3FileType::PcProfile(_) | Bl3FileType::Ps4Profile(_) => Ok(()),
+        }
+    }
+
+    pub fn round_trip(&self) -> Result<&RoundTripReport> {
+        match self {
+            Bl3FileType::PcSave(s) | Bl3FileType::Ps4Save(s) => s.round_trip(),
+            Bl3FileType::PcProfile(p) | Bl3FileType::Ps4Profile(p) => p.round_trip(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::protobuf_diff;
+
+    use super::*;
+
+    #[test]
+    fn test_convert_to() {
+        let filename = Path::new("./test_files/310pc.sav");
+        let data = std::fs::read(filename).unwrap();
+
+        let file = Bl3FileType::from_unknown_data(filename, &data).unwrap();
+
+        let (file_name, output) = file.convert_to(Platform::Ps4).unwrap();
+
+        assert_eq!(file_name, "784.sav");
+
+        let converted = Bl3FileType::from_unknown_data(filename, &output).unwrap();
+
+        match (&file, &converted) {
+            (Bl3FileType::PcSave(save), Bl3FileType::Ps4Save(converted_save)) => {
+                assert!(protobuf_diff::diff_messages(
+                    &save.character_data.character,
+                    &converted_save.character_data.character
+                )
+                .is_empty());
+            }
+            _ => panic!("expected a PC save to convert to a PS4 save"),
+        }
+
+        let (file_name, _) = converted.convert_to(Platform::Pc).unwrap();
+
+        assert_eq!(file_name, "310.sav");
+
+        let filename = Path::new("./test_files/2profps4.sav");
+        let data = std::fs::read(filename).unwrap();
+
+        let file = Bl3FileType::from_unknown_data(filename, &data).unwrap();
+
+        let (file_name, output) = file.convert_to(Platform::Pc).unwrap();
+
+        assert_eq!(file_name, "profile.sav");
+        assert!(matches!(
+            Bl3FileType::from_unknown_data(filename, &output).unwrap(),
+            Bl3FileType::PcProfile(_)
+        ));
+    }
+
+    #[test]
+    fn test_read_bytes_errors() {
+        let filename = Path::new("./test_files/1.sav");
+        let data = std::fs::read(filename).unwrap();
+
+        let error = read_bytes(filename, &data[..30]).unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+
+        assert_eq!(error.field, "build_id");
+        assert_eq!(error.position, Position::Byte(22));
+
+        let error = read_bytes(filename, &data[..data.len() - 1]).unwrap_err();
+        let error = error.downcast::<ParseError>().unwrap();
+
+        assert_eq!(error.field, "remaining_data_len");
+        assert!(matches!(error.source, BL3ParserError::Unexpected { .. }));
+
+        let mut bad_header = data.clone();
+        bad_header[0] = b'X';
+
+        let error = read_bytes(filename, &bad_header).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "failed to read header at byte 0: expected \"GVAS\" but found \"XVAS\""
+        );
+    }
+
+    #[test]
+    fn test_copy_header() {
+        let read = |file_name: &str| {
+            let path = Path::new("./test_files").join(file_name);
+
+            Bl3FileType::from_unknown_data(&path, &std::fs::read(&path).unwrap()).unwrap()
+        };
+
+        let newer = read("2profps4.sav").header();
+
+        let mut save = match read("19.sav") {
+            Bl3FileType::PcSave(save) => save,
+            _ => panic!("expected a PC save"),
+        };
+
+        assert_ne!(save.header(), newer);
+
+        save.set_header(newer.clone());
+
+        assert_eq!(
+            save.custom_format_data_count as usize,
+            newer.custom_format_data.len()
+        );
+
+        let (output, _) = save.as_bytes().unwrap();
+
+        let reloaded = Bl3FileType::from_unknown_data(Path::new("19.sav"), &output).unwrap();
+
+        assert_eq!(reloaded.header(), newer);
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let filename = Path::new("./test_files/2profps4.sav");
+        let data = std::fs::read(filename).unwrap();
+
+        let report = diagnose(filename, &data);
+
+        assert_eq!(report[2], "Save game type: BP_DefaultOakProfile_C");
+        assert!(report.contains(&"As a PS4 Profile: read successfully".to_owned()));
+        assert!(report.contains(
+            &"As a PC Profile: data does not look like a profile encrypted for PC".to_owned()
+        ));
+
+        let report = diagnose(filename, &data[..30]);
+
+        assert_eq!(report.len(), 2);
+        assert!(report[1].starts_with("Header: failed to read build_id at byte 22"));
+    }
+
+    #[test]
+    fn test_from_unknown_data() {
+        for (file_name, expected) in [
+            ("1.sav", HeaderType::PcSave),
+            ("1prof.sav", HeaderType::PcProfile),
+            ("1ps4_v100.sav", HeaderType::Ps4Save),
+            ("2profps4.sav", HeaderType::Ps4Profile),
+            ("310ps4.sav", HeaderType::Ps4Save),
+        ] {
+            let filename = Path::new("./test_files").join(file_name);
+            let data = std::fs::read(&filename).unwrap();
+
+            let header_type = match Bl3FileType::from_unknown_data(&filename, &data).unwrap() {
+                Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => save.header_type,
+                Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
+                    profile.header_type
+                }
+            };
+
+            assert_eq!(header_type, expected, "{}", file_name);
+        }
+
+        for (file_name, save_game_type, expected, kind) in [
+            ("1.sav", SAVE_GAME_TYPE, HeaderType::SAVE_TYPES, "save"),
+            (
+                "1prof.sav",
+                PROFILE_GAME_TYPE,
+                HeaderType::PROFILE_TYPES,
+                "profile",
+            ),
+        ] {
+            let filename = Path::new("./test_files").join(file_name);
+            let mut data = std::fs::read(&filename).unwrap();
+
+            // Damage the end of the file so that only the full decrypt fails
+            let len = data.len();
+            data[len - 100..].iter_mut().for_each(|b| *b = !*b);
+
+            let error = Bl3FileType::from_unknown_data(&filename, &data).unwrap_err();
+            let error = error.downcast::<FileTypeError>().unwrap();
+
+            assert_eq!(error.save_game_type, save_game_type);
+            assert_eq!(
+                error
+                    .attempts
+                    .iter()
+                    .map(|(header_type, _)| *header_type)
+                    .collect::<Vec<_>>(),
+                expected,
+                "{}",
+                file_name
+            );
+            assert!(error.attempts[1]
+                .1
+                .to_string()
+                .contains(&format!("does not look like a {} encrypted for PS4", kind)));
+        }
+    }
+
+    #[test]
+    fn test_payload_formats() {
+        for (file_name, is_save) in [("19.sav", true), ("2profps4.sav", false)] {
+            let filename = Path::new("./test_files").join(file_name);
+            let data = std::fs::read(&filename).unwrap();
+
+            let file = Bl3FileType::from_unknown_data(&filename, &data).unwrap();
+
+            for payload_format in [PayloadFormat::Decrypted, PayloadFormat::Raw] {
+                let output = match &file {
+                    Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+                        let mut save = save.clone();
+                        save.payload_format = payload_format;
+                        save.as_bytes().unwrap().0
+                    }
+                    Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
+                        let mut profile = profile.clone();
+                        profile.payload_format = payload_format;
+                        profile.as_bytes().unwrap().0
+                    }
+                };
+
+                assert_eq!(
+                    output.starts_with(b"GVAS"),
+                    payload_format == PayloadFormat::Decrypted
+                );
+
+                let read = Bl3FileType::from_unknown_data(&filename, &output).unwrap();
+
+                match (read, is_save) {
+                    (Bl3FileType::PcSave(save), true) => {
+                        assert_eq!(save.payload_format, payload_format);
+                        assert_eq!(
+                            save.build_id.is_empty(),
+                            payload_format == PayloadFormat::Raw
+                        );
+                    }
+                    (Bl3FileType::PcProfile(profile), false) => {
+                        assert_eq!(profile.payload_format, payload_format);
+                    }
+                    (read, _) => panic!("{} was read as {}", file_name, read),
+                }
+            }
+        }
+
+        let filename = Path::new("./test_files/19.sav");
+        let data = std::fs::read(filename).unwrap();
+
+        let mut save = Bl3Save::from_bytes(filename, &data, HeaderType::PcSave).unwrap();
+        save.payload_format = PayloadFormat::Raw;
+
+        let (output, mut raw_save) = save.as_bytes().unwrap();
+
+        // Without a header only the raw format can be written
+        raw_save.payload_format = PayloadFormat::Encrypted;
+        assert!(raw_save.as_bytes().is_err());
+
+        raw_save.set_header(save.header());
+
+        let (encrypted, _) = raw_save.as_bytes().unwrap();
+
+        save.payload_format = PayloadFormat::Encrypted;
+
+        assert_eq!(encrypted, save.as_bytes().unwrap().0);
+        assert!(Bl3FileType::from_unknown_data(filename, &output[..0]).is_err());
+    }
+
+    #[test]
+    fn test_verify_bytes() {
+        let filename = Path::new("./test_files/19.sav");
+        let data = std::fs::read(filename).unwrap();
+
+        let save = Bl3Save::from_bytes(filename, &data, HeaderType::PcSave).unwrap();
+
+        let (output, new_save) = save.as_bytes().unwrap();
+
+        assert_eq!(new_save, save);
+
+        verify_bytes::<Character>(
+            filename,
+            &output,
+            HeaderType::PcSave,
+            PayloadFormat::Encrypted,
+        )
+        .unwrap();
+
+        // Decrypting with the wrong key or cutting the file short should both be caught
+        assert!(verify_bytes::<Character>(
+            filename,
+            &output,
+            HeaderType::Ps4Save,
+            PayloadFormat::Encrypted
+        )
+        .is_err());
+        assert!(verify_bytes::<Character>(
+            filename,
+            &output[..output.len() - 16],
+            HeaderType::PcSave,
+            PayloadFormat::Encrypted
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,398 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::bl3_item::{
+    item_type_from_balance, BalancePart, Bl3Item, ItemRarity, ItemType, ManufacturerPart,
+    WeaponType,
+};
+use crate::bl3_save::character_data::MAX_CHARACTER_LEVEL;
+use crate::game_data::BALANCE_TO_INV_KEY;
+use crate::resources::{
+    ResourceItem, ResourcePart, INVENTORY_BALANCE_PARTS, INVENTORY_MANUFACTURER_PARTS,
+    INVENTORY_PARTS_ALL_CATEGORIZED, INVENTORY_SERIAL_DB,
+};
+
+// An item with no balance, parts or anointments that we build everything else on top of
+const BLANK_ITEM_SERIAL: &str = "BL3(BAAAAAD2aoA+P1vAEgA=)";
+
+#[derive(Debug, Clone, Default)]
+pub struct ItemGeneratorFilter {
+    pub item_type: Option<ItemType>,
+    pub weapon_type: Option<WeaponType>,
+    pub manufacturer: Option<ManufacturerPart>,
+    pub rarity: Option<ItemRarity>,
+}
+
+impl ItemGeneratorFilter {
+    fn matches(&self, balance_part: &BalancePart) -> bool {
+        let resource_item = match balance_part
+            .short_ident
+            .as_ref()
+            .and_then(|s| INVENTORY_PARTS_ALL_CATEGORIZED.get(s))
+        {
+            Some(resource_item) => resource_item,
+            None => return false,
+        };
+
+        let balance_ident_lower = balance_part.ident.to_lowercase();
+
+        let part_inv_key = match BALANCE_TO_INV_KEY
+            .iter()
+            .find(|gd| balance_ident_lower == gd.ident)
+        {
+            Some(gd) => gd.name,
+            None => return false,
+        };
+
+        let (item_type, weapon_type) = item_type_from_balance(&balance_part.ident, part_inv_key);
+
+        if let Some(filter_item_type) = &self.item_type {
+            if item_type != *filter_item_type {
+                return false;
+            }
+        }
+
+        if let Some(filter_weapon_type) = &self.weapon_type {
+            if weapon_type.as_ref() != Some(filter_weapon_type) {
+                return false;
+            }
+        }
+
+        if let Some(filter_rarity) = &self.rarity {
+            if ItemRarity::from_str(&resource_item.rarity).ok().as_ref() != Some(filter_rarity) {
+                return false;
+            }
+        }
+
+        if let Some(filter_manufacturer) = &self.manufacturer {
+            let manufacturer = manufacturer_part(&resource_item.manufacturer);
+
+            if manufacturer.map(|m| &m.ident) != Some(&filter_manufacturer.ident) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+pub struct ItemGenerator {
+    rng: StdRng,
+}
+
+impl ItemGenerator {
+    /// Using the same seed will always generate the same items in the same order.
+    pub fn new(seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Self { rng }
+    }
+
+    pub fn generate(&mut self, balance_part: &BalancePart, level: usize) -> Result<Bl3Item> {
+        let short_ident = balance_part
+            .short_ident
+            .as_ref()
+            .with_context(|| format!("balance has no short name: {}", balance_part.ident))?;
+
+        let resource_item = INVENTORY_PARTS_ALL_CATEGORIZED
+            .get(short_ident)
+            .with_context(|| format!("no part information was found for: {}", short_ident))?;
+
+        self.generate_with_resource_item(balance_part, resource_item, level)
+    }
+
+    /// Picks a random balance out of all of the balances that match `filter` before generating
+    /// the item.
+    pub fn generate_from_filter(
+        &mut self,
+        filter: &ItemGeneratorFilter,
+        level: usize,
+    ) -> Result<Bl3Item> {
+        let balance_parts = INVENTORY_BALANCE_PARTS
+            .par_iter()
+            .filter(|b| filter.matches(b))
+            .collect::<Vec<_>>();
+
+        let balance_part = balance_parts
+            .choose(&mut self.rng)
+            .context("no items match the given filter")?;
+
+        self.generate(balance_part, level)
+    }
+
+    fn generate_with_resource_item(
+        &mut self,
+        balance_part: &BalancePart,
+        resource_item: &ResourceItem,
+        level: usize,
+    ) -> Result<Bl3Item> {
+        let (mut item, part_inv_key) = new_item(balance_part, level)?;
+
+        if let Some(manufacturer_part) = manufacturer_part(&resource_item.manufacturer) {
+            item.set_manufacturer(manufacturer_part.to_owned())?;
+        }
+
+        for part in self.choose_parts(resource_item)? {
+            let bl3_part = INVENTORY_SERIAL_DB.get_part_by_short_name(&part_inv_key, &part.name)?;
+
+            item.add_part(bl3_part)?;
+        }
+
+        // Read the item again so that the rarity and weapon type are filled in
+        Bl3Item::from_serial_bytes(&item.get_serial_number(true)?, None)
+    }
+
+    /// Chooses between min_parts and max_parts parts for each category, categories are visited in
+    /// order so a part can only depend on parts from the same or an earlier category.
+    fn choose_parts<'a>(
+        &mut self,
+        resource_item: &'a ResourceItem,
+    ) -> Result<Vec<&'a ResourcePart>> {
+        let mut chosen_parts: Vec<&ResourcePart> = Vec::new();
+
+        for category in &resource_item.inventory_categorized_parts {
+            let (min_parts, max_parts) = category
+                .parts
+                .iter()
+                .map(|p| (p.min_parts as usize, p.max_parts.max(p.min_parts) as usize))
+                .next()
+                .unwrap_or((0, 0));
+
+            let num_parts = self.rng.gen_range(min_parts..=max_parts);
+
+            let mut candidates = category.parts.iter().collect::<Vec<_>>();
+
+            candidates.shuffle(&mut self.rng);
+
+            let mut category_parts = 0;
+
+            for part in candidates {
+                if category_parts == num_parts {
+                    break;
+                }
+
+                if part_is_allowed(part, &chosen_parts) {
+                    chosen_parts.push(part);
+                    category_parts += 1;
+                }
+            }
+
+            if category_parts < min_parts {
+                bail!(
+                    "could not choose {} parts for {} without breaking their dependencies or excluders",
+                    min_parts,
+                    category.category
+                );
+            }
+        }
+
+        Ok(chosen_parts)
+    }
+}
+
+/// Creates an item with `balance_part` at `level` that has no parts yet, also returns the
+/// `part_inv_key` that any parts should be looked up with.
+pub(crate) fn new_item(balance_part: &BalancePart, level: usize) -> Result<(Bl3Item, String)> {
+    if !(1..=MAX_CHARACTER_LEVEL).contains(&level) {
+        bail!(
+            "level must be between 1 and {} but it was: {}",
+            MAX_CHARACTER_LEVEL,
+            level
+        );
+    }
+
+    let mut item = Bl3Item::from_serial_base64(BLANK_ITEM_SERIAL)?;
+
+    item.set_balance(balance_part.to_owned())?;
+
+    let part_inv_key = item
+        .item_parts
+        .as_ref()
+        .map(|ip| ip.part_inv_key.to_owned())
+        .with_context(|| format!("balance does not support parts: {}", balance_part.ident))?;
+
+    item.set_level(level)?;
+
+    Ok((item, part_inv_key))
+}
+
+pub(crate) fn part_is_allowed(part: &ResourcePart, chosen_parts: &[&ResourcePart]) -> bool {
+    let excludes = |a: &ResourcePart, b: &ResourcePart| {
+        a.excluders
+            .as_ref()
+            .map(|e| e.contains(&b.name))
+            .unwrap_or(false)
+    };
+
+    let is_excluded = chosen_parts
+        .iter()
+        .any(|c| c.name == part.name || excludes(part, c) || excludes(c, part));
+
+    let dependencies_met = part
+        .dependencies
+        .as_ref()
+        .map(|d| {
+            d.is_empty()
+                || d.iter()
+                    .any(|dep| chosen_parts.iter().any(|c| c.name == *dep))
+        })
+        .unwrap_or(true);
+
+    !is_excluded && dependencies_met
+}
+
+pub(crate) fn manufacturer_part(resource_manufacturer: &str) -> Option<&'static ManufacturerPart> {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase()
+    };
+
+    let resource_manufacturer = match normalize(resource_manufacturer) {
+        m if m == "childrenofthevault" => "cov".to_owned(),
+        m => m,
+    };
+
+    INVENTORY_MANUFACTURER_PARTS.iter().find(|m| {
+        m.short_ident
+            .as_ref()
+            .map(|s| normalize(s) == resource_manufacturer)
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::resources::{ResourceCategorizedParts, ResourcePartInfo};
+
+    use super::*;
+
+    fn resource_part(name: &str, min_parts: u8, max_parts: u8) -> ResourcePart {
+        ResourcePart {
+            name: name.to_owned(),
+            min_parts,
+            max_parts,
+            dependencies: None,
+            excluders: None,
+            info: ResourcePartInfo::default(),
+        }
+    }
+
+    /// Parts for a common Hyperion SMG where each barrel has its own accessory and the second
+    /// magazine can't be used with the first barrel.
+    pub(crate) fn test_resource_item() -> ResourceItem {
+        let barrels = ["Part_SM_HYP_Barrel_01", "Part_SM_HYP_Barrel_02"];
+
+        let barrel_accessories = barrels
+            .iter()
+            .map(|b| ResourcePart {
+                dependencies: Some(vec![b.to_string()]),
+                ..resource_part(&format!("{}_A", b), 0, 1)
+            })
+            .collect();
+
+        let mags = vec![
+            resource_part("Part_SM_HYP_Mag_01", 1, 1),
+            ResourcePart {
+                excluders: Some(vec!["Part_SM_HYP_Barrel_01".to_owned()]),
+                ..resource_part("Part_SM_HYP_Mag_02", 1, 1)
+            },
+        ];
+
+        ResourceItem {
+            manufacturer: "Hyperion".to_owned(),
+            rarity: "01/Common".to_owned(),
+            inventory_categorized_parts: vec![
+                ResourceCategorizedParts {
+                    category: "Body".to_owned(),
+                    parts: vec![resource_part("Part_SM_HYP_Body", 1, 1)],
+                },
+                ResourceCategorizedParts {
+                    category: "Barrel".to_owned(),
+                    parts: barrels.iter().map(|b| resource_part(b, 1, 1)).collect(),
+                },
+                ResourceCategorizedParts {
+                    category: "Barrel Accessory".to_owned(),
+                    parts: barrel_accessories,
+                },
+                ResourceCategorizedParts {
+                    category: "Magazine".to_owned(),
+                    parts: mags,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_generate_with_resource_item() {
+        let balance_part = INVENTORY_BALANCE_PARTS
+            .iter()
+            .find(|b| b.short_ident.as_deref() == Some("Balance_SM_HYP_01_Common"))
+            .expect("failed to find balance");
+
+        let resource_item = test_resource_item();
+
+        for seed in 0..50 {
+            let item = ItemGenerator::new(Some(seed))
+                .generate_with_resource_item(balance_part, &resource_item, 50)
+                .expect("failed to generate item");
+
+            let same_item = ItemGenerator::new(Some(seed))
+                .generate_with_resource_item(balance_part, &resource_item, 50)
+                .expect("failed to generate item");
+
+            assert_eq!(item, same_item);
+            assert_eq!(item.level(), 50);
+            assert_eq!(item.balance_part().ident, balance_part.ident);
+            assert_eq!(
+                item.manufacturer_part().short_ident.as_deref(),
+                Some("Hyperion")
+            );
+
+            let parts = item
+                .item_parts
+                .as_ref()
+                .expect("item has no parts")
+                .parts()
+                .iter()
+                .filter_map(|p| p.short_ident.as_deref())
+                .collect::<Vec<_>>();
+
+            let count = |prefix: &str| parts.iter().filter(|p| p.starts_with(prefix)).count();
+
+            assert_eq!(count("Part_SM_HYP_Body"), 1);
+            assert_eq!(count("Part_SM_HYP_Mag_"), 1);
+
+            let barrel = parts
+                .iter()
+                .find(|p| p.starts_with("Part_SM_HYP_Barrel_") && !p.ends_with("_A"))
+                .expect("item has no barrel");
+
+            assert_eq!(
+                parts
+                    .iter()
+                    .filter(|p| p.starts_with("Part_SM_HYP_Barrel_"))
+                    .count(),
+                if parts.contains(&format!("{}_A", barrel).as_str()) {
+                    2
+                } else {
+                    1
+                }
+            );
+
+            assert!(
+                !(parts.contains(&"Part_SM_HYP_Barrel_01")
+                    && parts.contains(&"Part_SM_HYP_Mag_02"))
+            );
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::Serialize;
+use strum::Display;
+
+use crate::bl3_item::Bl3Item;
+use crate::file_helper::Bl3FileType;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Display, Serialize)]
+pub enum ItemLocation {
+    #[strum(to_string = "Inventory")]
+    Inventory,
+    #[strum(to_string = "Gun Rack")]
+    GunRack,
+    #[strum(to_string = "Bank")]
+    Bank,
+    #[strum(to_string = "Lost Loot")]
+    LostLoot,
+}
+
+impl ItemLocation {
+    pub fn is_removable(&self) -> bool {
+        matches!(self, ItemLocation::Inventory | ItemLocation::Bank)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IndexedItem {
+    pub file_name: String,
+    pub owner: String,
+    pub location: ItemLocation,
+    pub slot: usize,
+    pub item: Bl3Item,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DuplicateItems {
+    pub kept: IndexedItem,
+    pub extras: Vec<IndexedItem>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ItemIndex {
+    items: Vec<IndexedItem>,
+}
+
+impl ItemIndex {
+    pub fn from_files(files: &[Bl3FileType]) -> Self {
+        let items = files
+            .par_iter()
+            .flat_map_iter(Self::items_from_file)
+            .collect::<Vec<_>>();
+
+        Self { items }
+    }
+
+    fn indexed_items<'a>(
+        file_name: &str,
+        owner: &str,
+        location: ItemLocation,
+        items: impl IntoIterator<Item = (usize, &'a Bl3Item)>,
+    ) -> Vec<IndexedItem> {
+        items
+            .into_iter()
+            .map(|(slot, item)| IndexedItem {
+                file_name: file_name.to_owned(),
+                owner: owner.to_owned(),
+                location,
+                slot,
+                item: item.clone(),
+            })
+            .collect()
+    }
+
+    fn items_from_file(file: &Bl3FileType) -> Vec<IndexedItem> {
+        match file {
+            Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+                let owner = format!(
+                    "{} ({})",
+                    save.character_data.character.preferred_character_name,
+                    save.character_data.player_class()
+                );
+
+                let mut items = Self::indexed_items(
+                    &save.file_name,
+                    &owner,
+                    ItemLocation::Inventory,
+                    save.character_data.inventory_items().iter().enumerate(),
+                );
+
+                items.extend(Self::indexed_items(
+                    &save.file_name,
+                    &owner,
+                    ItemLocation::GunRack,
+                    save.character_data
+                        .gun_rack_items()
+                        .iter()
+                        .map(|(slot, item)| (*slot, item)),
+                ));
+
+                items
+            }
+            Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
+                let mut items = Self::indexed_items(
+                    &profile.file_name,
+                    "Bank",
+                    ItemLocation::Bank,
+                    profile.profile_data.bank_items().iter().enumerate(),
+                );
+
+                items.extend(Self::indexed_items(
+                    &profile.file_name,
+                    "Lost Loot",
+                    ItemLocation::LostLoot,
+                    profile.profile_data.lost_loot_items().iter().enumerate(),
+                ));
+
+                items
+            }
+        }
+    }
+
+    pub fn items(&self) -> &[IndexedItem] {
+        &self.items
+    }
+
+    /// Items are treated as duplicates when their balance, inventory data, manufacturer, parts and
+    /// anointments all match, regardless of their seed or level. Only the Inventory and Bank are
+    /// checked as these are the only places we can remove items from.
+    pub fn duplicates(&self) -> Vec<DuplicateItems> {
+        let mut groups: HashMap<Vec<String>, Vec<&IndexedItem>> = HashMap::new();
+
+        self.items
+            .iter()
+            .filter(|i| i.location.is_removable())
+            .for_each(|i| {
+                groups
+                    .entry(Self::duplicate_key(&i.item))
+                    .or_default()
+                    .push(i)
+            });
+
+        let mut duplicates = groups
+            .into_values()
+            .filter(|g| g.len() > 1)
+            .map(|mut group| {
+                // Keep the highest level copy, otherwise the first one we found
+                group.sort_by_key(|i| std::cmp::Reverse(i.item.level()));
+
+                let mut group = group.into_iter().cloned();
+
+                DuplicateItems {
+                    kept: group.next().expect("group was empty"),
+                    extras: group.collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        duplicates.sort_by(|a, b| {
+            a.kept
+                .item
+                .balance_part()
+                .ident
+                .cmp(&b.kept.item.balance_part().ident)
+        });
+
+        duplicates
+    }
+
+    fn duplicate_key(item: &Bl3Item) -> Vec<String> {
+        let mut key = vec![
+            item.balance_part().ident.clone(),
+            item.inv_data_part().ident.clone(),
+            item.manufacturer_part().ident.clone(),
+        ];
+
+        if let Some(item_parts) = &item.item_parts {
+            let mut parts = item_parts
+                .parts()
+                .iter()
+                .map(|p| p.ident.clone())
+                .collect::<Vec<_>>();
+
+            parts.sort();
+
+            let mut generic_parts = item_parts
+                .generic_parts()
+                .iter()
+                .map(|p| format!("generic:{}", p.ident))
+                .collect::<Vec<_>>();
+
+            generic_parts.sort();
+
+            key.extend(parts);
+            key.extend(generic_parts);
+        }
+
+        key
+    }
+}
+
+/// Removes the given items from `file`, any items that do not belong to this file are ignored.
+/// Each item must still be in the slot it was indexed from, otherwise nothing is removed.
+pub fn remove_indexed_items(file: &mut Bl3FileType, items: &[IndexedItem]) -> Result<usize> {
+    let (file_name, current_items) = match file {
+        Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => (
+            save.file_name.clone(),
+            save.character_data.inventory_items().clone(),
+        ),
+        Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => (
+            profile.file_name.clone(),
+            profile.profile_data.bank_items().clone(),
+        ),
+    };
+
+    let mut slots = items
+        .iter()
+        .filter(|i| i.file_name == file_name && i.location.is_removable())
+        .map(|i| {
+            let still_exists = current_items
+                .get(i.slot)
+                .map(|c| c.get_serial_number(true).ok() == i.item.get_serial_number(true).ok())
+                .unwrap_or(false);
+
+            if still_exists {
+                Ok(i.slot)
+            } else {
+                bail!(
+                    "item in slot {} of {} has changed since it was indexed",
+                    i.slot,
+                    file_name
+                )
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Remove from the back so that earlier slots stay valid
+    slots.sort_unstable();
+    slots.dedup();
+
+    for slot in slots.iter().rev() {
+        match file {
+            Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+                save.character_data.remove_inventory_item(*slot)
+            }
+            Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
+                profile.profile_data.remove_bank_item(*slot)
+            }
+        }
+    }
+
+    Ok(slots.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::bl3_save::character_data::CharacterData;
+    use crate::bl3_save::Bl3Save;
+    use crate::parser::HeaderType;
+    use crate::protos::oak_save::CrewQuartersGunRackItemSaveData;
+
+    use super::*;
+
+    #[test]
+    fn test_duplicates() {
+        let filename = Path::new("./test_files/19.sav");
+
+        let mut save = Bl3Save::from_bytes(
+            filename,
+            &std::fs::read(filename).unwrap(),
+            HeaderType::PcSave,
+        )
+        .expect("failed to read test save");
+
+        let mut duplicate = save
+            .character_data
+            .inventory_items()
+            .iter()
+            .find(|i| i.level() > 1)
+            .cloned()
+            .expect("no item above level 1");
+
+        duplicate.set_level(duplicate.level() - 1).unwrap();
+
+        let pickup_order_index = save.character_data.inventory_items().len() as i32;
+
+        save.character_data
+            .add_inventory_item(pickup_order_index, &duplicate)
+            .unwrap();
+
+        let mut file = Bl3FileType::PcSave(save);
+
+        let index = ItemIndex::from_files(&[file.clone()]);
+
+        let duplicates = index.duplicates();
+
+        let group = duplicates
+            .iter()
+            .find(|d| d.extras.iter().any(|e| e.item == duplicate))
+            .expect("duplicate was not found");
+
+        let removed = remove_indexed_items(&mut file, &group.extras).unwrap();
+
+        assert_eq!(removed, group.extras.len());
+
+        let index = ItemIndex::from_files(&[file]);
+
+        assert!(!index.items().iter().any(|i| i.item == duplicate));
+    }
+
+    #[test]
+    fn test_gun_rack_slots() {
+        let filename = Path::new("./test_files/19.sav");
+
+        let mut save = Bl3Save::from_bytes(
+            filename,
+            &std::fs::read(filename).unwrap(),
+            HeaderType::PcSave,
+        )
+        .expect("failed to read test save");
+
+        let mut character = save.character_data.character.clone();
+
+        let serial = character.inventory_items[0].item_serial_number.clone();
+
+        // The item in the first slot can't be read
+        let rack = character.mut_crew_quarters_gun_rack();
+
+        rack.rack_save_data.clear();
+
+        for serial in [vec![0; 4], serial] {
+            let mut rack_item = CrewQuartersGunRackItemSaveData::new();
+            rack_item.encrypted_serial_number = serial;
+
+            rack.rack_save_data.push(rack_item);
+        }
+
+        save.character_data = CharacterData::from_character(character).unwrap();
+
+        let index = ItemIndex::from_files(&[Bl3FileType::PcSave(save)]);
+
+        let gun_rack_slots = index
+            .items()
+            .iter()
+            .filter(|i| i.location == ItemLocation::GunRack)
+            .map(|i| i.slot)
+            .collect::<Vec<_>>();
+
+        assert_eq!(gun_rack_slots, vec![1]);
+    }
+}
@@ -0,0 +1,444 @@
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::bl3_item::{BalancePart, Bl3Item, ManufacturerPart};
+use crate::item_generator::{manufacturer_part, new_item, part_is_allowed};
+use crate::resources::{
+    ResourceItem, ResourcePart, INVENTORY_BALANCE_PARTS, INVENTORY_MANUFACTURER_PARTS,
+    INVENTORY_PARTS_ALL_CATEGORIZED, INVENTORY_SERIAL_DB, INVENTORY_SERIAL_DB_PARTS_CATEGORIZED,
+};
+
+const BUILT_IN_ITEM_TEMPLATES_RON: &str = include_str!("../resources/ITEM_TEMPLATES.ron");
+
+const GENERIC_PART_INV_KEY: &str = "InventoryGenericPartData";
+
+pub static BUILT_IN_ITEM_TEMPLATES: Lazy<Vec<ItemTemplate>> = Lazy::new(|| {
+    ItemTemplate::from_ron(BUILT_IN_ITEM_TEMPLATES_RON).expect("failed to read built in templates")
+});
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ItemTemplatePart {
+    /// Any part from the category that doesn't break the item's part rules.
+    Any,
+    Part(String),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ItemTemplateCategory {
+    pub category: String,
+    pub parts: Vec<ItemTemplatePart>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ItemTemplate {
+    pub name: String,
+    pub balance: String,
+    #[serde(default)]
+    pub manufacturer: Option<String>,
+    pub categories: Vec<ItemTemplateCategory>,
+    #[serde(default)]
+    pub anointment: Option<ItemTemplatePart>,
+}
+
+impl ItemTemplate {
+    pub fn from_ron(input: &str) -> Result<Vec<Self>> {
+        ron::de::from_str(input).context("failed to read item templates")
+    }
+
+    pub fn to_ron(templates: &[Self]) -> Result<String> {
+        ron::ser::to_string_pretty(&templates, ron::ser::PrettyConfig::new())
+            .context("failed to write item templates")
+    }
+
+    /// Creates a template that recreates `item` exactly, parts are grouped using the same
+    /// categories that are shown in the item editor.
+    pub fn from_item(name: &str, item: &Bl3Item) -> Result<Self> {
+        let balance = item
+            .balance_part()
+            .short_ident
+            .clone()
+            .context("item balance has no short name")?;
+
+        let item_parts = item
+            .item_parts
+            .as_ref()
+            .context("item does not have any parts")?;
+
+        let serial_db_categories =
+            INVENTORY_SERIAL_DB_PARTS_CATEGORIZED.get(&item_parts.part_inv_key);
+
+        let mut categories: Vec<ItemTemplateCategory> = Vec::new();
+
+        for part in item_parts.parts() {
+            let part_name = part
+                .short_ident
+                .clone()
+                .with_context(|| format!("part has no short name: {}", part.ident))?;
+
+            let category = serial_db_categories
+                .and_then(|c| {
+                    c.iter()
+                        .find(|c| c.parts.iter().any(|p| p.name == part_name))
+                })
+                .map(|c| c.category.to_owned())
+                .unwrap_or_else(|| "Unknown Parts".to_owned());
+
+            match categories.last_mut() {
+                Some(last) if last.category == category => {
+                    last.parts.push(ItemTemplatePart::Part(part_name))
+                }
+                _ => categories.push(ItemTemplateCategory {
+                    category,
+                    parts: vec![ItemTemplatePart::Part(part_name)],
+                }),
+            }
+        }
+
+        let anointment = item_parts
+            .generic_parts()
+            .iter()
+            .filter_map(|p| p.short_ident.as_ref())
+            .find(|p| is_anointment(p))
+            .map(|p| ItemTemplatePart::Part(p.to_owned()));
+
+        Ok(Self {
+            name: name.to_owned(),
+            balance,
+            manufacturer: item.manufacturer_part().short_ident.clone(),
+            categories,
+            anointment,
+        })
+    }
+
+    /// Creates the item described by this template at `level`. Using the same seed will always
+    /// choose the same parts for any wildcards.
+    pub fn instantiate(&self, level: usize, seed: Option<u64>) -> Result<Bl3Item> {
+        let resource_item = self
+            .balance_part()?
+            .short_ident
+            .as_ref()
+            .and_then(|s| INVENTORY_PARTS_ALL_CATEGORIZED.get(s));
+
+        self.instantiate_with_resource_item(level, seed, resource_item)
+    }
+
+    fn instantiate_with_resource_item(
+        &self,
+        level: usize,
+        seed: Option<u64>,
+        resource_item: Option<&ResourceItem>,
+    ) -> Result<Bl3Item> {
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let balance_part = self.balance_part()?;
+
+        let (mut item, part_inv_key) = new_item(balance_part, level)?;
+
+        let manufacturer_part = match &self.manufacturer {
+            Some(manufacturer) => Some(template_manufacturer_part(manufacturer)?),
+            None => resource_item.and_then(|r| manufacturer_part(&r.manufacturer)),
+        };
+
+        if let Some(manufacturer_part) = manufacturer_part {
+            item.set_manufacturer(manufacturer_part.to_owned())?;
+        }
+
+        let resource_parts = |category: &str| {
+            resource_item
+                .and_then(|r| {
+                    r.inventory_categorized_parts
+                        .iter()
+                        .find(|c| c.category.eq_ignore_ascii_case(category))
+                })
+                .map(|c| &c.parts)
+        };
+
+        let template_parts = self
+            .categories
+            .iter()
+            .flat_map(|c| c.parts.iter().map(move |p| (c.category.as_str(), p)))
+            .collect::<Vec<_>>();
+
+        // Fill in the named parts first so that wildcards can respect their dependencies and
+        // excluders no matter where they are in the template
+        let mut chosen_parts = template_parts
+            .iter()
+            .map(|(category, part)| match part {
+                ItemTemplatePart::Part(name) => Some(
+                    resource_parts(category)
+                        .and_then(|parts| parts.iter().find(|p| p.name == *name))
+                        .cloned()
+                        .unwrap_or_else(|| ResourcePart {
+                            name: name.to_owned(),
+                            ..ResourcePart::default()
+                        }),
+                ),
+                ItemTemplatePart::Any => None,
+            })
+            .collect::<Vec<_>>();
+
+        for (i, (category, _)) in template_parts.iter().enumerate() {
+            if chosen_parts[i].is_some() {
+                continue;
+            }
+
+            let candidates = resource_parts(category).with_context(|| {
+                format!(
+                    "no part information was found for category {} of {}",
+                    category, self.balance
+                )
+            })?;
+
+            let already_chosen = chosen_parts.iter().flatten().collect::<Vec<_>>();
+
+            let part = candidates
+                .iter()
+                .filter(|p| part_is_allowed(p, &already_chosen))
+                .collect::<Vec<_>>()
+                .choose(&mut rng)
+                .map(|p| (*p).to_owned())
+                .with_context(|| {
+                    format!(
+                        "no parts in category {} can be used with the other parts of {}",
+                        category, self.balance
+                    )
+                })?;
+
+            chosen_parts[i] = Some(part);
+        }
+
+        for part in chosen_parts.into_iter().flatten() {
+            let bl3_part = INVENTORY_SERIAL_DB.get_part_by_short_name(&part_inv_key, &part.name)?;
+
+            item.add_part(bl3_part)?;
+        }
+
+        if let Some(anointment) = &self.anointment {
+            let anointment_name = match anointment {
+                ItemTemplatePart::Part(name) => name.to_owned(),
+                ItemTemplatePart::Any => INVENTORY_SERIAL_DB_PARTS_CATEGORIZED
+                    .get(GENERIC_PART_INV_KEY)
+                    .map(|c| {
+                        c.iter()
+                            .flat_map(|c| c.parts.iter())
+                            .filter(|p| is_anointment(&p.name))
+                            .collect::<Vec<_>>()
+                    })
+                    .and_then(|a| a.choose(&mut rng).map(|p| p.name.to_owned()))
+                    .context("no anointments were found")?,
+            };
+
+            let bl3_part = INVENTORY_SERIAL_DB
+                .get_part_by_short_name(GENERIC_PART_INV_KEY, &anointment_name)?;
+
+            item.add_generic_part(bl3_part)?;
+        }
+
+        // Read the item again so that the rarity and weapon type are filled in
+        Bl3Item::from_serial_bytes(&item.get_serial_number(true)?, None)
+    }
+
+    fn balance_part(&self) -> Result<&'static BalancePart> {
+        let balance_part = INVENTORY_BALANCE_PARTS.iter().find(|b| {
+            b.ident.eq_ignore_ascii_case(&self.balance)
+                || b.short_ident
+                    .as_ref()
+                    .map(|s| s.eq_ignore_ascii_case(&self.balance))
+                    .unwrap_or(false)
+        });
+
+        match balance_part {
+            Some(balance_part) => Ok(balance_part),
+            None => bail!(
+                "unknown balance in template {}: {}",
+                self.name,
+                self.balance
+            ),
+        }
+    }
+}
+
+fn template_manufacturer_part(manufacturer: &str) -> Result<&'static ManufacturerPart> {
+    INVENTORY_MANUFACTURER_PARTS
+        .iter()
+        .find(|m| {
+            m.ident.eq_ignore_ascii_case(manufacturer)
+                || m.short_ident
+                    .as_ref()
+                    .map(|s| s.eq_ignore_ascii_case(manufacturer))
+                    .unwrap_or(false)
+        })
+        .with_context(|| format!("unknown manufacturer in template: {}", manufacturer))
+}
+
+// Generic parts also include Mayhem levels, only the GPart_ ones are anointments
+fn is_anointment(part_name: &str) -> bool {
+    part_name.starts_with("GPart_")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::item_generator;
+
+    use super::*;
+
+    #[test]
+    fn test_built_in_templates() {
+        for template in BUILT_IN_ITEM_TEMPLATES.iter() {
+            let item = template
+                .instantiate(72, Some(0))
+                .unwrap_or_else(|e| panic!("failed to create {}: {}", template.name, e));
+
+            assert_eq!(item.level(), 72);
+            assert_eq!(
+                item.balance_part().short_ident.as_ref(),
+                Some(&template.balance)
+            );
+            assert_eq!(item.manufacturer_part().short_ident, template.manufacturer);
+
+            let item_parts = item.item_parts.as_ref().expect("item has no parts");
+
+            let part_names = item_parts
+                .parts()
+                .iter()
+                .map(|p| ItemTemplatePart::Part(p.short_ident.clone().unwrap()))
+                .collect::<Vec<_>>();
+
+            let template_parts = template
+                .categories
+                .iter()
+                .flat_map(|c| c.parts.clone())
+                .collect::<Vec<_>>();
+
+            assert_eq!(part_names, template_parts);
+            assert_eq!(item_parts.generic_parts().len(), 1);
+
+            if let Some(ItemTemplatePart::Part(anointment)) = &template.anointment {
+                assert_eq!(
+                    item_parts.generic_parts()[0].short_ident.as_ref(),
+                    Some(anointment)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_item() {
+        let item =
+            Bl3Item::from_serial_base64("BL3(BF57SUj7QZiGg6xoyXMGom1OCDJA+etOiEQlC4ObAMwPsg==)")
+                .expect("failed to read item");
+
+        let template =
+            ItemTemplate::from_item("Hellwalker", &item).expect("failed to create template");
+
+        let ron = ItemTemplate::to_ron(std::slice::from_ref(&template))
+            .expect("failed to write template");
+
+        assert_eq!(
+            ItemTemplate::from_ron(&ron).expect("failed to read template"),
+            vec![template.clone()]
+        );
+
+        let new_item = template
+            .instantiate(item.level(), None)
+            .expect("failed to create item from template");
+
+        assert_eq!(new_item.balance_part(), item.balance_part());
+        assert_eq!(new_item.manufacturer_part(), item.manufacturer_part());
+        assert_eq!(
+            new_item.item_parts.as_ref().map(|ip| ip.parts()),
+            item.item_parts.as_ref().map(|ip| ip.parts())
+        );
+    }
+
+    #[test]
+    fn test_any_part_respects_part_rules() {
+        let resource_item = item_generator::tests::test_resource_item();
+
+        let template = |barrel: ItemTemplatePart, magazine: ItemTemplatePart| {
+            let category = |category: &str, part: ItemTemplatePart| ItemTemplateCategory {
+                category: category.to_owned(),
+                parts: vec![part],
+            };
+
+            ItemTemplate {
+                name: "Hyperion SMG".to_owned(),
+                balance: "Balance_SM_HYP_01_Common".to_owned(),
+                manufacturer: None,
+                categories: vec![
+                    category(
+                        "Body",
+                        ItemTemplatePart::Part("Part_SM_HYP_Body".to_owned()),
+                    ),
+                    category("Barrel", barrel),
+                    category("Barrel Accessory", ItemTemplatePart::Any),
+                    category("Magazine", magazine),
+                ],
+                anointment: None,
+            }
+        };
+
+        // A named barrel decides the accessory and magazine, a named magazine decides the barrel
+        let barrel_template = template(
+            ItemTemplatePart::Part("Part_SM_HYP_Barrel_01".to_owned()),
+            ItemTemplatePart::Any,
+        );
+
+        let magazine_template = template(
+            ItemTemplatePart::Any,
+            ItemTemplatePart::Part("Part_SM_HYP_Mag_02".to_owned()),
+        );
+
+        let expected_parts = [
+            (
+                &barrel_template,
+                [
+                    "Part_SM_HYP_Body",
+                    "Part_SM_HYP_Barrel_01",
+                    "Part_SM_HYP_Barrel_01_A",
+                    "Part_SM_HYP_Mag_01",
+                ],
+            ),
+            (
+                &magazine_template,
+                [
+                    "Part_SM_HYP_Body",
+                    "Part_SM_HYP_Barrel_02",
+                    "Part_SM_HYP_Barrel_02_A",
+                    "Part_SM_HYP_Mag_02",
+                ],
+            ),
+        ];
+
+        for (template, expected_parts) in expected_parts {
+            for seed in 0..20 {
+                let item = template
+                    .instantiate_with_resource_item(50, Some(seed), Some(&resource_item))
+                    .expect("failed to create item from template");
+
+                assert_eq!(
+                    item.manufacturer_part().short_ident.as_deref(),
+                    Some("Hyperion")
+                );
+
+                let parts = item
+                    .item_parts
+                    .as_ref()
+                    .expect("item has no parts")
+                    .parts()
+                    .iter()
+                    .filter_map(|p| p.short_ident.as_deref())
+                    .collect::<Vec<_>>();
+
+                assert_eq!(parts, expected_parts);
+            }
+        }
+    }
+}
@@ -3,10 +3,16 @@ pub mod bl3_item;
 pub mod bl3_profile;
 pub mod bl3_save;
 pub mod error;
+pub mod file_diff;
 pub mod file_helper;
 pub mod game_data;
+pub mod item_generator;
+pub mod item_index;
+pub mod item_template;
 pub mod models;
 pub mod parser;
+pub mod protobuf_diff;
 pub mod protos;
 pub mod resources;
+pub mod round_trip;
 pub mod vehicle_data;
@@ -5,3 +5,18 @@ pub struct CustomFormatData {
     pub guid: Vec<u8>,
     pub entry: u32,
 }
+
+/// The GVAS header that is written before the encrypted data, this is what the game checks to
+/// see which version of the game wrote a file.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GvasHeader {
+    pub save_game_version: u32,
+    pub package_version: u32,
+    pub engine_major: u16,
+    pub engine_minor: u16,
+    pub engine_patch: u16,
+    pub engine_build: u32,
+    pub build_id: String,
+    pub custom_format_version: u32,
+    pub custom_format_data: Vec<CustomFormatData>,
+}
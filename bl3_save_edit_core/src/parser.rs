@@ -1,10 +1,10 @@
 use anyhow::{Context, Result};
 use byteorder::{LittleEndian, WriteBytesExt};
+use protobuf::descriptor::FieldDescriptorProto_Type;
 use strum::Display;
 
 use crate::error::BL3ParserError;
 use crate::error::ErrorExt;
-use crate::models::CustomFormatData;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Display)]
 pub enum HeaderType {
@@ -28,8 +28,106 @@ impl HeaderType {
     pub const SAVE_TYPES: [HeaderType; 2] = [HeaderType::PcSave, HeaderType::Ps4Save];
 
     pub const PROFILE_TYPES: [HeaderType; 2] = [HeaderType::PcProfile, HeaderType::Ps4Profile];
+
+    pub const ALL_TYPES: [HeaderType; 4] = [
+        HeaderType::PcSave,
+        HeaderType::PcProfile,
+        HeaderType::Ps4Save,
+        HeaderType::Ps4Profile,
+    ];
+
+    /// The header types a file could be given the save game type in its header.
+    pub fn candidates(save_game_type: &str) -> &'static [HeaderType] {
+        match save_game_type {
+            SAVE_GAME_TYPE => &HeaderType::SAVE_TYPES,
+            PROFILE_GAME_TYPE => &HeaderType::PROFILE_TYPES,
+            _ => &HeaderType::ALL_TYPES,
+        }
+    }
+
+    pub fn platform(&self) -> Platform {
+        match self {
+            HeaderType::PcSave | HeaderType::PcProfile => Platform::Pc,
+            HeaderType::Ps4Save | HeaderType::Ps4Profile => Platform::Ps4,
+        }
+    }
+
+    /// The same kind of file (save or profile) for `platform`.
+    pub fn with_platform(&self, platform: Platform) -> HeaderType {
+        match (self, platform) {
+            (HeaderType::PcSave | HeaderType::Ps4Save, Platform::Pc) => HeaderType::PcSave,
+            (HeaderType::PcSave | HeaderType::Ps4Save, Platform::Ps4) => HeaderType::Ps4Save,
+            (HeaderType::PcProfile | HeaderType::Ps4Profile, Platform::Pc) => HeaderType::PcProfile,
+            (HeaderType::PcProfile | HeaderType::Ps4Profile, Platform::Ps4) => {
+                HeaderType::Ps4Profile
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Display)]
+pub enum Platform {
+    #[default]
+    #[strum(to_string = "PC")]
+    Pc,
+    #[strum(to_string = "PS4")]
+    Ps4,
+}
+
+impl Platform {
+    pub const ALL: [Platform; 2] = [Platform::Pc, Platform::Ps4];
+
+    /// The PC names saves after their slot in hex, PS4 save exports use the slot in decimal.
+    pub fn save_file_name(&self, save_game_id: u32) -> String {
+        match self {
+            Platform::Pc => format!("{:x}.sav", save_game_id),
+            Platform::Ps4 => format!("{}.sav", save_game_id),
+        }
+    }
+
+    pub fn profile_file_name(&self) -> String {
+        "profile.sav".to_owned()
+    }
+}
+
+/// How the protobuf payload is stored, other tools often exchange it decrypted or without the
+/// header at all.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Display)]
+pub enum PayloadFormat {
+    #[default]
+    #[strum(to_string = "Encrypted (.sav)")]
+    Encrypted,
+    #[strum(to_string = "Decrypted (.sav)")]
+    Decrypted,
+    #[strum(to_string = "Protobuf (.pb)")]
+    Raw,
+}
+
+impl PayloadFormat {
+    pub const ALL: [PayloadFormat; 3] = [
+        PayloadFormat::Encrypted,
+        PayloadFormat::Decrypted,
+        PayloadFormat::Raw,
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PayloadFormat::Encrypted | PayloadFormat::Decrypted => "sav",
+            PayloadFormat::Raw => "pb",
+        }
+    }
 }
 
+pub const SAVE_GAME_TYPE: &str = "OakSaveGame";
+
+pub const PROFILE_GAME_TYPE: &str = "BP_DefaultOakProfile_C";
+
+/// How much of a file is decrypted when checking if a key is the right one.
+const DECRYPT_CHECK_LEN: usize = 256;
+
+/// How many fields are read when checking if a key is the right one.
+const DECRYPT_CHECK_FIELDS: usize = 4;
+
 const PC_SAVE_PREFIX_MAGIC: [u8; 32] = [
     0x71, 0x34, 0x36, 0xB3, 0x56, 0x63, 0x25, 0x5F, 0xEA, 0xE2, 0x83, 0x73, 0xF4, 0x98, 0xB8, 0x18,
     0x2E, 0xE5, 0x42, 0x2E, 0x50, 0xA2, 0x0F, 0x49, 0x87, 0x24, 0xE6, 0x65, 0x9A, 0xF0, 0x7C, 0xD7,
@@ -71,7 +169,16 @@ const PS4_PROFILE_XOR_MAGIC: [u8; 32] = [
 ];
 
 pub fn read_header(i: &[u8]) -> nom::IResult<&[u8], &[u8], BL3ParserError<String>> {
-    nom::bytes::complete::tag("GVAS")(i)
+    let (r, header) = nom::bytes::complete::take(4_u32)(i)?;
+
+    if header != b"GVAS" {
+        return Err(nom::Err::Error(BL3ParserError::unexpected(
+            "\"GVAS\"",
+            format!("{:?}", String::from_utf8_lossy(header)),
+        )));
+    }
+
+    Ok((r, header))
 }
 
 pub fn read_int(i: &[u8]) -> nom::IResult<&[u8], u32, BL3ParserError<String>> {
@@ -88,6 +195,21 @@ pub fn read_short(i: &[u8]) -> nom::IResult<&[u8], u16, BL3ParserError<String>>
 
 pub fn read_str(i: &[u8]) -> nom::IResult<&[u8], String, BL3ParserError<String>> {
     let (i, data_len) = read_int(i)?;
+
+    if data_len == 0 {
+        return Err(nom::Err::Error(BL3ParserError::unexpected(
+            "a string length of at least 1",
+            0,
+        )));
+    }
+
+    if data_len as usize > i.len() {
+        return Err(nom::Err::Error(BL3ParserError::unexpected(
+            format!("a string of {} byte(s)", data_len),
+            format!("{} byte(s) left", i.len()),
+        )));
+    }
+
     let (i, res) = nom::bytes::complete::take(data_len)(i)?;
 
     let res = String::from_utf8(res[..res.len() - 1].to_vec()).parser_error()?;
@@ -109,34 +231,19 @@ pub fn write_str<T: std::io::Write>(output: &mut T, s: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn read_custom_format_data(
-    i: &[u8],
-    fmt_count: u32,
-) -> nom::IResult<&[u8], Vec<CustomFormatData>, BL3ParserError<String>> {
-    let mut custom_format_data = Vec::with_capacity(fmt_count as usize);
-
-    let mut i = i;
-
-    for _ in 0..fmt_count {
-        let (r, guid) = read_guid(i)?;
-        let (r, entry) = read_int(r)?;
-
-        custom_format_data.push(CustomFormatData {
-            guid: guid.to_vec(),
-            entry,
-        });
-
-        i = r;
-    }
-
-    Ok((i, custom_format_data))
-}
-
 pub fn read_guid(i: &[u8]) -> nom::IResult<&[u8], &[u8], BL3ParserError<String>> {
     nom::bytes::complete::take(16_u32)(i)
 }
 
 pub fn decrypt<T: protobuf::Message>(data: &[u8], header_type: &HeaderType) -> Result<T> {
+    let data = decrypt_bytes(data, header_type)?;
+
+    let result: T = protobuf::Message::parse_from_bytes(&data)?;
+
+    Ok(result)
+}
+
+pub fn decrypt_bytes(data: &[u8], header_type: &HeaderType) -> Result<Vec<u8>> {
     let (prefix_magic, xor_magic) = match header_type {
         HeaderType::PcSave => (PC_SAVE_PREFIX_MAGIC, PC_SAVE_XOR_MAGIC),
         HeaderType::PcProfile => (PC_PROFILE_PREFIX_MAGIC, PC_PROFILE_XOR_MAGIC),
@@ -146,7 +253,6 @@ pub fn decrypt<T: protobuf::Message>(data: &[u8], header_type: &HeaderType) -> R
 
     // Clone data so we can decrypt multiple times (when we don't know save type)
     let mut data = data.to_vec();
-    let data = data.as_mut_slice();
 
     for i in (0..data.len()).rev() {
         let b = if i < 32 {
@@ -168,9 +274,110 @@ pub fn decrypt<T: protobuf::Message>(data: &[u8], header_type: &HeaderType) -> R
         })?;
     }
 
-    let result: T = protobuf::Message::parse_from_bytes(data)?;
+    Ok(data)
+}
+
+/// Decrypts only the start of `data` and checks that its first few fields are ones that `T` has,
+/// this is much cheaper than decrypting and parsing the whole file to find out the key is wrong.
+pub fn could_decrypt<T: protobuf::Message>(data: &[u8], header_type: &HeaderType) -> bool {
+    // Each byte only depends on itself and the byte 32 before it so the start can be decrypted
+    // on its own
+    let prefix = match decrypt_bytes(&data[..data.len().min(DECRYPT_CHECK_LEN)], header_type) {
+        Ok(prefix) => prefix,
+        Err(_) => return false,
+    };
 
-    Ok(result)
+    starts_like::<T>(&prefix, data.len())
+}
+
+/// Checks that the first few fields of already decrypted `data` are ones that `T` has.
+pub fn looks_like<T: protobuf::Message>(data: &[u8]) -> bool {
+    !data.is_empty() && starts_like::<T>(&data[..data.len().min(DECRYPT_CHECK_LEN)], data.len())
+}
+
+/// `prefix` is the decrypted start of a payload that is `total_len` bytes long.
+fn starts_like<T: protobuf::Message>(prefix: &[u8], total_len: usize) -> bool {
+    let is_truncated = prefix.len() < total_len;
+
+    let descriptor = T::descriptor_static();
+
+    let mut pos = 0;
+
+    for _ in 0..DECRYPT_CHECK_FIELDS {
+        if pos >= prefix.len() {
+            return is_truncated || pos == prefix.len();
+        }
+
+        let tag = match read_varint(prefix, &mut pos) {
+            Some(tag) => tag,
+            None => return is_truncated,
+        };
+
+        let (number, wire_type) = (tag >> 3, (tag & 7) as u8);
+
+        let field = match descriptor
+            .fields()
+            .iter()
+            .find(|f| f.proto().get_number() as u64 == number)
+        {
+            Some(field) => field,
+            None => return false,
+        };
+
+        let expected_wire_type = match field.proto().get_field_type() {
+            FieldDescriptorProto_Type::TYPE_DOUBLE
+            | FieldDescriptorProto_Type::TYPE_FIXED64
+            | FieldDescriptorProto_Type::TYPE_SFIXED64 => 1,
+            FieldDescriptorProto_Type::TYPE_FLOAT
+            | FieldDescriptorProto_Type::TYPE_FIXED32
+            | FieldDescriptorProto_Type::TYPE_SFIXED32 => 5,
+            FieldDescriptorProto_Type::TYPE_STRING
+            | FieldDescriptorProto_Type::TYPE_BYTES
+            | FieldDescriptorProto_Type::TYPE_MESSAGE => 2,
+            FieldDescriptorProto_Type::TYPE_GROUP => 3,
+            _ => 0,
+        };
+
+        // Repeated numbers can also be packed together
+        if wire_type != expected_wire_type && !(field.is_repeated() && wire_type == 2) {
+            return false;
+        }
+
+        match wire_type {
+            0 => {
+                if read_varint(prefix, &mut pos).is_none() {
+                    return is_truncated;
+                }
+            }
+            1 => pos += 8,
+            5 => pos += 4,
+            2 => match read_varint(prefix, &mut pos) {
+                Some(len) if len <= (total_len - pos) as u64 => pos += len as usize,
+                Some(_) => return false,
+                None => return is_truncated,
+            },
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+fn read_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0;
+
+    for i in 0..10 {
+        let b = *data.get(*pos)?;
+        *pos += 1;
+
+        value |= ((b & 0x7F) as u64) << (i * 7);
+
+        if b & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
 }
 
 pub fn encrypt(data: &mut [u8], header_type: HeaderType) -> Result<()> {
@@ -0,0 +1,211 @@
+use protobuf::reflect::{ReflectFieldRef, ReflectValueRef};
+use protobuf::Message;
+use serde::Serialize;
+
+/// A field that has a different value in two messages, such as
+/// `mission_playthroughs_data[1].mission_list[37].status`. A value is `None` where the field isn't
+/// set, such as an entry that only exists in one of the messages.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ProtobufFieldChange {
+    pub path: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl std::fmt::Display for ProtobufFieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} ➜ {}",
+            self.path,
+            self.old.as_deref().unwrap_or("<unset>"),
+            self.new.as_deref().unwrap_or("<unset>")
+        )
+    }
+}
+
+/// Compares every field of `old` against `new` using their descriptors, this includes fields that
+/// the editor doesn't read. Unknown fields are not compared.
+pub fn diff_messages<M: Message>(old: &M, new: &M) -> Vec<ProtobufFieldChange> {
+    let mut changes = Vec::new();
+
+    diff_message("", old, new, &mut changes);
+
+    changes
+}
+
+fn diff_message(
+    path: &str,
+    old: &dyn Message,
+    new: &dyn Message,
+    changes: &mut Vec<ProtobufFieldChange>,
+) {
+    for field in old.descriptor().fields() {
+        let field_path = if path.is_empty() {
+            field.name().to_owned()
+        } else {
+            format!("{}.{}", path, field.name())
+        };
+
+        match (field.get_reflect(old), field.get_reflect(new)) {
+            (ReflectFieldRef::Optional(old_value), ReflectFieldRef::Optional(new_value)) => {
+                diff_values(field_path, old_value, new_value, changes);
+            }
+            (ReflectFieldRef::Repeated(old_values), ReflectFieldRef::Repeated(new_values)) => {
+                for i in 0..old_values.len().max(new_values.len()) {
+                    let old_value = (i < old_values.len()).then(|| old_values.get(i).as_ref());
+                    let new_value = (i < new_values.len()).then(|| new_values.get(i).as_ref());
+
+                    diff_values(
+                        format!("{}[{}]", field_path, i),
+                        old_value,
+                        new_value,
+                        changes,
+                    );
+                }
+            }
+            (ReflectFieldRef::Map(old_values), ReflectFieldRef::Map(new_values)) => {
+                // Maps have no order of their own so entries are paired by key
+                let mut old_entries = old_values
+                    .reflect_iter()
+                    .map(|(k, v)| (format_value(k.as_ref()), v))
+                    .collect::<Vec<_>>();
+
+                let mut new_entries = new_values
+                    .reflect_iter()
+                    .map(|(k, v)| (format_value(k.as_ref()), v))
+                    .collect::<Vec<_>>();
+
+                old_entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                new_entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                for (key, old_value) in &old_entries {
+                    let new_value = new_entries
+                        .iter()
+                        .find(|(k, _)| k == key)
+                        .map(|(_, v)| v.as_ref());
+
+                    diff_values(
+                        format!("{}[{}]", field_path, key),
+                        Some(old_value.as_ref()),
+                        new_value,
+                        changes,
+                    );
+                }
+
+                for (key, new_value) in &new_entries {
+                    if !old_entries.iter().any(|(k, _)| k == key) {
+                        diff_values(
+                            format!("{}[{}]", field_path, key),
+                            None,
+                            Some(new_value.as_ref()),
+                            changes,
+                        );
+                    }
+                }
+            }
+            // Both messages share the same descriptor so a field is always the same kind
+            _ => (),
+        }
+    }
+}
+
+fn diff_values(
+    path: String,
+    old_value: Option<ReflectValueRef>,
+    new_value: Option<ReflectValueRef>,
+    changes: &mut Vec<ProtobufFieldChange>,
+) {
+    match (old_value, new_value) {
+        (Some(ReflectValueRef::Message(old)), Some(ReflectValueRef::Message(new))) => {
+            diff_message(&path, old, new, changes);
+        }
+        (old_value, new_value) => {
+            let old = old_value.map(format_value);
+            let new = new_value.map(format_value);
+
+            if old != new {
+                changes.push(ProtobufFieldChange { path, old, new });
+            }
+        }
+    }
+}
+
+fn format_value(value: ReflectValueRef) -> String {
+    match value {
+        ReflectValueRef::U32(v) => v.to_string(),
+        ReflectValueRef::U64(v) => v.to_string(),
+        ReflectValueRef::I32(v) => v.to_string(),
+        ReflectValueRef::I64(v) => v.to_string(),
+        ReflectValueRef::F32(v) => v.to_string(),
+        ReflectValueRef::F64(v) => v.to_string(),
+        ReflectValueRef::Bool(v) => v.to_string(),
+        ReflectValueRef::String(v) => format!("{:?}", v),
+        ReflectValueRef::Bytes(v) => v.iter().map(|b| format!("{:02x}", b)).collect(),
+        ReflectValueRef::Enum(v) => v.name().to_owned(),
+        ReflectValueRef::Message(v) => {
+            format!("{{ {} }}", protobuf::text_format::print_to_string(v))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::bl3_save::Bl3Save;
+    use crate::parser::HeaderType;
+    use crate::protos::oak_save::MissionStatusPlayerSaveGameData_MissionState;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_messages() {
+        let filename = Path::new("./test_files/19.sav");
+
+        let save = Bl3Save::from_bytes(
+            filename,
+            &std::fs::read(filename).unwrap(),
+            HeaderType::PcSave,
+        )
+        .expect("failed to read test save");
+
+        let old = save.character_data.character;
+
+        assert!(diff_messages(&old, &old).is_empty());
+
+        let mut new = old.clone();
+
+        let mission = &mut new.mission_playthroughs_data[1].mission_list[37];
+        let old_status = mission.status;
+
+        mission.status = if old_status == MissionStatusPlayerSaveGameData_MissionState::MS_Complete
+        {
+            MissionStatusPlayerSaveGameData_MissionState::MS_Active
+        } else {
+            MissionStatusPlayerSaveGameData_MissionState::MS_Complete
+        };
+
+        let new_status = mission.status;
+
+        new.preferred_character_name.push('!');
+
+        let changes = diff_messages(&old, &new);
+
+        assert_eq!(
+            changes,
+            vec![
+                ProtobufFieldChange {
+                    path: "mission_playthroughs_data[1].mission_list[37].status".to_owned(),
+                    old: Some(format!("{:?}", old_status)),
+                    new: Some(format!("{:?}", new_status)),
+                },
+                ProtobufFieldChange {
+                    path: "preferred_character_name".to_owned(),
+                    old: Some(format!("{:?}", old.preferred_character_name)),
+                    new: Some(format!("{:?}", new.preferred_character_name)),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use anyhow::{bail, Result};
+use protobuf::reflect::{ReflectFieldRef, ReflectValueRef};
+use protobuf::Message;
+
+use crate::protobuf_diff;
+
+/// A field that was read from a file but isn't in our copy of the protobufs, most likely because
+/// it was added in a game update. These are kept as raw data and written back as they were.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct UnknownField {
+    pub path: String,
+    pub number: u32,
+}
+
+impl std::fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "#{}", self.number)
+        } else {
+            write!(f, "{}.#{}", self.path, self.number)
+        }
+    }
+}
+
+/// What was found when checking that a decrypted file survives being re-encoded.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RoundTripReport {
+    pub unknown_fields: Vec<UnknownField>,
+    /// Everything that reads back differently after `message` is re-encoded, each of these would
+    /// be lost by saving.
+    pub lost_fields: Vec<String>,
+}
+
+impl RoundTripReport {
+    /// Re-encodes `message`, which was read from a file, and compares what it reads back as.
+    /// Sizes can't be compared as the game writes some repeated fields more compactly than we do,
+    /// and fields that are explicitly set to their default value are left out when re-encoding.
+    pub fn new<M: Message>(message: &M) -> Result<Self> {
+        let reencoded = M::parse_from_bytes(&message.write_to_bytes()?)?;
+
+        Ok(Self::compare(message, &reencoded))
+    }
+
+    /// Reports everything in `original` that reads back differently in `reencoded`.
+    pub fn compare<M: Message>(original: &M, reencoded: &M) -> Self {
+        let unknown_fields = find_unknown_fields(original);
+        let reencoded_unknown_fields = find_unknown_fields(reencoded);
+
+        let mut lost_fields = protobuf_diff::diff_messages(original, reencoded)
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+
+        lost_fields.extend(
+            unknown_fields
+                .iter()
+                .filter(|f| !reencoded_unknown_fields.contains(f))
+                .map(|f| format!("unknown field {}", f)),
+        );
+
+        Self {
+            unknown_fields,
+            lost_fields,
+        }
+    }
+
+    pub fn is_lossless(&self) -> bool {
+        self.lost_fields.is_empty()
+    }
+
+    /// Lists the first few fields that would be lost, for showing in errors.
+    pub fn describe_lost_fields(&self) -> String {
+        format!(
+            "{} field(s) would be lost: {}",
+            self.lost_fields.len(),
+            self.lost_fields
+                .iter()
+                .take(5)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// Finds every field in `message` (and any messages within it) that isn't in its descriptor.
+pub fn find_unknown_fields<M: Message>(message: &M) -> Vec<UnknownField> {
+    let mut unknown_fields = Vec::new();
+
+    find_unknown_fields_in("", message, &mut unknown_fields);
+
+    unknown_fields.sort();
+
+    unknown_fields
+}
+
+fn find_unknown_fields_in(path: &str, message: &dyn Message, found: &mut Vec<UnknownField>) {
+    found.extend(
+        message
+            .get_unknown_fields()
+            .iter()
+            .map(|(number, _)| UnknownField {
+                path: path.to_owned(),
+                number,
+            }),
+    );
+
+    for field in message.descriptor().fields() {
+        let field_path = if path.is_empty() {
+            field.name().to_owned()
+        } else {
+            format!("{}.{}", path, field.name())
+        };
+
+        match field.get_reflect(message) {
+            ReflectFieldRef::Optional(Some(ReflectValueRef::Message(m))) => {
+                find_unknown_fields_in(&field_path, m, found);
+            }
+            ReflectFieldRef::Repeated(values) => {
+                for i in 0..values.len() {
+                    if let ReflectValueRef::Message(m) = values.get(i).as_ref() {
+                        find_unknown_fields_in(&format!("{}[{}]", field_path, i), m, found);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Makes sure `encoded` reads back as everything in `message`, including any unknown fields. This
+/// compares bytes rather than messages so that values such as `NaN` don't count as a change.
+pub fn verify_encoded<M: Message>(message: &M, encoded: &[u8]) -> Result<()> {
+    let decoded = M::parse_from_bytes(encoded)?;
+
+    if decoded.write_to_bytes()? != encoded {
+        let changes = protobuf_diff::diff_messages(message, &decoded);
+
+        if changes.is_empty() {
+            bail!("re-encoding the file changed its unknown fields");
+        }
+
+        bail!(
+            "re-encoding the file changed {} field(s): {}",
+            changes.len(),
+            changes
+                .iter()
+                .take(5)
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::file_helper::Bl3FileType;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip_corpus() {
+        let mut files = std::fs::read_dir("./test_files")
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect::<Vec<_>>();
+
+        files.sort();
+
+        for path in files {
+            let data = std::fs::read(&path).unwrap();
+
+            let (report, header_type, output) =
+                match Bl3FileType::from_unknown_data(&path, &data).unwrap() {
+                    Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+                        let (output, new_save) = save.as_bytes().unwrap();
+
+                        assert!(protobuf_diff::diff_messages(
+                            &save.character_data.character,
+                            &new_save.character_data.character
+                        )
+                        .is_empty());
+
+                        (save.round_trip().unwrap().clone(), save.header_type, output)
+                    }
+                    Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
+                        let (output, new_profile) = profile.as_bytes().unwrap();
+
+                        assert!(protobuf_diff::diff_messages(
+                            &profile.profile_data.profile,
+                            &new_profile.profile_data.profile
+                        )
+                        .is_empty());
+
+                        (
+                            profile.round_trip().unwrap().clone(),
+                            profile.header_type,
+                            output,
+                        )
+                    }
+                };
+
+            assert!(report.is_lossless(), "{:?} dropped data", path);
+
+            // Writing the output again should give the same bytes
+            let reloaded = Bl3FileType::from_unknown_data(Path::new(&path), &output).unwrap();
+
+            let output_again = match reloaded {
+                Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+                    assert_eq!(save.header_type, header_type);
+                    assert_eq!(
+                        save.round_trip().unwrap().unknown_fields,
+                        report.unknown_fields
+                    );
+
+                    save.as_bytes().unwrap().0
+                }
+                Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
+                    assert_eq!(profile.header_type, header_type);
+                    assert_eq!(
+                        profile.round_trip().unwrap().unknown_fields,
+                        report.unknown_fields
+                    );
+
+                    profile.as_bytes().unwrap().0
+                }
+            };
+
+            assert_eq!(output_again, output, "{:?} is not stable", path);
+        }
+    }
+
+    #[test]
+    fn test_unknown_fields_are_kept() {
+        use crate::protos::oak_save::Character;
+
+        let mut character = Character::new();
+        character.set_preferred_character_name("Amara".to_owned());
+        character
+            .mut_unknown_fields()
+            .add_length_delimited(9999, b"new game data".to_vec());
+
+        let encoded = character.write_to_bytes().unwrap();
+
+        assert_eq!(
+            find_unknown_fields(&character),
+            vec![UnknownField {
+                path: "".to_owned(),
+                number: 9999,
+            }]
+        );
+
+        verify_encoded(&character, &encoded).unwrap();
+
+        let report = RoundTripReport::new(&Character::parse_from_bytes(&encoded).unwrap()).unwrap();
+
+        assert!(report.is_lossless());
+        assert_eq!(report.unknown_fields.len(), 1);
+
+        // Losing the unknown field is reported as dropped data
+        let mut stripped = character.clone();
+        *stripped.mut_unknown_fields() = protobuf::UnknownFields::new();
+
+        let report = RoundTripReport::compare(&character, &stripped);
+
+        assert!(!report.is_lossless());
+        assert_eq!(report.lost_fields, vec!["unknown field #9999".to_owned()]);
+    }
+
+    #[test]
+    fn test_explicit_defaults_are_lossless() {
+        use crate::protos::oak_save::Character;
+
+        let mut character = Character::new();
+        character.set_preferred_character_name("Amara".to_owned());
+
+        let mut encoded = character.write_to_bytes().unwrap();
+
+        // experience_points (field 7) written as 0, which re-encoding leaves out
+        encoded.extend([7 << 3, 0]);
+
+        let parsed = Character::parse_from_bytes(&encoded).unwrap();
+
+        assert!(parsed.write_to_bytes().unwrap().len() < encoded.len());
+        assert!(RoundTripReport::new(&parsed).unwrap().is_lossless());
+
+        // A field that changes is reported even when the re-encoded file is larger
+        let mut changed = parsed.clone();
+        changed.set_experience_points(1000);
+
+        assert_eq!(
+            RoundTripReport::compare(&parsed, &changed).lost_fields,
+            vec!["experience_points: <unset> ➜ 1000".to_owned()]
+        );
+    }
+}
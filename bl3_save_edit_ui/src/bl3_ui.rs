@@ -1,12 +1,14 @@
 use std::mem;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use iced::alignment::Horizontal;
+use iced::keyboard::{self, KeyCode};
 use iced::{
     button, pick_list, svg, tooltip, Alignment, Application, Button, Color, Column, Command,
-    Container, Element, Length, PickList, Row, Svg, Text, Tooltip,
+    Container, Element, Length, PickList, Row, Subscription, Svg, Text, Tooltip,
 };
-use tracing::{error, info};
+use iced_native::{event, window, Event};
+use tracing::{error, info, warn};
 
 use bl3_save_edit_core::bl3_profile::sdu::ProfileSduSlot;
 use bl3_save_edit_core::bl3_profile::Bl3Profile;
@@ -15,25 +17,43 @@ use bl3_save_edit_core::bl3_save::sdu::SaveSduSlot;
 use bl3_save_edit_core::bl3_save::util::{experience_to_level, REQUIRED_XP_LIST};
 use bl3_save_edit_core::bl3_save::Bl3Save;
 use bl3_save_edit_core::file_helper::Bl3FileType;
-use bl3_save_edit_core::parser::HeaderType;
+use bl3_save_edit_core::item_index::IndexedItem;
+use bl3_save_edit_core::models::GvasHeader;
+use bl3_save_edit_core::parser::{HeaderType, PayloadFormat};
 
 use crate::bl3_ui_style::{
     Bl3UiContentStyle, Bl3UiMenuBarStyle, Bl3UiPositiveButtonStyle, Bl3UiStyle, Bl3UiTooltipStyle,
 };
+use crate::commands::interaction::backups::Backup;
+use crate::commands::interaction::choose_save_directory::{
+    DirectoryLoad, DirectoryLoadEvent, LoadedFiles, UnreadableFile,
+};
+use crate::commands::interaction::file_fingerprint::{
+    FileFingerprint, FileFingerprints, FileModifiedError, FileModifiedKind, OverwriteCheck,
+};
+use crate::commands::interaction::file_save::{FilesAfterSave, GuardianDataInjection, SavedFile};
+use crate::commands::interaction::save_slots::SlotChange;
+use crate::commands::interaction::saves_dir_watch::SavesDirWatch;
 use crate::commands::{initialization, interaction};
 use crate::config::{Bl3Config, ConfigMessage};
 use crate::resources::fonts::{
     JETBRAINS_MONO, JETBRAINS_MONO_BOLD, JETBRAINS_MONO_NL_EXTRA_BOLD_ITALIC,
 };
-use crate::resources::svgs::REFRESH;
-use crate::state_mappers::{manage_profile, manage_save};
+use crate::resources::svgs::{BACKUPS, CHARACTER, DUPLICATES, REFRESH, SEARCH};
+use crate::state_mappers::edit_history::EditHistory;
+use crate::state_mappers::{edit_history, manage_profile, manage_save};
 use crate::update::Release;
-use crate::util::ErrorExt;
+use crate::util::{self, ErrorExt};
+use crate::views::backups::{BackupsInteractionMessage, BackupsState};
 use crate::views::choose_save_directory::{
     ChooseSaveDirectoryState, ChooseSaveInteractionMessage, ChooseSaveMessage,
 };
+use crate::views::duplicate_items::{DuplicateItemsInteractionMessage, DuplicateItemsState};
+use crate::views::gvas_header::GvasHeaderInteractionMessage;
 use crate::views::initialization::InitializationMessage;
+use crate::views::item_editor::item_compare::ItemCompare;
 use crate::views::item_editor::ItemEditorFileType;
+use crate::views::item_search::{ItemSearchInteractionMessage, ItemSearchState};
 use crate::views::manage_profile::bank::ProfileBankInteractionMessage;
 use crate::views::manage_profile::general::ProfileGeneralInteractionMessage;
 use crate::views::manage_profile::keys::ProfileKeysInteractionMessage;
@@ -54,9 +74,11 @@ use crate::views::manage_save::inventory::SaveInventoryInteractionMessage;
 use crate::views::manage_save::main::{SaveTabBarInteractionMessage, SaveTabBarView};
 use crate::views::manage_save::vehicle::{SaveVehicleInteractionMessage, VehicleUnlockedMessage};
 use crate::views::manage_save::{ManageSaveInteractionMessage, ManageSaveState, ManageSaveView};
+use crate::views::save_slots::{SaveSlotsInteractionMessage, SaveSlotsState};
 use crate::views::settings::{SettingsInteractionMessage, SettingsState};
 use crate::views::InteractionExt;
 use crate::widgets::notification::{Notification, NotificationSentiment};
+use crate::widgets::prompt::Prompt;
 use crate::{state_mappers, update, views, VERSION};
 
 #[derive(Debug, Default)]
@@ -66,16 +88,39 @@ pub struct Bl3Application {
     choose_save_directory_state: ChooseSaveDirectoryState,
     pub manage_save_state: ManageSaveState,
     pub manage_profile_state: ManageProfileState,
-    loaded_files_selector: pick_list::State<Bl3FileType>,
+    pub edit_history: EditHistory,
+    loaded_files_selector: pick_list::State<LoadedFileEntry>,
+    unreadable_files_selector: pick_list::State<LoadedFileEntry>,
     pub loaded_files_selected: Box<Bl3FileType>,
-    loaded_files: Vec<Bl3FileType>,
+    pub loaded_files: Vec<Bl3FileType>,
+    unreadable_files: Vec<UnreadableFile>,
     refresh_button_state: button::State,
+    duplicate_items_button_state: button::State,
+    duplicate_items_state: DuplicateItemsState,
+    item_search_button_state: button::State,
+    item_search_state: ItemSearchState,
+    backups_button_state: button::State,
+    backups_state: BackupsState,
+    save_slots_button_state: button::State,
+    save_slots_state: SaveSlotsState,
     update_button_state: button::State,
     save_file_button_state: button::State,
     notification: Option<Notification>,
+    prompt: Option<Prompt>,
+    file_fingerprints: FileFingerprints,
+    // A file that was changed by another program while it had unsaved edits that were kept, saving
+    // over it has to be confirmed until it is reloaded or overwritten
+    kept_edits_over_changes: Option<String>,
     latest_release: Option<Release>,
     is_updating: bool,
     is_reloading_saves: bool,
+    directory_load: Option<DirectoryLoadProgress>,
+    directory_loads_started: u64,
+    is_saving: bool,
+    is_checking_saves_dir: bool,
+    // The saves folder changed while a prompt was open, it's checked once the prompt is closed
+    is_saves_dir_check_queued: bool,
+    should_exit: bool,
     settings_state: SettingsState,
 }
 
@@ -88,10 +133,25 @@ pub enum Bl3Message {
     Config(ConfigMessage),
     Interaction(InteractionMessage),
     ChooseSave(ChooseSaveMessage),
-    SaveFileCompleted(MessageResult<Bl3Save>),
-    SaveProfileCompleted(MessageResult<Bl3Profile>),
-    FilesLoadedAfterSave(MessageResult<(Bl3FileType, Vec<Bl3FileType>)>),
+    SaveFileCompleted(MessageResult<SavedFile<Bl3Save>>),
+    SaveProfileCompleted(MessageResult<SavedFile<Bl3Profile>>),
+    DirectoryLoad(u64, DirectoryLoadEvent),
+    FilesLoadedAfterSave(MessageResult<FilesAfterSave>),
+    FileModifiedExternally(String),
+    SaveAsDirChosen(MessageResult<PathBuf>),
+    FilesReloaded(EditorReload, MessageResult<LoadedFiles<String>>),
+    CheckSavesDirectory,
+    SavesDirectoryChecked(MessageResult<Vec<String>>),
+    DiagnosticsCompleted(MessageResult<(String, Vec<String>)>),
+    DuplicateItemsRemoved(MessageResult<LoadedFiles<usize>>),
+    BackupsLoaded(MessageResult<Vec<Backup>>),
+    BackupRestored(MessageResult<LoadedFiles<String>>),
+    SaveSlotsChanged(MessageResult<LoadedFiles<SlotChange>>),
+    PromptActionPressed(Box<Bl3Message>),
+    ClearPrompt,
     ClearNotification,
+    WindowCloseRequested,
+    Exit,
 }
 
 #[derive(Debug, Clone)]
@@ -127,8 +187,26 @@ pub enum InteractionMessage {
     ManageSaveInteraction(ManageSaveInteractionMessage),
     ManageProfileInteraction(ManageProfileInteractionMessage),
     SettingsInteraction(SettingsInteractionMessage),
-    LoadedFileSelected(Box<Bl3FileType>),
+    DuplicateItemsInteraction(DuplicateItemsInteractionMessage),
+    ItemSearchInteraction(ItemSearchInteractionMessage),
+    BackupsInteraction(BackupsInteractionMessage),
+    SaveSlotsInteraction(SaveSlotsInteractionMessage),
+    LoadedFileSelected(LoadedFileEntry),
+    RunDiagnostics(PathBuf),
+    SwitchLoadedFile(Box<Bl3FileType>),
+    JumpToItem(Box<IndexedItem>),
+    ConfirmSave,
     RefreshSavesDirectory,
+    ReloadModifiedFile,
+    KeepUnsavedEdits,
+    OverwriteModifiedFile,
+    SaveModifiedFileAs,
+    Undo,
+    Redo,
+    FindDuplicateItems,
+    SearchAllItems,
+    ManageBackups,
+    ManageSaveSlots,
     Ignore,
 }
 
@@ -139,6 +217,10 @@ pub enum ViewState {
     ChooseSaveDirectory,
     ManageSave(ManageSaveView),
     ManageProfile(ManageProfileView),
+    DuplicateItems,
+    ItemSearch,
+    Backups,
+    SaveSlots,
 }
 
 impl std::default::Default for ViewState {
@@ -147,62 +229,763 @@ impl std::default::Default for ViewState {
     }
 }
 
-impl Application for Bl3Application {
-    type Executor = tokio::runtime::Runtime;
-    type Message = Bl3Message;
-    type Flags = Bl3Config;
+/// A saves folder that is being loaded, `read` counts unreadable files too.
+#[derive(Debug, Clone)]
+struct DirectoryLoadProgress {
+    load: DirectoryLoad,
+    read: usize,
+    /// The names of the files that will be read, sorted by name.
+    listed: Option<Vec<String>>,
+    /// The file that was open before, when loading the same folder again.
+    previous_selection: Option<String>,
+}
 
-    fn new(config: Self::Flags) -> (Self, Command<Self::Message>) {
-        let startup_commands = [
-            Command::perform(initialization::load_lazy_data(), |_| {
-                Bl3Message::Initialization(InitializationMessage::LoadSaves)
-            }),
-            Command::perform(update::get_latest_release(), |r| {
-                Bl3Message::LatestRelease(MessageResult::handle_result(r))
-            }),
-        ];
+/// What happens to the editor when the saves folder is loaded again.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EditorReload {
+    /// Map the selected file to the editor again, losing any unsaved changes.
+    Remap,
+    /// Only update the list of files.
+    KeepEdits,
+}
 
-        let config_dir_input = config.config_dir().to_string_lossy().to_string();
-        let saves_dir_input = config.saves_dir().to_string_lossy().to_string();
-        let backup_dir_input = config.backup_dir().to_string_lossy().to_string();
-        let ui_scale_factor = config.ui_scale_factor();
+/// An entry in the list of files, files that couldn't be read are listed with the reason so that
+/// they don't just disappear.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LoadedFileEntry {
+    Readable { file_name: String, label: String },
+    Unreadable(UnreadableFile),
+}
 
-        (
-            Bl3Application {
-                config,
-                view_state: ViewState::Initializing,
-                settings_state: SettingsState {
-                    config_dir_input,
-                    backup_dir_input,
-                    saves_dir_input,
-                    ui_scale_factor,
-                    ..SettingsState::default()
-                },
-                ..Bl3Application::default()
-            },
-            Command::batch(startup_commands),
+impl LoadedFileEntry {
+    fn readable(file: &Bl3FileType) -> Self {
+        LoadedFileEntry::Readable {
+            file_name: file.filename().to_owned(),
+            label: file.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for LoadedFileEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadedFileEntry::Readable { label, .. } => write!(f, "{}", label),
+            LoadedFileEntry::Unreadable(file) => {
+                let mut reason = file.reason.chars().take(60).collect::<String>();
+
+                if reason.len() < file.reason.len() {
+                    reason.push_str("...");
+                }
+
+                write!(f, "{} - {}", file.file_name, reason)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SaveMode {
+    /// Refuse to save if the file was changed by something else after it was loaded.
+    Checked,
+    Overwrite,
+    /// Save to the same file name in another folder.
+    SaveAs(PathBuf),
+}
+
+impl Bl3Application {
+    /// Shows a summary of what is about to be written before saving the selected file.
+    fn confirm_save(&mut self) -> Command<Bl3Message> {
+        match state_mappers::unsaved_changes(self) {
+            Ok(Some(changes)) => {
+                let msg = if changes.is_empty() {
+                    format!("Save changes to {}?", self.loaded_files_selected.filename())
+                } else {
+                    format!(
+                        "Save {} change(s) to {}?",
+                        changes.len(),
+                        self.loaded_files_selected.filename()
+                    )
+                };
+
+                let mut details = state_mappers::describe_unsaved_changes(&changes);
+
+                let payload_format = match *self.loaded_files_selected {
+                    Bl3FileType::PcSave(_) | Bl3FileType::Ps4Save(_) => {
+                        self.manage_save_state
+                            .save_view_state
+                            .general_state
+                            .payload_format_selected
+                    }
+                    Bl3FileType::PcProfile(_) | Bl3FileType::Ps4Profile(_) => {
+                        self.manage_profile_state
+                            .profile_view_state
+                            .general_state
+                            .payload_format_selected
+                    }
+                };
+
+                // Only encrypted files can be read by the game, anything else is for other tools
+                if payload_format != PayloadFormat::Encrypted {
+                    details.insert(
+                        0,
+                        format!(
+                            "Warning: the game can't read {} files, save them to another folder unless you're sure",
+                            payload_format
+                        ),
+                    );
+
+                    self.prompt = Some(
+                        Prompt::new(msg)
+                            .details(details)
+                            .action(
+                                "Save As",
+                                Bl3Message::Interaction(InteractionMessage::SaveModifiedFileAs),
+                            )
+                            .action(
+                                "Save Anyway",
+                                Bl3Message::Interaction(InteractionMessage::ConfirmSave),
+                            ),
+                    );
+                } else {
+                    self.prompt = Some(Prompt::new(msg).details(details).action(
+                        "Save",
+                        Bl3Message::Interaction(InteractionMessage::ConfirmSave),
+                    ));
+                }
+
+                Command::none()
+            }
+            // Nothing to summarise, saving will report it if the editor can't be mapped
+            _ => self.save_selected_file(SaveMode::Checked),
+        }
+    }
+
+    /// Asks before running `on_confirm` if the editor has unsaved changes, returns whether it asked.
+    fn confirm_discard_changes(
+        &mut self,
+        msg: String,
+        action_label: &str,
+        on_confirm: Bl3Message,
+    ) -> bool {
+        if self.loaded_files.is_empty() {
+            return false;
+        }
+
+        let changes = match state_mappers::unsaved_changes(self) {
+            Ok(None) => return false,
+            Ok(Some(changes)) => state_mappers::describe_unsaved_changes(&changes),
+            Err(e) => vec![format!("Failed to read the editor: {}", e)],
+        };
+
+        self.prompt = Some(
+            Prompt::new(msg)
+                .details(changes)
+                .action(action_label, on_confirm),
+        );
+
+        true
+    }
+
+    fn switch_loaded_file(&mut self, loaded_file: Box<Bl3FileType>) {
+        self.loaded_files_selected = loaded_file;
+        self.kept_edits_over_changes = None;
+
+        state_mappers::map_loaded_file_to_state(self).handle_ui_error(
+            "Failed to map loaded file to editor",
+            &mut self.notification,
+        );
+
+        self.notify_round_trip_issues();
+    }
+
+    fn jump_to_item(&mut self, indexed_item: &IndexedItem) {
+        if self.loaded_files_selected.filename() != indexed_item.file_name {
+            self.kept_edits_over_changes = None;
+        }
+
+        state_mappers::jump_to_item(self, indexed_item)
+            .handle_ui_error("Failed to open item in editor", &mut self.notification);
+    }
+
+    /// Lists the selected file as unreadable when it can't be opened and goes back to the file that
+    /// is still open in the editor, or failing that the first file that can be opened.
+    pub fn unselect_unopenable_file(&mut self, reason: String) {
+        let file_name = self.loaded_files_selected.filename().to_owned();
+
+        self.loaded_files.retain(|f| f.filename() != file_name);
+        self.unreadable_files.retain(|f| f.file_name != file_name);
+
+        self.unreadable_files.push(UnreadableFile {
+            path: self.config.saves_dir().join(&file_name),
+            file_name,
+            reason,
+        });
+        self.unreadable_files
+            .sort_by(|a, b| a.file_name.cmp(&b.file_name));
+
+        let open_file_name = match self.view_state {
+            ViewState::ManageSave(_) => Some(&self.manage_save_state.current_file.file_name),
+            ViewState::ManageProfile(_) => Some(&self.manage_profile_state.current_file.file_name),
+            _ => None,
+        };
+
+        let open_file = open_file_name
+            .and_then(|open_file_name| {
+                self.loaded_files
+                    .iter()
+                    .find(|f| f.filename() == open_file_name)
+            })
+            .cloned();
+
+        if let Some(open_file) = open_file {
+            self.loaded_files_selected = Box::new(open_file);
+        } else if self.directory_load.is_some() {
+            // While loading the next file to open is picked once it has been read
+            self.view_state = ViewState::Loading;
+        } else if let Some(first_file) = self.loaded_files.first().cloned() {
+            self.loaded_files_selected = Box::new(first_file);
+
+            // Any file that can't be opened either is moved out of the list in the same way
+            let _ = state_mappers::map_loaded_file_to_state(self);
+        } else {
+            self.view_state = ViewState::ChooseSaveDirectory;
+        }
+    }
+
+    /// Let the user know if the selected file has data we don't understand, saving it is refused
+    /// when some of it couldn't be read at all.
+    fn notify_round_trip_issues(&mut self) {
+        let file_name = self.loaded_files_selected.filename().to_owned();
+
+        let round_trip = match self.loaded_files_selected.round_trip() {
+            Ok(round_trip) => round_trip,
+            Err(e) => {
+                let msg = format!("{} could not be checked before saving: {:#}.", file_name, e);
+
+                error!("{}", msg);
+
+                self.notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+
+                return;
+            }
+        };
+
+        if !round_trip.is_lossless() {
+            let msg = format!(
+                "{} could not be fully read so it can't be saved, {}.",
+                file_name,
+                round_trip.describe_lost_fields()
+            );
+
+            error!("{}", msg);
+
+            self.notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+        } else if !round_trip.unknown_fields.is_empty() {
+            warn!(
+                "{} has unknown fields: {}",
+                file_name,
+                round_trip
+                    .unknown_fields
+                    .iter()
+                    .map(|f| f.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
+            let msg = format!(
+                "{} has {} field(s) from a newer game version, they will be kept as they are.",
+                file_name,
+                round_trip.unknown_fields.len()
+            );
+
+            self.notification = Some(Notification::new(msg, NotificationSentiment::Info));
+        }
+    }
+
+    fn save_selected_file(&mut self, save_mode: SaveMode) -> Command<Bl3Message> {
+        // The saved file is put into the list afterwards, which would race with the files still
+        // arriving
+        if self.directory_load.is_some() {
+            self.notification = Some(Notification::new(
+                "Please wait for the saves folder to finish loading before saving.",
+                NotificationSentiment::Info,
+            ));
+
+            return Command::none();
+        }
+
+        // Its fingerprint was updated when the edits were kept so saving wouldn't notice the change
+        if save_mode == SaveMode::Checked
+            && self.kept_edits_over_changes.as_deref()
+                == Some(self.loaded_files_selected.filename())
+        {
+            let reason = FileModifiedError {
+                file_name: self.loaded_files_selected.filename().to_owned(),
+                kind: FileModifiedKind::Changed,
+            };
+
+            self.prompt_file_modified(reason.to_string());
+
+            return Command::none();
+        }
+
+        match *self.loaded_files_selected {
+            Bl3FileType::PcSave(_) | Bl3FileType::Ps4Save(_) => self.save_file(save_mode),
+            Bl3FileType::PcProfile(_) | Bl3FileType::Ps4Profile(_) => self.save_profile(save_mode),
+        }
+    }
+
+    /// Asks what to do when the file that is being saved over isn't as it was loaded, `reason` is
+    /// what changed.
+    fn prompt_file_modified(&mut self, reason: String) {
+        let msg = format!(
+            "{}. Reload it and lose your edits, overwrite it or save your edits to another folder?",
+            reason
+        );
+
+        info!("{}", msg);
+
+        self.prompt = Some(
+            Prompt::new(msg)
+                .action(
+                    "Reload",
+                    Bl3Message::Interaction(InteractionMessage::ReloadModifiedFile),
+                )
+                .action(
+                    "Overwrite",
+                    Bl3Message::Interaction(InteractionMessage::OverwriteModifiedFile),
+                )
+                .action(
+                    "Save As",
+                    Bl3Message::Interaction(InteractionMessage::SaveModifiedFileAs),
+                ),
+        );
+    }
+
+    fn save_file(&mut self, save_mode: SaveMode) -> Command<Bl3Message> {
+        //Lets not make any modifications to the current file just in case we have any errors
+        let mut current_file = self.manage_save_state.current_file.clone();
+
+        if let Err(e) =
+            manage_save::map_all_states_to_save(&mut self.manage_save_state, &mut current_file)
+        {
+            let msg = format!("Failed to save file: {}", e);
+
+            error!("{}", msg);
+
+            self.notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+
+            return Command::none();
+        }
+
+        let (output_file, overwrite_check) =
+            self.output_file(&self.manage_save_state.current_file.file_name, save_mode);
+
+        // Back up the file as it was loaded, `current_file` may already have edits applied to it
+        let existing_save = match &*self.loaded_files_selected {
+            Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => save.clone(),
+            _ => self.manage_save_state.current_file.clone(),
+        };
+
+        match current_file.as_bytes() {
+            Ok((output, save_file)) => {
+                self.is_saving = true;
+
+                Command::perform(
+                    interaction::file_save::save_file(
+                        self.config.backup_dir().to_path_buf(),
+                        self.config.backup_retention(),
+                        output_file,
+                        output,
+                        existing_save,
+                        save_file,
+                        overwrite_check,
+                    ),
+                    |r| save_completed_message(r, Bl3Message::SaveFileCompleted),
+                )
+            }
+            Err(e) => {
+                let msg = format!("Failed to save file: {}", e);
+
+                error!("{}", msg);
+
+                self.notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+
+                Command::none()
+            }
+        }
+    }
+
+    fn save_profile(&mut self, save_mode: SaveMode) -> Command<Bl3Message> {
+        //Lets not make any modifications to the current file just in case we have any errors
+        let mut current_file = self.manage_profile_state.current_file.clone();
+
+        let guardian_data_injection_required = match manage_profile::map_all_states_to_profile(
+            &mut self.manage_profile_state,
+            &mut current_file,
+        ) {
+            Ok(injection_required) => injection_required,
+            Err(e) => {
+                let msg = format!("Failed to save profile: {}", e);
+
+                error!("{}", msg);
+
+                self.notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+
+                return Command::none();
+            }
+        };
+
+        let (output_file, overwrite_check) =
+            self.output_file(&self.manage_profile_state.current_file.file_name, save_mode);
+
+        let existing_profile = match &*self.loaded_files_selected {
+            Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => profile.clone(),
+            _ => self.manage_profile_state.current_file.clone(),
+        };
+
+        let guardian_data_injection =
+            guardian_data_injection_required.then(|| GuardianDataInjection {
+                saves: self
+                    .loaded_files
+                    .iter()
+                    .filter(|f| matches!(f, Bl3FileType::PcSave(_) | Bl3FileType::Ps4Save(_)))
+                    .cloned()
+                    .collect(),
+                file_fingerprints: self.file_fingerprints.clone(),
+            });
+
+        match current_file.as_bytes() {
+            Ok((output, profile)) => {
+                self.is_saving = true;
+
+                Command::perform(
+                    interaction::file_save::save_profile(
+                        self.config.backup_dir().to_path_buf(),
+                        self.config.backup_retention(),
+                        self.config.saves_dir().to_path_buf(),
+                        output_file,
+                        output,
+                        existing_profile,
+                        profile,
+                        overwrite_check,
+                        guardian_data_injection,
+                    ),
+                    |r| save_completed_message(r, Bl3Message::SaveProfileCompleted),
+                )
+            }
+            Err(e) => {
+                let msg = format!("Failed to save file: {}", e);
+
+                error!("{}", msg);
+
+                self.notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+
+                Command::none()
+            }
+        }
+    }
+
+    fn reload_files(&mut self, editor_reload: EditorReload) -> Command<Bl3Message> {
+        self.is_reloading_saves = true;
+
+        Command::perform(
+            interaction::choose_save_directory::reload_files_in_directory(
+                self.config.saves_dir().to_path_buf(),
+                self.loaded_files_selected.filename().to_owned(),
+            ),
+            move |r| Bl3Message::FilesReloaded(editor_reload, MessageResult::handle_result(r)),
         )
     }
 
-    fn title(&self) -> String {
-        format!("Borderlands 3 Save Editor - v{}", VERSION)
+    /// Starts loading the saves folder `dir`, files are added to the picker as they are read.
+    fn load_directory(&mut self, dir: PathBuf) {
+        self.directory_loads_started += 1;
+
+        let id = self.directory_loads_started;
+
+        let previous_selection = (!self.loaded_files.is_empty() && dir == *self.config.saves_dir())
+            .then(|| self.loaded_files_selected.filename().to_owned());
+
+        self.directory_load = Some(DirectoryLoadProgress {
+            load: DirectoryLoad { id, dir },
+            read: 0,
+            listed: None,
+            previous_selection,
+        });
+
+        self.loaded_files.clear();
+        self.unreadable_files.clear();
+        self.file_fingerprints.clear();
+        self.kept_edits_over_changes = None;
+
+        self.view_state = ViewState::Loading;
     }
 
-    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+    fn finish_directory_load(&mut self) -> Command<Bl3Message> {
+        let directory_load = match self.directory_load.take() {
+            Some(directory_load) => directory_load,
+            None => return Command::none(),
+        };
+
+        if let Err(e) = interaction::choose_save_directory::ensure_files_found(
+            &self.loaded_files,
+            &self.unreadable_files,
+        ) {
+            self.directory_load_failed(e);
+
+            return Command::none();
+        }
+
+        self.open_file_while_loading(&directory_load);
+
+        // The unreadable files are listed when choosing a folder instead
+        if self.loaded_files.is_empty() {
+            self.view_state = ViewState::ChooseSaveDirectory;
+
+            self.notification = Some(Notification::new(
+                format!(
+                    "None of the files in {} could be read.",
+                    directory_load.load.dir.display()
+                ),
+                NotificationSentiment::Negative,
+            ));
+
+            return Command::none();
+        }
+
+        info!(
+            "Read {} files from: {}",
+            self.loaded_files.len(),
+            directory_load.load.dir.display()
+        );
+
+        state_mappers::map_copy_from_files(self);
+
+        self.config.set_saves_dir(directory_load.load.dir);
+        self.settings_state.saves_dir_input = self.config.saves_dir().to_string_lossy().to_string();
+
+        Command::perform(self.config.clone().save(), |r| {
+            Bl3Message::Config(ConfigMessage::SaveCompleted(MessageResult::handle_result(
+                r,
+            )))
+        })
+    }
+
+    /// Opens the file that was open before loading the folder again, or otherwise the first file by
+    /// name, once it has been read. Nothing is opened while a file that comes before it hasn't been
+    /// read yet so that it doesn't depend on which file is read first.
+    fn open_file_while_loading(&mut self, directory_load: &DirectoryLoadProgress) {
+        let listed = match &directory_load.listed {
+            Some(listed) => listed,
+            None => return,
+        };
+
+        let previous_selection = directory_load
+            .previous_selection
+            .as_ref()
+            .filter(|name| listed.contains(name));
+
+        // Files that can't be opened are moved out of the list so the next one is tried
+        while self.view_state == ViewState::Loading {
+            let mut file_to_open = None;
+
+            for name in previous_selection.into_iter().chain(listed) {
+                if let Ok(pos) = self
+                    .loaded_files
+                    .binary_search_by(|f| f.filename().cmp(name))
+                {
+                    file_to_open = Some(self.loaded_files[pos].clone());
+                    break;
+                }
+
+                if !self.unreadable_files.iter().any(|f| f.file_name == *name) {
+                    break;
+                }
+            }
+
+            match file_to_open {
+                Some(file) => self.switch_loaded_file(Box::new(file)),
+                None => break,
+            }
+        }
+    }
+
+    /// How many files have been read so far, while the saves folder is loading.
+    fn directory_load_progress(&self) -> Option<String> {
+        let directory_load = self.directory_load.as_ref()?;
+
+        Some(match &directory_load.listed {
+            Some(listed) => format!("Loading {}/{}", directory_load.read, listed.len()),
+            None => "Loading...".to_owned(),
+        })
+    }
+
+    fn directory_load_failed<E: std::fmt::Display>(&mut self, e: E) {
+        let msg = format!("Failed to load save folder: {}", e);
+
+        error!("{}", msg);
+
+        self.view_state = ViewState::ChooseSaveDirectory;
+
+        self.notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+    }
+
+    /// Whether files in the saves folder are being written or loaded by us.
+    /// Checks the saves folder if it changed while a prompt was open.
+    fn check_queued_saves_dir(&mut self) -> Command<Bl3Message> {
+        if mem::take(&mut self.is_saves_dir_check_queued) {
+            self.handle_message(Bl3Message::CheckSavesDirectory)
+        } else {
+            Command::none()
+        }
+    }
+
+    fn is_busy(&self) -> bool {
+        self.is_saving
+            || self.is_reloading_saves
+            || self.directory_load.is_some()
+            || self.duplicate_items_state.is_removing
+            || self.backups_state.is_restoring
+            || self.save_slots_state.is_changing
+    }
+
+    /// The header of `file_name` in the saves folder, to be applied to the file being edited.
+    fn copy_header_from(&mut self, file_name: &str) -> Option<GvasHeader> {
+        match self.loaded_files.iter().find(|f| f.filename() == file_name) {
+            Some(file) => {
+                let header = file.header();
+
+                self.notification = Some(Notification::new(
+                    format!(
+                        "Copied the header from {} (build {}), save to apply it.",
+                        file_name, header.build_id
+                    ),
+                    NotificationSentiment::Info,
+                ));
+
+                Some(header)
+            }
+            None => {
+                self.notification = Some(Notification::new(
+                    format!("{} is no longer in the saves folder.", file_name),
+                    NotificationSentiment::Negative,
+                ));
+
+                None
+            }
+        }
+    }
+
+    /// A copy of the save called `file_name` as it was loaded from the saves folder.
+    fn loaded_save(&self, file_name: &str) -> Option<Bl3Save> {
+        self.loaded_files.iter().find_map(|f| match f {
+            Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save)
+                if save.file_name == file_name =>
+            {
+                Some(save.clone())
+            }
+            _ => None,
+        })
+    }
+
+    /// The slots used by every save other than `file_name`.
+    fn used_save_slots(&self, file_name: &str) -> Vec<u32> {
+        self.loaded_files
+            .iter()
+            .filter_map(|f| match f {
+                Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save)
+                    if save.file_name != file_name =>
+                {
+                    Some(save.character_data.character.save_game_id)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn move_save_slot(&mut self, file_name: String) -> Command<Bl3Message> {
+        let save_game_id = match self.save_slots_state.slot(&file_name) {
+            Some(slot) => slot.slot_input,
+            None => return Command::none(),
+        };
+
+        match self.loaded_save(&file_name) {
+            Some(save) => {
+                self.save_slots_state.is_changing = true;
+
+                Command::perform(
+                    interaction::save_slots::renumber_save(
+                        self.config.backup_dir().to_path_buf(),
+                        self.config.backup_retention(),
+                        self.config.saves_dir().to_path_buf(),
+                        save,
+                        save_game_id,
+                        self.used_save_slots(&file_name),
+                    ),
+                    |r| Bl3Message::SaveSlotsChanged(MessageResult::handle_result(r)),
+                )
+            }
+            None => Command::none(),
+        }
+    }
+
+    /// Puts a file that was just saved into the list of files in place of what was read before.
+    fn replace_loaded_file(&mut self, file: Bl3FileType, file_fingerprint: FileFingerprint) {
+        let file_name = file.filename().to_owned();
+
+        if self.kept_edits_over_changes.as_ref() == Some(&file_name) {
+            self.kept_edits_over_changes = None;
+        }
+
+        self.unreadable_files.retain(|f| f.file_name != file_name);
+
+        match self
+            .loaded_files
+            .iter_mut()
+            .find(|f| f.filename() == file_name)
+        {
+            Some(loaded_file) => *loaded_file = file,
+            None => interaction::choose_save_directory::insert_sorted(&mut self.loaded_files, file),
+        }
+
+        self.file_fingerprints.insert(file_name, file_fingerprint);
+    }
+
+    /// Where to write `file_name` to and what must be true of any file already there.
+    fn output_file(&self, file_name: &str, save_mode: SaveMode) -> (PathBuf, OverwriteCheck) {
+        let saves_dir = self.config.saves_dir();
+
+        // A file name that wasn't loaded, such as after changing the format, mustn't be in the way
+        let checked = || match self.file_fingerprints.get(file_name) {
+            Some(file_fingerprint) => OverwriteCheck::Unchanged(*file_fingerprint),
+            None => OverwriteCheck::Missing,
+        };
+
+        match save_mode {
+            SaveMode::Checked => (saves_dir.join(file_name), checked()),
+            SaveMode::Overwrite => (saves_dir.join(file_name), OverwriteCheck::Any),
+            SaveMode::SaveAs(dir) => {
+                // Saving as into the saves folder is the same as saving normally
+                let overwrite_check = if dir == *saves_dir {
+                    checked()
+                } else {
+                    OverwriteCheck::Any
+                };
+
+                (dir.join(file_name), overwrite_check)
+            }
+        }
+    }
+
+    fn handle_message(&mut self, message: Bl3Message) -> Command<Bl3Message> {
         match message {
             Bl3Message::Initialization(initialization_msg) => match initialization_msg {
                 InitializationMessage::LoadSaves => {
                     if self.config.saves_dir().exists() {
-                        return Command::perform(
-                            interaction::choose_save_directory::load_files_in_directory(
-                                self.config.saves_dir().to_path_buf(),
-                            ),
-                            |r| {
-                                Bl3Message::ChooseSave(ChooseSaveMessage::FilesLoaded(
-                                    MessageResult::handle_result(r),
-                                ))
-                            },
-                        );
+                        self.load_directory(self.config.saves_dir().to_path_buf());
+
+                        return Command::none();
                     } else if *self.config.saves_dir() != PathBuf::default() {
                         let msg = "Failed to load your previously selected Save/Profile folder. Please select another folder.";
 
@@ -333,7 +1116,15 @@ impl Application for Bl3Application {
                                         .guid_input = guid;
                                 }
                                 SaveGeneralInteractionMessage::Slot(slot) => {
-                                    let filename = format!("{:x}.sav", slot);
+                                    let filename = format!(
+                                        "{:x}.{}",
+                                        slot,
+                                        self.manage_save_state
+                                            .save_view_state
+                                            .general_state
+                                            .payload_format_selected
+                                            .extension()
+                                    );
 
                                     self.manage_save_state
                                         .save_view_state
@@ -362,7 +1153,42 @@ impl Application for Bl3Application {
                                         .general_state
                                         .save_type_selected = save_type;
                                 }
-                            },
+                                SaveGeneralInteractionMessage::PayloadFormatSelected(
+                                    payload_format,
+                                ) => {
+                                    let general_state =
+                                        &mut self.manage_save_state.save_view_state.general_state;
+
+                                    general_state.payload_format_selected = payload_format;
+
+                                    let filename = Path::new(&general_state.filename_input)
+                                        .with_extension(payload_format.extension())
+                                        .to_string_lossy()
+                                        .to_string();
+
+                                    general_state.filename_input = filename.clone();
+
+                                    self.manage_save_state.current_file.file_name = filename;
+                                }
+                                SaveGeneralInteractionMessage::Header(
+                                    GvasHeaderInteractionMessage::CopyFromSelected(file_name),
+                                ) => {
+                                    if let Some(header) = self.copy_header_from(&file_name) {
+                                        self.manage_save_state
+                                            .save_view_state
+                                            .general_state
+                                            .header_state
+                                            .map_header(header);
+                                    }
+                                }
+                                SaveGeneralInteractionMessage::Header(header_msg) => {
+                                    self.manage_save_state
+                                        .save_view_state
+                                        .general_state
+                                        .header_state
+                                        .update(header_msg);
+                                }
+                            },
                             ManageSaveInteractionMessage::Character(character_msg) => {
                                 match character_msg {
                                     SaveCharacterInteractionMessage::Name(name_input) => {
@@ -696,58 +1522,7 @@ impl Application for Bl3Application {
                                 }
                             },
                             ManageSaveInteractionMessage::SaveFilePressed => {
-                                //Lets not make any modifications to the current file just in case we have any errors
-                                let mut current_file = self.manage_save_state.current_file.clone();
-
-                                if let Err(e) = manage_save::map_all_states_to_save(
-                                    &mut self.manage_save_state,
-                                    &mut current_file,
-                                ) {
-                                    let msg = format!("Failed to save file: {}", e);
-
-                                    error!("{}", msg);
-
-                                    self.notification = Some(Notification::new(
-                                        msg,
-                                        NotificationSentiment::Negative,
-                                    ));
-
-                                    return Command::none();
-                                }
-
-                                let output_file = self
-                                    .config
-                                    .saves_dir()
-                                    .join(&self.manage_save_state.current_file.file_name);
-
-                                match current_file.as_bytes() {
-                                    Ok((output, save_file)) => {
-                                        return Command::perform(
-                                            interaction::file_save::save_file(
-                                                self.config.backup_dir().to_path_buf(),
-                                                output_file,
-                                                output,
-                                                self.manage_save_state.current_file.clone(),
-                                                save_file,
-                                            ),
-                                            |r| {
-                                                Bl3Message::SaveFileCompleted(
-                                                    MessageResult::handle_result(r),
-                                                )
-                                            },
-                                        );
-                                    }
-                                    Err(e) => {
-                                        let msg = format!("Failed to save file: {}", e);
-
-                                        error!("{}", msg);
-
-                                        self.notification = Some(Notification::new(
-                                            msg,
-                                            NotificationSentiment::Negative,
-                                        ));
-                                    }
-                                };
+                                return self.confirm_save();
                             }
                         }
                     }
@@ -792,6 +1567,43 @@ impl Application for Bl3Application {
                                             .general_state
                                             .profile_type_selected = profile_type;
                                     }
+                                    ProfileGeneralInteractionMessage::PayloadFormatSelected(
+                                        payload_format,
+                                    ) => {
+                                        let general_state = &mut self
+                                            .manage_profile_state
+                                            .profile_view_state
+                                            .general_state;
+
+                                        general_state.payload_format_selected = payload_format;
+
+                                        let filename = Path::new(&general_state.filename_input)
+                                            .with_extension(payload_format.extension())
+                                            .to_string_lossy()
+                                            .to_string();
+
+                                        general_state.filename_input = filename.clone();
+
+                                        self.manage_profile_state.current_file.file_name = filename;
+                                    }
+                                    ProfileGeneralInteractionMessage::Header(
+                                        GvasHeaderInteractionMessage::CopyFromSelected(file_name),
+                                    ) => {
+                                        if let Some(header) = self.copy_header_from(&file_name) {
+                                            self.manage_profile_state
+                                                .profile_view_state
+                                                .general_state
+                                                .header_state
+                                                .map_header(header);
+                                        }
+                                    }
+                                    ProfileGeneralInteractionMessage::Header(header_msg) => {
+                                        self.manage_profile_state
+                                            .profile_view_state
+                                            .general_state
+                                            .header_state
+                                            .update(header_msg);
+                                    }
                                 }
                             }
                             ManageProfileInteractionMessage::Profile(profile_msg) => {
@@ -1090,65 +1902,7 @@ impl Application for Bl3Application {
                                 }
                             }
                             ManageProfileInteractionMessage::SaveProfilePressed => {
-                                //Lets not make any modifications to the current file just in case we have any errors
-                                let mut current_file =
-                                    self.manage_profile_state.current_file.clone();
-
-                                let guardian_data_injection_required =
-                                    match manage_profile::map_all_states_to_profile(
-                                        &mut self.manage_profile_state,
-                                        &mut current_file,
-                                    ) {
-                                        Ok(injection_required) => injection_required,
-                                        Err(e) => {
-                                            let msg = format!("Failed to save profile: {}", e);
-
-                                            error!("{}", msg);
-
-                                            self.notification = Some(Notification::new(
-                                                msg,
-                                                NotificationSentiment::Negative,
-                                            ));
-
-                                            return Command::none();
-                                        }
-                                    };
-
-                                let output_file = self
-                                    .config
-                                    .saves_dir()
-                                    .join(&self.manage_profile_state.current_file.file_name);
-
-                                match current_file.as_bytes() {
-                                    Ok((output, profile)) => {
-                                        return Command::perform(
-                                            interaction::file_save::save_profile(
-                                                self.config.backup_dir().to_path_buf(),
-                                                self.config.saves_dir().to_path_buf(),
-                                                output_file,
-                                                output,
-                                                self.manage_profile_state.current_file.clone(),
-                                                profile,
-                                                guardian_data_injection_required,
-                                            ),
-                                            |r| {
-                                                Bl3Message::SaveProfileCompleted(
-                                                    MessageResult::handle_result(r),
-                                                )
-                                            },
-                                        );
-                                    }
-                                    Err(e) => {
-                                        let msg = format!("Failed to save file: {}", e);
-
-                                        error!("{}", msg);
-
-                                        self.notification = Some(Notification::new(
-                                            msg,
-                                            NotificationSentiment::Negative,
-                                        ));
-                                    }
-                                };
+                                return self.confirm_save();
                             }
                         }
                     }
@@ -1283,21 +2037,90 @@ impl Application for Bl3Application {
 
                             match choose_dir_res {
                                 MessageResult::Success(dir) => {
-                                    self.view_state = ViewState::Loading;
+                                    self.load_directory(dir);
+                                }
+                                MessageResult::Error(e) => {
+                                    let msg = format!("Failed to choose saves folder: {}", e);
+
+                                    error!("{}", msg);
+
+                                    self.notification = Some(Notification::new(
+                                        msg,
+                                        NotificationSentiment::Negative,
+                                    ));
+                                }
+                            }
+                        }
+                        SettingsInteractionMessage::ConvertPlatformSelected(platform) => {
+                            self.settings_state.convert_platform_selected = platform;
+                        }
+                        SettingsInteractionMessage::ConvertSaves => {
+                            self.settings_state.converting_saves = true;
 
+                            return Command::perform(
+                                interaction::choose_dir(self.config.saves_dir().to_path_buf()),
+                                |r| {
+                                    Bl3Message::Interaction(
+                                        InteractionMessage::SettingsInteraction(
+                                            SettingsInteractionMessage::ConvertSavesOutputChosen(
+                                                MessageResult::handle_result(r),
+                                            ),
+                                        ),
+                                    )
+                                },
+                            );
+                        }
+                        SettingsInteractionMessage::ConvertSavesOutputChosen(choose_dir_res) => {
+                            match choose_dir_res {
+                                MessageResult::Success(output_dir) => {
                                     return Command::perform(
-                                        interaction::choose_save_directory::load_files_in_directory(
-                                            dir,
+                                        interaction::convert_saves::convert_dir(
+                                            self.config.saves_dir().to_path_buf(),
+                                            output_dir,
+                                            self.settings_state.convert_platform_selected,
                                         ),
                                         |r| {
-                                            Bl3Message::ChooseSave(ChooseSaveMessage::FilesLoaded(
-                                                MessageResult::handle_result(r),
-                                            ))
+                                            Bl3Message::Interaction(
+                                                InteractionMessage::SettingsInteraction(
+                                                    SettingsInteractionMessage::ConvertSavesCompleted(
+                                                        MessageResult::handle_result(r),
+                                                    ),
+                                                ),
+                                            )
                                         },
                                     );
                                 }
                                 MessageResult::Error(e) => {
-                                    let msg = format!("Failed to choose saves folder: {}", e);
+                                    self.settings_state.converting_saves = false;
+
+                                    let msg = format!("Failed to choose output folder: {}", e);
+
+                                    error!("{}", msg);
+
+                                    self.notification = Some(Notification::new(
+                                        msg,
+                                        NotificationSentiment::Negative,
+                                    ));
+                                }
+                            }
+                        }
+                        SettingsInteractionMessage::ConvertSavesCompleted(res) => {
+                            self.settings_state.converting_saves = false;
+
+                            match res {
+                                MessageResult::Success((output_dir, count)) => {
+                                    self.notification = Some(Notification::new(
+                                        format!(
+                                            "Converted {} file(s) to {} in {}.",
+                                            count,
+                                            self.settings_state.convert_platform_selected,
+                                            output_dir.display()
+                                        ),
+                                        NotificationSentiment::Positive,
+                                    ));
+                                }
+                                MessageResult::Error(e) => {
+                                    let msg = format!("Failed to convert saves: {}", e);
 
                                     error!("{}", msg);
 
@@ -1336,180 +2159,664 @@ impl Application for Bl3Application {
                                 });
                             }
                         }
-                    },
-                    InteractionMessage::LoadedFileSelected(loaded_file) => {
-                        self.loaded_files_selected = loaded_file;
+                        SettingsInteractionMessage::DeleteOldBackups(delete_old_backups) => {
+                            self.settings_state.backup_retention.delete_old_backups =
+                                delete_old_backups;
 
-                        state_mappers::map_loaded_file_to_state(self).handle_ui_error(
-                            "Failed to map loaded file to editor",
-                            &mut self.notification,
-                        );
-                    }
-                    InteractionMessage::RefreshSavesDirectory => {
-                        self.view_state = ViewState::Loading;
+                            self.config
+                                .set_backup_retention(self.settings_state.backup_retention);
 
-                        return Command::perform(
-                            interaction::choose_save_directory::load_files_in_directory(
-                                self.config.saves_dir().to_path_buf(),
-                            ),
-                            |r| {
-                                Bl3Message::ChooseSave(ChooseSaveMessage::FilesLoaded(
+                            return Command::perform(self.config.clone().save(), |r| {
+                                Bl3Message::Config(ConfigMessage::SaveCompleted(
                                     MessageResult::handle_result(r),
                                 ))
-                            },
-                        );
-                    }
-                    InteractionMessage::Ignore => {}
-                }
-            }
-            Bl3Message::ChooseSave(choose_save_msg) => match choose_save_msg {
-                ChooseSaveMessage::ChooseDirCompleted(choose_dir_res) => {
-                    self.choose_save_directory_state.choose_dir_window_open = false;
+                            });
+                        }
+                        SettingsInteractionMessage::BackupKeepLast(keep_last) => {
+                            self.settings_state.backup_retention.keep_last = keep_last;
 
-                    match choose_dir_res {
-                        MessageResult::Success(dir) => {
-                            self.view_state = ViewState::Loading;
+                            self.config
+                                .set_backup_retention(self.settings_state.backup_retention);
 
-                            return Command::perform(
-                                interaction::choose_save_directory::load_files_in_directory(dir),
-                                |r| {
-                                    Bl3Message::ChooseSave(ChooseSaveMessage::FilesLoaded(
-                                        MessageResult::handle_result(r),
-                                    ))
-                                },
-                            );
+                            return Command::perform(self.config.clone().save(), |r| {
+                                Bl3Message::Config(ConfigMessage::SaveCompleted(
+                                    MessageResult::handle_result(r),
+                                ))
+                            });
                         }
-                        MessageResult::Error(e) => {
-                            let msg = format!("Failed to choose saves folder: {}", e);
+                        SettingsInteractionMessage::BackupKeepDaily(keep_daily) => {
+                            self.settings_state.backup_retention.keep_daily = keep_daily;
 
-                            error!("{}", msg);
+                            self.config
+                                .set_backup_retention(self.settings_state.backup_retention);
 
-                            self.notification =
-                                Some(Notification::new(msg, NotificationSentiment::Negative));
+                            return Command::perform(self.config.clone().save(), |r| {
+                                Bl3Message::Config(ConfigMessage::SaveCompleted(
+                                    MessageResult::handle_result(r),
+                                ))
+                            });
                         }
-                    }
-                }
-                ChooseSaveMessage::FilesLoaded(res) => match res {
-                    MessageResult::Success((dir, mut files)) => {
-                        files.sort();
-                        self.loaded_files = files;
+                        SettingsInteractionMessage::BackupKeepWeekly(keep_weekly) => {
+                            self.settings_state.backup_retention.keep_weekly = keep_weekly;
 
-                        self.loaded_files_selected = Box::new(
-                            self.loaded_files
-                                .get(0)
-                                .expect("loaded_files was empty")
-                                .clone(),
-                        );
+                            self.config
+                                .set_backup_retention(self.settings_state.backup_retention);
 
-                        state_mappers::map_loaded_file_to_state(self).handle_ui_error(
-                            "Failed to map loaded file to editor",
-                            &mut self.notification,
-                        );
+                            return Command::perform(self.config.clone().save(), |r| {
+                                Bl3Message::Config(ConfigMessage::SaveCompleted(
+                                    MessageResult::handle_result(r),
+                                ))
+                            });
+                        }
+                        SettingsInteractionMessage::BackupMaxTotalSize(max_total_size_mb) => {
+                            self.settings_state.backup_retention.max_total_size_mb =
+                                max_total_size_mb;
 
-                        self.config.set_saves_dir(dir);
-                        self.settings_state.saves_dir_input =
-                            self.config.saves_dir().to_string_lossy().to_string();
+                            self.config
+                                .set_backup_retention(self.settings_state.backup_retention);
 
-                        return Command::perform(self.config.clone().save(), |r| {
-                            Bl3Message::Config(ConfigMessage::SaveCompleted(
-                                MessageResult::handle_result(r),
-                            ))
-                        });
+                            return Command::perform(self.config.clone().save(), |r| {
+                                Bl3Message::Config(ConfigMessage::SaveCompleted(
+                                    MessageResult::handle_result(r),
+                                ))
+                            });
+                        }
+                    },
+                    InteractionMessage::LoadedFileSelected(LoadedFileEntry::Unreadable(file)) => {
+                        let msg = format!("{} could not be read.", file.file_name);
+
+                        self.prompt = Some(
+                            Prompt::new(msg)
+                                .details(vec![file.reason])
+                                .action(
+                                    "Run Diagnostics",
+                                    Bl3Message::Interaction(InteractionMessage::RunDiagnostics(
+                                        file.path,
+                                    )),
+                                )
+                                .action(
+                                    "Open Backups Folder",
+                                    Bl3Message::Interaction(
+                                        InteractionMessage::SettingsInteraction(
+                                            SettingsInteractionMessage::OpenBackupDir,
+                                        ),
+                                    ),
+                                ),
+                        );
                     }
-                    MessageResult::Error(e) => {
-                        let msg = format!("Failed to load save folder: {}", e);
-
-                        error!("{}", msg);
+                    InteractionMessage::LoadedFileSelected(LoadedFileEntry::Readable {
+                        file_name,
+                        ..
+                    }) => {
+                        let loaded_file =
+                            match self.loaded_files.iter().find(|f| f.filename() == file_name) {
+                                Some(loaded_file) => Box::new(loaded_file.clone()),
+                                None => return Command::none(),
+                            };
+
+                        let msg = format!(
+                            "{} has unsaved changes. Switch to {} and lose them?",
+                            self.loaded_files_selected.filename(),
+                            loaded_file.filename()
+                        );
 
-                        self.view_state = ViewState::ChooseSaveDirectory;
+                        if !self.confirm_discard_changes(
+                            msg,
+                            "Switch Anyway",
+                            Bl3Message::Interaction(InteractionMessage::SwitchLoadedFile(
+                                loaded_file.clone(),
+                            )),
+                        ) {
+                            self.switch_loaded_file(loaded_file);
+                        }
+                    }
+                    InteractionMessage::SwitchLoadedFile(loaded_file) => {
+                        self.switch_loaded_file(loaded_file);
+                    }
+                    InteractionMessage::JumpToItem(indexed_item) => {
+                        self.jump_to_item(&indexed_item);
+                    }
+                    InteractionMessage::RunDiagnostics(path) => {
+                        return Command::perform(
+                            interaction::choose_save_directory::diagnose_file(path),
+                            |r| Bl3Message::DiagnosticsCompleted(MessageResult::handle_result(r)),
+                        );
+                    }
+                    InteractionMessage::ConfirmSave => {
+                        return self.save_selected_file(SaveMode::Checked);
+                    }
+                    InteractionMessage::RefreshSavesDirectory => {
+                        self.load_directory(self.config.saves_dir().to_path_buf());
+                    }
+                    InteractionMessage::ReloadModifiedFile => {
+                        return self.reload_files(EditorReload::Remap);
+                    }
+                    InteractionMessage::KeepUnsavedEdits => {
+                        self.kept_edits_over_changes =
+                            Some(self.loaded_files_selected.filename().to_owned());
 
-                        self.notification =
-                            Some(Notification::new(msg, NotificationSentiment::Negative));
+                        return self.reload_files(EditorReload::KeepEdits);
                     }
-                },
-            },
-            Bl3Message::SaveFileCompleted(res) => match res {
-                MessageResult::Success(save) => {
-                    self.notification = Some(Notification::new(
-                        "Successfully saved file!",
-                        NotificationSentiment::Positive,
-                    ));
+                    InteractionMessage::OverwriteModifiedFile => {
+                        return self.save_selected_file(SaveMode::Overwrite);
+                    }
+                    InteractionMessage::SaveModifiedFileAs => {
+                        return Command::perform(
+                            interaction::choose_dir(self.config.saves_dir().to_path_buf()),
+                            |r| Bl3Message::SaveAsDirChosen(MessageResult::handle_result(r)),
+                        );
+                    }
+                    InteractionMessage::Undo => {
+                        if let Err(e) = edit_history::undo(self) {
+                            let msg = format!("Failed to undo: {}", e);
 
-                    self.is_reloading_saves = true;
+                            error!("{}", msg);
 
-                    let bl3_file_type = match save.header_type {
-                        HeaderType::PcSave => Bl3FileType::PcSave(save),
-                        HeaderType::Ps4Save => Bl3FileType::Ps4Save(save),
-                        _ => {
-                            let msg = "Unexpected Bl3FileType when reloading save";
+                            self.notification =
+                                Some(Notification::new(msg, NotificationSentiment::Negative));
+                        }
+                    }
+                    InteractionMessage::Redo => {
+                        if let Err(e) = edit_history::redo(self) {
+                            let msg = format!("Failed to redo: {}", e);
 
                             error!("{}", msg);
-                            panic!("{}", msg);
+
+                            self.notification =
+                                Some(Notification::new(msg, NotificationSentiment::Negative));
                         }
-                    };
+                    }
+                    InteractionMessage::FindDuplicateItems => {
+                        self.duplicate_items_state.map_files(&self.loaded_files);
 
-                    return Command::perform(
-                        interaction::file_save::load_files_after_save(
-                            self.config.saves_dir().to_path_buf(),
-                            bl3_file_type,
-                        ),
-                        |r| Bl3Message::FilesLoadedAfterSave(MessageResult::handle_result(r)),
-                    );
-                }
-                MessageResult::Error(e) => {
-                    let msg = format!("Failed to save file: {}", e);
+                        self.view_state = ViewState::DuplicateItems;
+                    }
+                    InteractionMessage::DuplicateItemsInteraction(duplicate_items_msg) => {
+                        let (file_name, is_confirmed) = match duplicate_items_msg {
+                            DuplicateItemsInteractionMessage::BackPressed => {
+                                state_mappers::return_to_editor(self);
 
-                    error!("{}", msg);
+                                return Command::none();
+                            }
+                            DuplicateItemsInteractionMessage::RemoveExtrasPressed(file_name) => {
+                                (Some(file_name), false)
+                            }
+                            DuplicateItemsInteractionMessage::RemoveAllExtrasPressed => {
+                                (None, false)
+                            }
+                            DuplicateItemsInteractionMessage::RemoveExtrasConfirmed(file_name) => {
+                                (file_name, true)
+                            }
+                        };
 
-                    self.notification =
-                        Some(Notification::new(msg, NotificationSentiment::Negative));
-                }
-            },
-            Bl3Message::SaveProfileCompleted(res) => match res {
-                MessageResult::Success(profile) => {
-                    self.notification = Some(Notification::new(
-                        "Successfully saved profile!",
-                        NotificationSentiment::Positive,
-                    ));
+                        let extras = self.duplicate_items_state.extras(file_name.as_deref());
 
-                    self.is_reloading_saves = true;
+                        // The selected file is read again afterwards if items are removed from it
+                        let is_selected_file_changed = extras
+                            .iter()
+                            .any(|e| e.file_name == self.loaded_files_selected.filename());
 
-                    let bl3_file_type = match profile.header_type {
-                        HeaderType::PcProfile => Bl3FileType::PcProfile(profile),
-                        HeaderType::Ps4Profile => Bl3FileType::Ps4Profile(profile),
-                        _ => {
-                            let msg = "Unexpected Bl3FileType when reloading profile";
+                        if !is_confirmed && is_selected_file_changed {
+                            let msg = format!(
+                                "{} has unsaved changes. Remove its duplicate items and lose them?",
+                                self.loaded_files_selected.filename()
+                            );
 
-                            error!("{}", msg);
-                            panic!("{}", msg);
+                            if self.confirm_discard_changes(
+                                msg,
+                                "Remove Anyway",
+                                Bl3Message::Interaction(
+                                    InteractionMessage::DuplicateItemsInteraction(
+                                        DuplicateItemsInteractionMessage::RemoveExtrasConfirmed(
+                                            file_name,
+                                        ),
+                                    ),
+                                ),
+                            ) {
+                                return Command::none();
+                            }
                         }
-                    };
-
-                    return Command::perform(
-                        interaction::file_save::load_files_after_save(
-                            self.config.saves_dir().to_path_buf(),
-                            bl3_file_type,
-                        ),
-                        |r| Bl3Message::FilesLoadedAfterSave(MessageResult::handle_result(r)),
-                    );
-                }
-                MessageResult::Error(e) => {
-                    let msg = format!("Failed to save profile: {}", e);
 
-                    error!("{}", msg);
+                        self.duplicate_items_state.is_removing = true;
 
-                    self.notification =
-                        Some(Notification::new(msg, NotificationSentiment::Negative));
-                }
-            },
-            Bl3Message::FilesLoadedAfterSave(res) => {
-                match res {
-                    MessageResult::Success((saved_file, mut files)) => {
-                        files.sort();
+                        return Command::perform(
+                            interaction::duplicate_items::remove_duplicate_items(
+                                self.config.backup_dir().to_path_buf(),
+                                self.config.backup_retention(),
+                                self.config.saves_dir().to_path_buf(),
+                                self.loaded_files.clone(),
+                                self.file_fingerprints.clone(),
+                                extras,
+                            ),
+                            |r| Bl3Message::DuplicateItemsRemoved(MessageResult::handle_result(r)),
+                        );
+                    }
+                    InteractionMessage::SearchAllItems => {
+                        self.item_search_state.map_files(&self.loaded_files);
 
-                        self.loaded_files = files;
+                        self.view_state = ViewState::ItemSearch;
+                    }
+                    InteractionMessage::ItemSearchInteraction(item_search_msg) => {
+                        match item_search_msg {
+                            ItemSearchInteractionMessage::BackPressed => {
+                                state_mappers::return_to_editor(self);
+                            }
+                            ItemSearchInteractionMessage::SearchInputChanged(search_input) => {
+                                self.item_search_state.set_search_input(search_input);
+                            }
+                            ItemSearchInteractionMessage::CompareItemPressed(id) => {
+                                if let Some(indexed_item) = self.item_search_state.result(id) {
+                                    let item_compare = ItemCompare::new(
+                                        indexed_item.item.clone(),
+                                        views::item_search::location_text(indexed_item),
+                                    );
 
-                        let selected_file = self.loaded_files.iter().find(|f| **f == saved_file);
+                                    // Compared against the item selected in the editor for the
+                                    // open file, which is then shown again
+                                    match *self.loaded_files_selected {
+                                        Bl3FileType::PcSave(_) | Bl3FileType::Ps4Save(_) => {
+                                            self.manage_save_state
+                                                .save_view_state
+                                                .inventory_state
+                                                .item_editor_state
+                                                .item_compare = Some(item_compare);
+
+                                            self.view_state = ViewState::ManageSave(
+                                                ManageSaveView::TabBar(SaveTabBarView::Inventory),
+                                            );
+                                        }
+                                        Bl3FileType::PcProfile(_) | Bl3FileType::Ps4Profile(_) => {
+                                            self.manage_profile_state
+                                                .profile_view_state
+                                                .bank_state
+                                                .item_editor_state
+                                                .item_compare = Some(item_compare);
+
+                                            self.view_state = ViewState::ManageProfile(
+                                                ManageProfileView::TabBar(ProfileTabBarView::Bank),
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                            ItemSearchInteractionMessage::JumpToItemPressed(id) => {
+                                if let Some(indexed_item) = self.item_search_state.result(id) {
+                                    let indexed_item = Box::new(indexed_item.clone());
+
+                                    // Opening an item in another file loses the unsaved changes
+                                    let is_other_file = self.loaded_files_selected.filename()
+                                        != indexed_item.file_name;
+
+                                    let msg = format!(
+                                        "{} has unsaved changes. Open {} and lose them?",
+                                        self.loaded_files_selected.filename(),
+                                        indexed_item.file_name
+                                    );
+
+                                    if !is_other_file
+                                        || !self.confirm_discard_changes(
+                                            msg,
+                                            "Open Anyway",
+                                            Bl3Message::Interaction(
+                                                InteractionMessage::JumpToItem(
+                                                    indexed_item.clone(),
+                                                ),
+                                            ),
+                                        )
+                                    {
+                                        self.jump_to_item(&indexed_item);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    InteractionMessage::ManageBackups => {
+                        self.backups_state.is_loading = true;
+
+                        self.view_state = ViewState::Backups;
+
+                        return Command::perform(
+                            interaction::backups::load_backups(
+                                self.config.backup_dir().to_path_buf(),
+                            ),
+                            |r| Bl3Message::BackupsLoaded(MessageResult::handle_result(r)),
+                        );
+                    }
+                    InteractionMessage::BackupsInteraction(backups_msg) => match backups_msg {
+                        BackupsInteractionMessage::BackPressed => {
+                            state_mappers::return_to_editor(self);
+                        }
+                        BackupsInteractionMessage::RestorePressed(group_id, backup_id) => {
+                            if let Some((group, backup)) =
+                                self.backups_state.backup(group_id, backup_id)
+                            {
+                                let existing_file = self
+                                    .loaded_files
+                                    .iter()
+                                    .find(|f| f.filename() == group.output_file_name)
+                                    .cloned();
+
+                                let restore = interaction::backups::restore_backup(
+                                    self.config.backup_dir().to_path_buf(),
+                                    self.config.backup_retention(),
+                                    self.config.saves_dir().to_path_buf(),
+                                    backup.clone(),
+                                    group.output_file_name.clone(),
+                                    existing_file,
+                                );
+
+                                self.backups_state.is_restoring = true;
+
+                                return Command::perform(restore, |r| {
+                                    Bl3Message::BackupRestored(MessageResult::handle_result(r))
+                                });
+                            }
+                        }
+                        BackupsInteractionMessage::ComparePressed(group_id, backup_id) => {
+                            self.backups_state
+                                .compare(group_id, backup_id, &self.loaded_files)
+                                .handle_ui_error(
+                                    "Failed to compare backup",
+                                    &mut self.notification,
+                                );
+                        }
+                        BackupsInteractionMessage::CloseComparisonPressed => {
+                            self.backups_state.comparison = None;
+                        }
+                        BackupsInteractionMessage::CopyComparisonPressed => {
+                            if let Some(comparison) = &self.backups_state.comparison {
+                                let copied = comparison
+                                    .to_json()
+                                    .map_err(anyhow::Error::new)
+                                    .and_then(util::set_clipboard_contents);
+
+                                match copied {
+                                    Ok(_) => {
+                                        self.notification = Some(Notification::new(
+                                            "Comparison was copied to clipboard.",
+                                            NotificationSentiment::Info,
+                                        ));
+                                    }
+                                    Err(e) => e.handle_ui_error(
+                                        "Failed to copy comparison to clipboard",
+                                        &mut self.notification,
+                                    ),
+                                }
+                            }
+                        }
+                    },
+                    InteractionMessage::ManageSaveSlots => {
+                        self.save_slots_state.map_files(&self.loaded_files);
+
+                        self.view_state = ViewState::SaveSlots;
+                    }
+                    InteractionMessage::SaveSlotsInteraction(save_slots_msg) => {
+                        match save_slots_msg {
+                            SaveSlotsInteractionMessage::BackPressed => {
+                                state_mappers::return_to_editor(self);
+                            }
+                            SaveSlotsInteractionMessage::SlotInputChanged(file_name, slot) => {
+                                if let Some(save_slot) = self
+                                    .save_slots_state
+                                    .slots
+                                    .iter_mut()
+                                    .find(|s| s.file_name == file_name)
+                                {
+                                    save_slot.slot_input = slot;
+                                }
+                            }
+                            SaveSlotsInteractionMessage::ClonePressed(file_name) => {
+                                if let Some(save) = self.loaded_save(&file_name) {
+                                    self.save_slots_state.is_changing = true;
+
+                                    return Command::perform(
+                                        interaction::save_slots::clone_save(
+                                            self.config.saves_dir().to_path_buf(),
+                                            save,
+                                            self.used_save_slots(""),
+                                        ),
+                                        |r| {
+                                            Bl3Message::SaveSlotsChanged(
+                                                MessageResult::handle_result(r),
+                                            )
+                                        },
+                                    );
+                                }
+                            }
+                            SaveSlotsInteractionMessage::MovePressed(file_name) => {
+                                let msg = format!(
+                                    "{} has unsaved changes. Move it to another slot and lose them?",
+                                    file_name
+                                );
+
+                                if self.loaded_files_selected.filename() != file_name
+                                    || !self.confirm_discard_changes(
+                                        msg,
+                                        "Move Anyway",
+                                        Bl3Message::Interaction(
+                                            InteractionMessage::SaveSlotsInteraction(
+                                                SaveSlotsInteractionMessage::MoveConfirmed(
+                                                    file_name.clone(),
+                                                ),
+                                            ),
+                                        ),
+                                    )
+                                {
+                                    return self.move_save_slot(file_name);
+                                }
+                            }
+                            SaveSlotsInteractionMessage::MoveConfirmed(file_name) => {
+                                return self.move_save_slot(file_name);
+                            }
+                            SaveSlotsInteractionMessage::DeletePressed(file_name) => {
+                                if let Some(slot) = self.save_slots_state.slot(&file_name) {
+                                    let msg = format!(
+                                        "Delete {} ({})? A backup of it will be made first.",
+                                        slot.title, file_name
+                                    );
+
+                                    self.prompt = Some(Prompt::new(msg).action(
+                                        "Delete",
+                                        Bl3Message::Interaction(
+                                            InteractionMessage::SaveSlotsInteraction(
+                                                SaveSlotsInteractionMessage::DeleteConfirmed(
+                                                    file_name,
+                                                ),
+                                            ),
+                                        ),
+                                    ));
+                                }
+                            }
+                            SaveSlotsInteractionMessage::DeleteConfirmed(file_name) => {
+                                if let Some(save) = self.loaded_save(&file_name) {
+                                    self.save_slots_state.is_changing = true;
+
+                                    return Command::perform(
+                                        interaction::save_slots::delete_save(
+                                            self.config.backup_dir().to_path_buf(),
+                                            self.config.backup_retention(),
+                                            self.config.saves_dir().to_path_buf(),
+                                            save,
+                                        ),
+                                        |r| {
+                                            Bl3Message::SaveSlotsChanged(
+                                                MessageResult::handle_result(r),
+                                            )
+                                        },
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    InteractionMessage::Ignore => {}
+                }
+            }
+            Bl3Message::ChooseSave(choose_save_msg) => match choose_save_msg {
+                ChooseSaveMessage::ChooseDirCompleted(choose_dir_res) => {
+                    self.choose_save_directory_state.choose_dir_window_open = false;
+
+                    match choose_dir_res {
+                        MessageResult::Success(dir) => {
+                            self.load_directory(dir);
+                        }
+                        MessageResult::Error(e) => {
+                            let msg = format!("Failed to choose saves folder: {}", e);
+
+                            error!("{}", msg);
+
+                            self.notification =
+                                Some(Notification::new(msg, NotificationSentiment::Negative));
+                        }
+                    }
+                }
+            },
+            Bl3Message::DirectoryLoad(id, event) => {
+                if self.directory_load.as_ref().map(|d| d.load.id) != Some(id) {
+                    return Command::none();
+                }
+
+                match event {
+                    DirectoryLoadEvent::Listed(listed) => {
+                        if let Some(directory_load) = &mut self.directory_load {
+                            directory_load.listed = Some(listed);
+                        }
+                    }
+                    DirectoryLoadEvent::FileRead(loaded_file) => {
+                        if let Some(directory_load) = &mut self.directory_load {
+                            directory_load.read += 1;
+                        }
+
+                        loaded_file.add_to(
+                            &mut self.loaded_files,
+                            &mut self.unreadable_files,
+                            &mut self.file_fingerprints,
+                        );
+
+                        // Start editing as soon as the file to open has been read, the rest show up
+                        // in the picker as they arrive
+                        if let Some(directory_load) = self.directory_load.clone() {
+                            self.open_file_while_loading(&directory_load);
+                        }
+                    }
+                    DirectoryLoadEvent::Finished => {
+                        return self.finish_directory_load();
+                    }
+                    DirectoryLoadEvent::Failed(e) => {
+                        self.directory_load = None;
+
+                        self.directory_load_failed(e);
+                    }
+                }
+            }
+            Bl3Message::SaveFileCompleted(res) => {
+                self.is_saving = false;
+
+                match res {
+                    MessageResult::Success(saved_file) => {
+                        self.notification = Some(Notification::new(
+                            "Successfully saved file!",
+                            NotificationSentiment::Positive,
+                        ));
+
+                        self.is_reloading_saves = true;
+
+                        let saved_file = saved_file.map(|save| match save.header_type {
+                            HeaderType::PcSave => Bl3FileType::PcSave(save),
+                            HeaderType::Ps4Save => Bl3FileType::Ps4Save(save),
+                            _ => {
+                                let msg = "Unexpected Bl3FileType when reloading save";
+
+                                error!("{}", msg);
+                                panic!("{}", msg);
+                            }
+                        });
+
+                        return Command::perform(
+                            interaction::file_save::load_files_after_save(
+                                self.config.saves_dir().to_path_buf(),
+                                saved_file,
+                            ),
+                            |r| Bl3Message::FilesLoadedAfterSave(MessageResult::handle_result(r)),
+                        );
+                    }
+                    MessageResult::Error(e) => {
+                        let msg = format!("Failed to save file: {}", e);
+
+                        error!("{}", msg);
+
+                        self.notification =
+                            Some(Notification::new(msg, NotificationSentiment::Negative));
+                    }
+                }
+            }
+            Bl3Message::SaveProfileCompleted(res) => {
+                self.is_saving = false;
+
+                match res {
+                    MessageResult::Success(saved_file) => {
+                        self.notification = Some(Notification::new(
+                            "Successfully saved profile!",
+                            NotificationSentiment::Positive,
+                        ));
+
+                        self.is_reloading_saves = true;
+
+                        let saved_file = saved_file.map(|profile| match profile.header_type {
+                            HeaderType::PcProfile => Bl3FileType::PcProfile(profile),
+                            HeaderType::Ps4Profile => Bl3FileType::Ps4Profile(profile),
+                            _ => {
+                                let msg = "Unexpected Bl3FileType when reloading profile";
+
+                                error!("{}", msg);
+                                panic!("{}", msg);
+                            }
+                        });
+
+                        return Command::perform(
+                            interaction::file_save::load_files_after_save(
+                                self.config.saves_dir().to_path_buf(),
+                                saved_file,
+                            ),
+                            |r| Bl3Message::FilesLoadedAfterSave(MessageResult::handle_result(r)),
+                        );
+                    }
+                    MessageResult::Error(e) => {
+                        let msg = format!("Failed to save profile: {}", e);
+
+                        error!("{}", msg);
+
+                        self.notification =
+                            Some(Notification::new(msg, NotificationSentiment::Negative));
+                    }
+                }
+            }
+            Bl3Message::FilesLoadedAfterSave(res) => {
+                match res {
+                    MessageResult::Success(files_after_save) => {
+                        let saved_file = match files_after_save {
+                            FilesAfterSave::Saved(saved_file, file_fingerprint) => {
+                                // Saving as into another folder leaves the saves folder as it was
+                                if let Some(file_fingerprint) = file_fingerprint {
+                                    self.replace_loaded_file(saved_file.clone(), file_fingerprint);
+                                }
+
+                                saved_file
+                            }
+                            FilesAfterSave::Reloaded((
+                                saved_file,
+                                files,
+                                unreadable_files,
+                                file_fingerprints,
+                            )) => {
+                                self.loaded_files = files;
+                                self.unreadable_files = unreadable_files;
+                                self.file_fingerprints = file_fingerprints;
+
+                                saved_file
+                            }
+                        };
+
+                        let selected_file = self.loaded_files.iter().find(|f| **f == saved_file);
 
                         if let Some(selected_file) = selected_file {
                             self.loaded_files_selected = Box::new(selected_file.to_owned());
@@ -1526,7 +2833,9 @@ impl Application for Bl3Application {
                         } else {
                             self.loaded_files_selected = Box::new(
                                 self.loaded_files
-                                    .get(0)
+                                    .iter()
+                                    .find(|f| f.filename() == saved_file.filename())
+                                    .or_else(|| self.loaded_files.get(0))
                                     .expect("loaded_files was empty")
                                     .clone(),
                             );
@@ -1551,15 +2860,553 @@ impl Application for Bl3Application {
 
                 self.is_reloading_saves = false;
             }
+            Bl3Message::DuplicateItemsRemoved(res) => {
+                self.duplicate_items_state.is_removing = false;
+
+                match res {
+                    MessageResult::Success((
+                        removed,
+                        files,
+                        unreadable_files,
+                        file_fingerprints,
+                    )) => {
+                        self.loaded_files = files;
+                        self.unreadable_files = unreadable_files;
+                        self.file_fingerprints = file_fingerprints;
+
+                        let selected_file = self
+                            .loaded_files
+                            .iter()
+                            .find(|f| f.filename() == self.loaded_files_selected.filename())
+                            .or_else(|| self.loaded_files.get(0))
+                            .expect("loaded_files was empty")
+                            .clone();
+
+                        // Only map the editor again if the selected file changed so that unsaved
+                        // changes to it are kept otherwise
+                        let is_selected_file_changed = selected_file != *self.loaded_files_selected;
+
+                        self.loaded_files_selected = Box::new(selected_file);
+
+                        if is_selected_file_changed {
+                            state_mappers::map_loaded_file_to_state(self).handle_ui_error(
+                                "Failed to map loaded file to editor",
+                                &mut self.notification,
+                            );
+                        }
+
+                        self.duplicate_items_state.map_files(&self.loaded_files);
+
+                        self.view_state = ViewState::DuplicateItems;
+
+                        self.notification = Some(Notification::new(
+                            format!("Successfully removed {} duplicate items!", removed),
+                            NotificationSentiment::Positive,
+                        ));
+                    }
+                    MessageResult::Error(e) => {
+                        let msg = format!("Failed to remove duplicate items: {}", e);
+
+                        error!("{}", msg);
+
+                        self.notification =
+                            Some(Notification::new(msg, NotificationSentiment::Negative));
+                    }
+                }
+            }
+            Bl3Message::BackupsLoaded(res) => {
+                self.backups_state.is_loading = false;
+
+                match res {
+                    MessageResult::Success(backups) => {
+                        self.backups_state.map_backups(backups, &self.loaded_files);
+                    }
+                    MessageResult::Error(e) => {
+                        let msg = format!("Failed to load backups: {}", e);
+
+                        error!("{}", msg);
+
+                        self.notification =
+                            Some(Notification::new(msg, NotificationSentiment::Negative));
+                    }
+                }
+            }
+            Bl3Message::BackupRestored(res) => {
+                self.backups_state.is_restoring = false;
+
+                match res {
+                    MessageResult::Success((
+                        restored_file_name,
+                        files,
+                        unreadable_files,
+                        file_fingerprints,
+                    )) => {
+                        self.loaded_files = files;
+                        self.unreadable_files = unreadable_files;
+                        self.file_fingerprints = file_fingerprints;
+
+                        let selected_file = self
+                            .loaded_files
+                            .iter()
+                            .find(|f| f.filename() == restored_file_name)
+                            .or_else(|| self.loaded_files.get(0))
+                            .expect("loaded_files was empty")
+                            .clone();
+
+                        self.loaded_files_selected = Box::new(selected_file);
+
+                        state_mappers::map_loaded_file_to_state(self).handle_ui_error(
+                            "Failed to map loaded file to editor",
+                            &mut self.notification,
+                        );
+
+                        self.notification = Some(Notification::new(
+                            format!("Successfully restored backup to {}!", restored_file_name),
+                            NotificationSentiment::Positive,
+                        ));
+
+                        self.backups_state.is_loading = true;
+
+                        self.view_state = ViewState::Backups;
+
+                        // Restoring makes a new backup so load them again
+                        return Command::perform(
+                            interaction::backups::load_backups(
+                                self.config.backup_dir().to_path_buf(),
+                            ),
+                            |r| Bl3Message::BackupsLoaded(MessageResult::handle_result(r)),
+                        );
+                    }
+                    MessageResult::Error(e) => {
+                        let msg = format!("Failed to restore backup: {}", e);
+
+                        error!("{}", msg);
+
+                        self.notification =
+                            Some(Notification::new(msg, NotificationSentiment::Negative));
+                    }
+                }
+            }
+            Bl3Message::FileModifiedExternally(reason) => {
+                self.is_saving = false;
+
+                self.prompt_file_modified(reason);
+            }
+            Bl3Message::SaveAsDirChosen(res) => match res {
+                MessageResult::Success(dir) => {
+                    return self.save_selected_file(SaveMode::SaveAs(dir));
+                }
+                MessageResult::Error(e) => {
+                    let msg = format!("Failed to choose folder: {}", e);
+
+                    error!("{}", msg);
+
+                    self.notification =
+                        Some(Notification::new(msg, NotificationSentiment::Negative));
+                }
+            },
+            Bl3Message::SaveSlotsChanged(res) => {
+                self.save_slots_state.is_changing = false;
+
+                match res {
+                    MessageResult::Success((
+                        change,
+                        files,
+                        unreadable_files,
+                        file_fingerprints,
+                    )) => {
+                        self.loaded_files = files;
+                        self.unreadable_files = unreadable_files;
+                        self.file_fingerprints = file_fingerprints;
+
+                        // The last save was deleted, any unreadable files are still listed
+                        if self.loaded_files.is_empty() {
+                            self.view_state = ViewState::ChooseSaveDirectory;
+
+                            self.notification = Some(Notification::new(
+                                format!(
+                                    "{} There are no saves or profiles left in this folder.",
+                                    change.summary
+                                ),
+                                NotificationSentiment::Positive,
+                            ));
+
+                            return Command::none();
+                        }
+
+                        let selected_file_name = self.loaded_files_selected.filename().to_owned();
+
+                        // Edits to the selected file are kept unless it was moved or deleted
+                        match self
+                            .loaded_files
+                            .iter()
+                            .find(|f| f.filename() == selected_file_name)
+                            .cloned()
+                        {
+                            Some(selected_file) => {
+                                self.loaded_files_selected = Box::new(selected_file);
+                            }
+                            None => {
+                                self.loaded_files_selected = Box::new(
+                                    self.loaded_files
+                                        .iter()
+                                        .find(|f| Some(f.filename()) == change.file_name.as_deref())
+                                        .or_else(|| self.loaded_files.get(0))
+                                        .expect("loaded_files was empty")
+                                        .clone(),
+                                );
+
+                                state_mappers::map_loaded_file_to_state(self).handle_ui_error(
+                                    "Failed to map loaded file to editor",
+                                    &mut self.notification,
+                                );
+                            }
+                        }
+
+                        self.save_slots_state.map_files(&self.loaded_files);
+
+                        self.view_state = ViewState::SaveSlots;
+
+                        self.notification = Some(Notification::new(
+                            change.summary,
+                            NotificationSentiment::Positive,
+                        ));
+                    }
+                    MessageResult::Error(e) => {
+                        let msg = format!("Failed to change save slots: {}", e);
+
+                        error!("{}", msg);
+
+                        self.notification =
+                            Some(Notification::new(msg, NotificationSentiment::Negative));
+                    }
+                }
+            }
+            Bl3Message::FilesReloaded(editor_reload, res) => {
+                match res {
+                    MessageResult::Success((
+                        file_name,
+                        files,
+                        unreadable_files,
+                        file_fingerprints,
+                    )) => {
+                        self.loaded_files = files;
+                        self.unreadable_files = unreadable_files;
+                        self.file_fingerprints = file_fingerprints;
+
+                        let selected_file = self
+                            .loaded_files
+                            .iter()
+                            .find(|f| f.filename() == file_name)
+                            .cloned();
+
+                        match (editor_reload, selected_file) {
+                            (EditorReload::KeepEdits, Some(selected_file)) => {
+                                self.loaded_files_selected = Box::new(selected_file);
+                            }
+                            // The file being edited was deleted, keep it selected so that the
+                            // edits can still be saved
+                            (EditorReload::KeepEdits, None) => (),
+                            (EditorReload::Remap, selected_file) => {
+                                self.kept_edits_over_changes = None;
+
+                                self.loaded_files_selected = Box::new(
+                                    selected_file
+                                        .or_else(|| self.loaded_files.get(0).cloned())
+                                        .expect("loaded_files was empty"),
+                                );
+
+                                state_mappers::map_loaded_file_to_state(self).handle_ui_error(
+                                    "Failed to map loaded file to editor",
+                                    &mut self.notification,
+                                );
+                            }
+                        }
+                    }
+                    MessageResult::Error(e) => {
+                        let msg = format!("Failed to load save folder: {}", e);
+
+                        error!("{}", msg);
+
+                        self.view_state = ViewState::ChooseSaveDirectory;
+
+                        self.notification =
+                            Some(Notification::new(msg, NotificationSentiment::Negative));
+                    }
+                }
+
+                self.is_reloading_saves = false;
+            }
+            Bl3Message::CheckSavesDirectory => {
+                // Asking about a changed file would replace the prompt that is open
+                if self.prompt.is_some() {
+                    self.is_saves_dir_check_queued = true;
+
+                    return Command::none();
+                }
+
+                if self.is_checking_saves_dir || self.is_busy() {
+                    return Command::none();
+                }
+
+                self.is_checking_saves_dir = true;
+
+                return Command::perform(
+                    interaction::file_fingerprint::changed_files(
+                        self.config.saves_dir().to_path_buf(),
+                        self.file_fingerprints.clone(),
+                    ),
+                    |r| Bl3Message::SavesDirectoryChecked(MessageResult::handle_result(r)),
+                );
+            }
+            Bl3Message::SavesDirectoryChecked(res) => {
+                self.is_checking_saves_dir = false;
+
+                match res {
+                    MessageResult::Success(changed_files)
+                        if !changed_files.is_empty() && self.prompt.is_some() =>
+                    {
+                        self.is_saves_dir_check_queued = true;
+                    }
+                    // Something else may have started changing files while we were checking
+                    MessageResult::Success(changed_files)
+                        if !changed_files.is_empty() && !self.is_busy() =>
+                    {
+                        info!("Saves folder changed: {}", changed_files.join(", "));
+
+                        let selected_file_name = self.loaded_files_selected.filename().to_owned();
+
+                        let selected_file_changed = changed_files.contains(&selected_file_name);
+
+                        if selected_file_changed && state_mappers::has_unsaved_changes(self) {
+                            let msg = format!(
+                                "{} was changed by another program while you were editing it. Reload it and lose your edits or keep your edits?",
+                                selected_file_name
+                            );
+
+                            self.prompt = Some(
+                                Prompt::new(msg)
+                                    .action(
+                                        "Reload",
+                                        Bl3Message::Interaction(
+                                            InteractionMessage::ReloadModifiedFile,
+                                        ),
+                                    )
+                                    .action(
+                                        "Keep My Edits",
+                                        Bl3Message::Interaction(
+                                            InteractionMessage::KeepUnsavedEdits,
+                                        ),
+                                    ),
+                            );
+                        } else {
+                            self.notification = Some(Notification::new(
+                                format!(
+                                    "Reloaded files that were changed by another program: {}",
+                                    changed_files.join(", ")
+                                ),
+                                NotificationSentiment::Info,
+                            ));
+
+                            return self.reload_files(if selected_file_changed {
+                                EditorReload::Remap
+                            } else {
+                                EditorReload::KeepEdits
+                            });
+                        }
+                    }
+                    MessageResult::Success(_) => (),
+                    MessageResult::Error(e) => {
+                        error!("Failed to check saves folder for changes: {}", e);
+                    }
+                }
+            }
+            Bl3Message::DiagnosticsCompleted(res) => match res {
+                MessageResult::Success((file_name, report)) => {
+                    self.prompt = Some(
+                        Prompt::new(format!("Diagnostics for {}", file_name))
+                            .details(report)
+                            .action(
+                                "Open Backups Folder",
+                                Bl3Message::Interaction(InteractionMessage::SettingsInteraction(
+                                    SettingsInteractionMessage::OpenBackupDir,
+                                )),
+                            ),
+                    );
+                }
+                MessageResult::Error(e) => {
+                    let msg = format!("Failed to run diagnostics: {}", e);
+
+                    error!("{}", msg);
+
+                    self.notification =
+                        Some(Notification::new(msg, NotificationSentiment::Negative));
+                }
+            },
+            Bl3Message::PromptActionPressed(message) => {
+                self.prompt = None;
+
+                let command = self.update(*message);
+
+                return Command::batch([command, self.check_queued_saves_dir()]);
+            }
+            Bl3Message::ClearPrompt => {
+                self.prompt = None;
+
+                return self.check_queued_saves_dir();
+            }
             Bl3Message::ClearNotification => {
                 self.notification = None;
             }
+            Bl3Message::WindowCloseRequested => {
+                let msg = format!(
+                    "{} has unsaved changes. Quit without saving them?",
+                    self.loaded_files_selected.filename()
+                );
+
+                if !self.confirm_discard_changes(msg, "Quit", Bl3Message::Exit) {
+                    self.should_exit = true;
+                }
+            }
+            Bl3Message::Exit => {
+                self.should_exit = true;
+            }
         };
 
         Command::none()
     }
+}
+
+/// A file that was changed by something else is not an error, it asks how to continue instead.
+fn save_completed_message<T>(
+    res: anyhow::Result<T>,
+    on_completed: fn(MessageResult<T>) -> Bl3Message,
+) -> Bl3Message {
+    match res {
+        Ok(v) => on_completed(MessageResult::Success(v)),
+        Err(e) => match e.downcast::<FileModifiedError>() {
+            Ok(e) => Bl3Message::FileModifiedExternally(e.to_string()),
+            Err(e) => on_completed(MessageResult::Error(e.to_string())),
+        },
+    }
+}
+
+fn window_event(event: Event, _status: event::Status) -> Option<Bl3Message> {
+    match event {
+        Event::Window(window::Event::CloseRequested) => Some(Bl3Message::WindowCloseRequested),
+        _ => None,
+    }
+}
+
+fn keyboard_shortcut(event: Event, status: event::Status) -> Option<Bl3Message> {
+    // Let text inputs handle any shortcuts of their own first
+    if status == event::Status::Captured {
+        return None;
+    }
+
+    match event {
+        Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code,
+            modifiers,
+        }) if modifiers.command() => match key_code {
+            KeyCode::Z if modifiers.shift() => Some(InteractionMessage::Redo),
+            KeyCode::Z => Some(InteractionMessage::Undo),
+            KeyCode::Y => Some(InteractionMessage::Redo),
+            _ => None,
+        }
+        .map(Bl3Message::Interaction),
+        _ => None,
+    }
+}
+
+impl Application for Bl3Application {
+    type Executor = tokio::runtime::Runtime;
+    type Message = Bl3Message;
+    type Flags = Bl3Config;
+
+    fn new(config: Self::Flags) -> (Self, Command<Self::Message>) {
+        let startup_commands = [
+            Command::perform(initialization::load_lazy_data(), |_| {
+                Bl3Message::Initialization(InitializationMessage::LoadSaves)
+            }),
+            Command::perform(update::get_latest_release(), |r| {
+                Bl3Message::LatestRelease(MessageResult::handle_result(r))
+            }),
+        ];
+
+        let config_dir_input = config.config_dir().to_string_lossy().to_string();
+        let saves_dir_input = config.saves_dir().to_string_lossy().to_string();
+        let backup_dir_input = config.backup_dir().to_string_lossy().to_string();
+        let ui_scale_factor = config.ui_scale_factor();
+        let backup_retention = config.backup_retention();
+
+        (
+            Bl3Application {
+                config,
+                view_state: ViewState::Initializing,
+                settings_state: SettingsState {
+                    config_dir_input,
+                    backup_dir_input,
+                    saves_dir_input,
+                    ui_scale_factor,
+                    backup_retention,
+                    ..SettingsState::default()
+                },
+                ..Bl3Application::default()
+            },
+            Command::batch(startup_commands),
+        )
+    }
+
+    fn title(&self) -> String {
+        format!("Borderlands 3 Save Editor - v{}", VERSION)
+    }
+
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        edit_history::before_message(self, &message);
+
+        self.handle_message(message)
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        let is_editing = matches!(
+            self.view_state,
+            ViewState::ManageSave(_) | ViewState::ManageProfile(_)
+        );
+
+        // Closing the window is handled by us so that unsaved changes can be warned about
+        let mut subscriptions = vec![iced_native::subscription::events_with(window_event)];
+
+        if let Some(directory_load) = &self.directory_load {
+            subscriptions.push(
+                Subscription::from_recipe(directory_load.load.clone())
+                    .map(|(id, event)| Bl3Message::DirectoryLoad(id, event)),
+            );
+        }
+
+        if !is_editing {
+            return Subscription::batch(subscriptions);
+        }
+
+        subscriptions.push(iced_native::subscription::events_with(keyboard_shortcut));
+
+        subscriptions.push(
+            Subscription::from_recipe(SavesDirWatch {
+                dir: self.config.saves_dir().to_path_buf(),
+            })
+            .map(|_| Bl3Message::CheckSavesDirectory),
+        );
+
+        Subscription::batch(subscriptions)
+    }
+
+    fn should_exit(&self) -> bool {
+        self.should_exit
+    }
 
     fn view(&mut self) -> Element<'_, Self::Message> {
+        let directory_load_progress = self.directory_load_progress();
+
         let title = Text::new("Borderlands 3 Save Editor".to_uppercase())
             .font(JETBRAINS_MONO_NL_EXTRA_BOLD_ITALIC)
             .size(40)
@@ -1588,12 +3435,102 @@ impl Application for Bl3Application {
         .size(17)
         .style(Bl3UiTooltipStyle);
 
+        let duplicate_items_icon_handle = svg::Handle::from_memory(DUPLICATES);
+
+        let duplicate_items_icon = Svg::new(duplicate_items_icon_handle)
+            .height(Length::Units(17))
+            .width(Length::Units(17));
+
+        let duplicate_items_button = Tooltip::new(
+            Button::new(&mut self.duplicate_items_button_state, duplicate_items_icon)
+                .on_press(InteractionMessage::FindDuplicateItems)
+                .padding(10)
+                .style(Bl3UiStyle)
+                .into_element(),
+            "Find duplicate items",
+            tooltip::Position::Bottom,
+        )
+        .gap(10)
+        .padding(10)
+        .font(JETBRAINS_MONO)
+        .size(17)
+        .style(Bl3UiTooltipStyle);
+
+        let item_search_icon_handle = svg::Handle::from_memory(SEARCH);
+
+        let item_search_icon = Svg::new(item_search_icon_handle)
+            .height(Length::Units(17))
+            .width(Length::Units(17));
+
+        let item_search_button = Tooltip::new(
+            Button::new(&mut self.item_search_button_state, item_search_icon)
+                .on_press(InteractionMessage::SearchAllItems)
+                .padding(10)
+                .style(Bl3UiStyle)
+                .into_element(),
+            "Search items in all files",
+            tooltip::Position::Bottom,
+        )
+        .gap(10)
+        .padding(10)
+        .font(JETBRAINS_MONO)
+        .size(17)
+        .style(Bl3UiTooltipStyle);
+
+        let backups_icon_handle = svg::Handle::from_memory(BACKUPS);
+
+        let backups_icon = Svg::new(backups_icon_handle)
+            .height(Length::Units(17))
+            .width(Length::Units(17));
+
+        let backups_button = Tooltip::new(
+            Button::new(&mut self.backups_button_state, backups_icon)
+                .on_press(InteractionMessage::ManageBackups)
+                .padding(10)
+                .style(Bl3UiStyle)
+                .into_element(),
+            "Manage backups",
+            tooltip::Position::Bottom,
+        )
+        .gap(10)
+        .padding(10)
+        .font(JETBRAINS_MONO)
+        .size(17)
+        .style(Bl3UiTooltipStyle);
+
+        let save_slots_icon_handle = svg::Handle::from_memory(CHARACTER);
+
+        let save_slots_icon = Svg::new(save_slots_icon_handle)
+            .height(Length::Units(17))
+            .width(Length::Units(17));
+
+        let save_slots_button = Tooltip::new(
+            Button::new(&mut self.save_slots_button_state, save_slots_icon)
+                .on_press(InteractionMessage::ManageSaveSlots)
+                .padding(10)
+                .style(Bl3UiStyle)
+                .into_element(),
+            "Manage save slots",
+            tooltip::Position::Bottom,
+        )
+        .gap(10)
+        .padding(10)
+        .font(JETBRAINS_MONO)
+        .size(17)
+        .style(Bl3UiTooltipStyle);
+
         let all_saves_picklist = if !self.is_reloading_saves {
+            let entries = self
+                .loaded_files
+                .iter()
+                .map(LoadedFileEntry::readable)
+                .collect::<Vec<_>>();
+
             PickList::new(
                 &mut self.loaded_files_selector,
-                &self.loaded_files,
-                Some(*self.loaded_files_selected.clone()),
-                |f| InteractionMessage::LoadedFileSelected(Box::new(f)),
+                entries,
+                Some(LoadedFileEntry::readable(&self.loaded_files_selected)),
+                InteractionMessage::LoadedFileSelected,
             )
             .font(JETBRAINS_MONO)
             .text_size(17)
@@ -1614,6 +3551,16 @@ impl Application for Bl3Application {
             .into()
         };
 
+        let unreadable_files_picklist =
+            if !self.is_reloading_saves && !self.unreadable_files.is_empty() {
+                Some(views::choose_save_directory::unreadable_files_picklist(
+                    &mut self.unreadable_files_selector,
+                    &self.unreadable_files,
+                ))
+            } else {
+                None
+            };
+
         let view_state_discrim = mem::discriminant(&self.view_state);
         let manage_save_discrim = mem::discriminant(&ViewState::ManageSave(
             ManageSaveView::TabBar(SaveTabBarView::General),
@@ -1647,7 +3594,25 @@ impl Application for Bl3Application {
         if view_state_discrim == manage_save_discrim || view_state_discrim == manage_profile_discrim
         {
             menu_bar_editor_content = menu_bar_editor_content.push(refresh_button);
+            menu_bar_editor_content = menu_bar_editor_content.push(duplicate_items_button);
+            menu_bar_editor_content = menu_bar_editor_content.push(item_search_button);
+            menu_bar_editor_content = menu_bar_editor_content.push(backups_button);
+            menu_bar_editor_content = menu_bar_editor_content.push(save_slots_button);
             menu_bar_editor_content = menu_bar_editor_content.push(all_saves_picklist);
+
+            if let Some(unreadable_files_picklist) = unreadable_files_picklist {
+                menu_bar_editor_content = menu_bar_editor_content.push(unreadable_files_picklist);
+            }
+
+            if let Some(progress) = &directory_load_progress {
+                menu_bar_editor_content = menu_bar_editor_content.push(
+                    Text::new(progress)
+                        .font(JETBRAINS_MONO)
+                        .color(Color::from_rgb8(220, 220, 200))
+                        .size(17),
+                );
+            }
+
             menu_bar_editor_content = menu_bar_editor_content.push(save_button.into_element());
         }
 
@@ -1692,10 +3657,13 @@ impl Application for Bl3Application {
 
         let content = match &self.view_state {
             ViewState::Initializing => views::initialization::view(),
-            ViewState::Loading => views::loading::view(),
-            ViewState::ChooseSaveDirectory => {
-                views::choose_save_directory::view(&mut self.choose_save_directory_state)
-            }
+            ViewState::Loading => views::loading::view(
+                directory_load_progress.unwrap_or_else(|| "Loading...".to_owned()),
+            ),
+            ViewState::ChooseSaveDirectory => views::choose_save_directory::view(
+                &mut self.choose_save_directory_state,
+                &self.unreadable_files,
+            ),
             ViewState::ManageSave(manage_save_view) => match manage_save_view {
                 ManageSaveView::TabBar(main_tab_bar_view) => views::manage_save::main::view(
                     &mut self.settings_state,
@@ -1710,6 +3678,12 @@ impl Application for Bl3Application {
                     main_tab_bar_view,
                 ),
             },
+            ViewState::DuplicateItems => {
+                views::duplicate_items::view(&mut self.duplicate_items_state)
+            }
+            ViewState::ItemSearch => views::item_search::view(&mut self.item_search_state),
+            ViewState::Backups => views::backups::view(&mut self.backups_state),
+            ViewState::SaveSlots => views::save_slots::view(&mut self.save_slots_state),
         };
 
         let mut all_content = Column::new().push(menu_bar);
@@ -1718,6 +3692,10 @@ impl Application for Bl3Application {
             all_content = all_content.push(notification.view());
         }
 
+        if let Some(prompt) = &mut self.prompt {
+            all_content = all_content.push(prompt.view());
+        }
+
         all_content = all_content.push(content);
 
         Container::new(all_content)
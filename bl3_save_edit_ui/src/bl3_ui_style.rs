@@ -308,3 +308,43 @@ impl button::StyleSheet for Bl3UiPositiveButtonStyle {
         }
     }
 }
+
+/// Files that couldn't be read, these are listed so that the reason can be shown but look disabled.
+pub struct Bl3UiUnreadableStyle;
+
+impl pick_list::StyleSheet for Bl3UiUnreadableStyle {
+    fn menu(&self) -> Menu {
+        Menu {
+            text_color: Color::from_rgb8(130, 130, 130),
+            background: Color::from_rgb8(20, 20, 20).into(),
+            border_width: 1.5,
+            border_color: Color::from_rgb8(35, 35, 35),
+            selected_background: Color::from_rgb8(28, 28, 28).into(),
+            selected_text_color: Color::from_rgb8(160, 160, 160),
+        }
+    }
+
+    fn active(&self) -> pick_list::Style {
+        pick_list::Style {
+            background: Color::from_rgb8(20, 20, 20).into(),
+            text_color: Color::from_rgb8(130, 130, 130),
+            border_width: 1.0,
+            border_radius: 1.0,
+            border_color: Color::from_rgb8(30, 30, 30),
+            icon_size: 0.5,
+            placeholder_color: Color::from_rgb8(130, 130, 130),
+        }
+    }
+
+    fn hovered(&self) -> pick_list::Style {
+        pick_list::Style {
+            background: Color::from_rgb8(25, 25, 25).into(),
+            text_color: Color::from_rgb8(160, 160, 160),
+            border_width: 1.0,
+            border_radius: 1.0,
+            border_color: Color::from_rgb8(40, 40, 40),
+            icon_size: 0.5,
+            placeholder_color: Color::from_rgb8(160, 160, 160),
+        }
+    }
+}
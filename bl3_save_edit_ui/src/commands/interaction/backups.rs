@@ -0,0 +1,336 @@
+use std::cmp::Reverse;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use tracing::{error, info};
+
+use bl3_save_edit_core::file_helper::Bl3FileType;
+
+use crate::commands::interaction::choose_save_directory;
+use crate::commands::interaction::choose_save_directory::LoadedFiles;
+use crate::commands::interaction::file_save::{
+    backup_profile, backup_save, write_file_atomically, BACKUP_TIME_FORMAT,
+};
+use crate::config::BackupRetention;
+
+// The length of a time formatted with BACKUP_TIME_FORMAT
+const BACKUP_TIME_LEN: usize = 23;
+
+// Older backups were only named down to the second
+const LEGACY_BACKUP_TIME_FORMAT: &str = "%d-%m-%Y_%H.%M.%S";
+const LEGACY_BACKUP_TIME_LEN: usize = 19;
+
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created: DateTime<Local>,
+    pub file: Bl3FileType,
+}
+
+pub async fn load_backups(backup_dir: PathBuf) -> Result<Vec<Backup>> {
+    let start_time = tokio::time::Instant::now();
+
+    let mut dirs = tokio::fs::read_dir(&*backup_dir).await?;
+
+    let mut all_data = vec![];
+
+    while let Ok(entry) = dirs.next_entry().await {
+        if let Some(entry) = entry {
+            let path = entry.path();
+            if choose_save_directory::is_save_file(&path) {
+                let modified = entry.metadata().await.and_then(|m| m.modified());
+
+                match tokio::fs::read(&path).await {
+                    Ok(data) => all_data.push((path, data, modified.ok())),
+                    Err(e) => error!("{}", e),
+                }
+            }
+        } else {
+            break;
+        }
+    }
+
+    let mut backups: Vec<Backup> = tokio_rayon::spawn(move || {
+        all_data
+            .into_par_iter()
+            .filter_map(|(path, data, modified)| {
+                let file = Bl3FileType::from_unknown_data(&path, &data).ok()?;
+
+                let created = backup_name_parts(&path)
+                    .map(|(_, created)| created)
+                    .or_else(|| modified.map(DateTime::<Local>::from))
+                    .unwrap_or_else(Local::now);
+
+                Some(Backup {
+                    path,
+                    created,
+                    file,
+                })
+            })
+            .collect::<Vec<_>>()
+    })
+    .await;
+
+    backups.sort_by_key(|b| Reverse(b.created));
+
+    if let Some(end_time) = tokio::time::Instant::now().checked_duration_since(start_time) {
+        info!(
+            "Read {} backups in {} milliseconds",
+            backups.len(),
+            end_time.as_millis()
+        );
+    }
+
+    Ok(backups)
+}
+
+/// Backups are named `<name>-<time>.sav` where `<name>` is the file they were made from, save
+/// backups also have the class and character name after the time. Returns `None` for anything
+/// that was renamed.
+fn backup_name_parts(path: &Path) -> Option<(&str, DateTime<Local>)> {
+    let file_stem = path.file_stem().and_then(OsStr::to_str)?;
+
+    let time_formats = [
+        (BACKUP_TIME_FORMAT, BACKUP_TIME_LEN),
+        (LEGACY_BACKUP_TIME_FORMAT, LEGACY_BACKUP_TIME_LEN),
+    ];
+
+    // The name may contain a `-` too so try each one until a time follows it
+    file_stem.match_indices('-').find_map(|(time_start, _)| {
+        let name = &file_stem[..time_start];
+        let rest = &file_stem[time_start + 1..];
+
+        let time = time_formats.iter().find_map(|(format, len)| {
+            let after_time = rest.get(*len..)?;
+
+            if !after_time.is_empty() && !after_time.starts_with('-') {
+                return None;
+            }
+
+            NaiveDateTime::parse_from_str(rest.get(..*len)?, format).ok()
+        })?;
+
+        Local
+            .from_local_datetime(&time)
+            .single()
+            .map(|created| (name, created))
+    })
+}
+
+#[derive(Debug)]
+struct BackupFile {
+    path: PathBuf,
+    name: String,
+    created: DateTime<Local>,
+    size: u64,
+}
+
+async fn read_backup_files(backup_dir: &Path) -> Result<Vec<BackupFile>> {
+    let mut dirs = tokio::fs::read_dir(backup_dir).await?;
+
+    let mut backup_files = vec![];
+
+    while let Some(entry) = dirs.next_entry().await? {
+        let path = entry.path();
+
+        if let Some((name, created)) = backup_name_parts(&path) {
+            let name = name.to_owned();
+            let size = entry.metadata().await?.len();
+
+            backup_files.push(BackupFile {
+                path,
+                name,
+                created,
+                size,
+            });
+        }
+    }
+
+    // Newest first
+    backup_files.sort_by_key(|b| Reverse(b.created));
+
+    Ok(backup_files)
+}
+
+/// Writes a backup named `backup_name` unless the latest backup of the same file has exactly the
+/// same contents, then deletes any backups that `retention` no longer keeps if it's turned on.
+pub async fn write_backup(
+    backup_dir: &Path,
+    backup_retention: BackupRetention,
+    backup_name: String,
+    data: Vec<u8>,
+) -> Result<()> {
+    let backup_path = backup_dir.join(backup_name);
+
+    let backup_files = read_backup_files(backup_dir).await?;
+
+    let latest_backup = backup_name_parts(&backup_path)
+        .and_then(|(name, _)| backup_files.iter().find(|b| b.name == name));
+
+    if let Some(latest_backup) = latest_backup {
+        if latest_backup.size == data.len() as u64
+            && tokio::fs::read(&latest_backup.path).await? == data
+        {
+            info!(
+                "Skipping backup as it is the same as: {}",
+                latest_backup.path.display()
+            );
+
+            return Ok(());
+        }
+    }
+
+    tokio::fs::write(&backup_path, data).await?;
+
+    if backup_retention.delete_old_backups {
+        prune_backups(backup_dir, backup_retention).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn prune_backups(backup_dir: &Path, backup_retention: BackupRetention) -> Result<()> {
+    let backup_files = read_backup_files(backup_dir).await?;
+
+    for backup_file in backups_to_remove(&backup_files, backup_retention) {
+        info!("Removing old backup: {}", backup_file.path.display());
+
+        if let Err(e) = tokio::fs::remove_file(&backup_file.path).await {
+            error!(
+                "Failed to remove old backup: {} - {}",
+                backup_file.path.display(),
+                e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// `backup_files` must be sorted newest first.
+fn backups_to_remove(
+    backup_files: &[BackupFile],
+    backup_retention: BackupRetention,
+) -> Vec<&BackupFile> {
+    let mut kept = vec![false; backup_files.len()];
+
+    let mut names: Vec<&str> = backup_files.iter().map(|b| b.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+
+    for name in names {
+        let mut days_seen = Vec::new();
+        let mut weeks_seen = Vec::new();
+
+        let file_backups = backup_files
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.name == name);
+
+        for (n, (i, backup_file)) in file_backups.enumerate() {
+            let date = backup_file.created.naive_local().date();
+            let week = (date.iso_week().year(), date.iso_week().week());
+
+            // Always keep the latest backup of a file
+            if n == 0 || n < backup_retention.keep_last {
+                kept[i] = true;
+            }
+
+            if !days_seen.contains(&date) && days_seen.len() < backup_retention.keep_daily {
+                days_seen.push(date);
+                kept[i] = true;
+            }
+
+            if !weeks_seen.contains(&week) && weeks_seen.len() < backup_retention.keep_weekly {
+                weeks_seen.push(week);
+                kept[i] = true;
+            }
+        }
+    }
+
+    if backup_retention.max_total_size_mb > 0 {
+        let max_total_size = backup_retention.max_total_size_mb * 1024 * 1024;
+
+        let mut total_size: u64 = backup_files
+            .iter()
+            .zip(&kept)
+            .filter(|(_, k)| **k)
+            .map(|(b, _)| b.size)
+            .sum();
+
+        // Remove the oldest backups first but never the latest backup of a file
+        for i in (0..backup_files.len()).rev() {
+            if total_size <= max_total_size {
+                break;
+            }
+
+            let backup_file = &backup_files[i];
+
+            let is_latest = backup_files.iter().position(|b| b.name == backup_file.name) == Some(i);
+
+            if kept[i] && !is_latest {
+                kept[i] = false;
+                total_size -= backup_file.size;
+            }
+        }
+    }
+
+    backup_files
+        .iter()
+        .zip(kept)
+        .filter(|(_, k)| !k)
+        .map(|(b, _)| b)
+        .collect()
+}
+
+/// Restores `backup` over `output_file_name` in the saves folder, the file that is currently
+/// there is backed up first so that the restore can itself be undone.
+pub async fn restore_backup(
+    backup_dir: PathBuf,
+    backup_retention: BackupRetention,
+    saves_dir: PathBuf,
+    backup: Backup,
+    output_file_name: String,
+    existing_file: Option<Bl3FileType>,
+) -> Result<LoadedFiles<String>> {
+    match existing_file {
+        Some(Bl3FileType::PcSave(existing_save)) | Some(Bl3FileType::Ps4Save(existing_save)) => {
+            backup_save(&backup_dir, backup_retention, &existing_save).await?;
+        }
+        Some(Bl3FileType::PcProfile(existing_profile))
+        | Some(Bl3FileType::Ps4Profile(existing_profile)) => {
+            backup_profile(&backup_dir, backup_retention, &existing_profile).await?;
+        }
+        None => (),
+    }
+
+    info!(
+        "Restoring backup: {} to: {}",
+        backup.path.display(),
+        output_file_name
+    );
+
+    let backup_data = tokio::fs::read(&backup.path)
+        .await
+        .with_context(|| format!("failed to read backup: {}", backup.path.display()))?;
+
+    write_file_atomically(
+        &saves_dir.join(&output_file_name),
+        backup_data,
+        |path, data| Bl3FileType::from_unknown_data(path, data).map(|_| ()),
+    )
+    .await?;
+
+    let (_, all_files, unreadable_files, file_fingerprints) =
+        choose_save_directory::load_files_in_directory(saves_dir).await?;
+
+    Ok((
+        output_file_name,
+        all_files,
+        unreadable_files,
+        file_fingerprints,
+    ))
+}
@@ -1,13 +1,18 @@
 use std::ffi::OsStr;
-use std::path::PathBuf;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use futures::channel::mpsc;
+use futures::stream::{self, BoxStream};
+use futures::{future, StreamExt};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use tracing::{error, info};
 
-use bl3_save_edit_core::file_helper::Bl3FileType;
+use bl3_save_edit_core::file_helper::{self, Bl3FileType};
 
 use crate::commands::interaction::choose_dir;
+use crate::commands::interaction::file_fingerprint::{FileFingerprint, FileFingerprints};
 
 #[cfg(not(target_os = "macos"))]
 pub async fn choose(existing_dir: PathBuf) -> Result<PathBuf> {
@@ -39,43 +44,137 @@ pub async fn choose(existing_dir: PathBuf) -> Result<PathBuf> {
     choose_dir(default_dir).await
 }
 
-pub async fn load_files_in_directory(dir: PathBuf) -> Result<(PathBuf, Vec<Bl3FileType>)> {
-    let start_time = tokio::time::Instant::now();
+/// Whether `path` is a file that is read from the saves folder, `.pb` files are bare protobuf
+/// payloads written by other tools.
+pub fn is_save_file(path: &Path) -> bool {
+    !path.is_dir() && matches!(path.extension().and_then(OsStr::to_str), Some("sav" | "pb"))
+}
 
-    let mut dirs = tokio::fs::read_dir(&*dir).await?;
-
-    let mut all_data = vec![];
-
-    while let Ok(entry) = dirs.next_entry().await {
-        if let Some(entry) = entry {
-            let path = entry.path();
-            if !path.is_dir()
-                && path
-                    .extension()
-                    .and_then(OsStr::to_str)
-                    .and_then(|p| if p == "sav" { Some(()) } else { None })
-                    .is_some()
-            {
-                match tokio::fs::read(&path).await {
-                    Ok(data) => all_data.push((path, data)),
-                    Err(e) => error!("{}", e),
-                }
+/// A `.sav` or `.pb` file in the saves folder that couldn't be read as a save or profile.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UnreadableFile {
+    pub path: PathBuf,
+    pub file_name: String,
+    pub reason: String,
+}
+
+/// The files in the saves folder along with `T`, which is whatever the caller needs passed back.
+pub type LoadedFiles<T> = (T, Vec<Bl3FileType>, Vec<UnreadableFile>, FileFingerprints);
+
+/// A file from the saves folder, sent on as soon as it has been read.
+#[derive(Debug, Clone)]
+pub struct LoadedFile {
+    pub file: Result<Bl3FileType, UnreadableFile>,
+    pub fingerprint: Option<(String, FileFingerprint)>,
+}
+
+impl LoadedFile {
+    pub fn add_to(
+        self,
+        files: &mut Vec<Bl3FileType>,
+        unreadable_files: &mut Vec<UnreadableFile>,
+        file_fingerprints: &mut FileFingerprints,
+    ) {
+        match self.file {
+            Ok(file) => insert_sorted(files, file),
+            Err(unreadable_file) => {
+                let pos =
+                    unreadable_files.partition_point(|f| f.file_name < unreadable_file.file_name);
+
+                unreadable_files.insert(pos, unreadable_file);
             }
-        } else {
-            break;
+        }
+
+        if let Some((name, fingerprint)) = self.fingerprint {
+            file_fingerprints.insert(name, fingerprint);
         }
     }
+}
 
-    let all_files: Vec<Bl3FileType> = tokio_rayon::spawn(move || {
-        all_data
-            .par_iter()
-            .filter_map(|(file_name, data)| Bl3FileType::from_unknown_data(file_name, data).ok())
-            .collect::<Vec<_>>()
-    })
-    .await;
+/// Puts `file` into `files` which are kept sorted by file name.
+pub fn insert_sorted(files: &mut Vec<Bl3FileType>, file: Bl3FileType) {
+    let pos = files.partition_point(|f| f.filename() < file.filename());
 
-    if all_files.is_empty() {
-        bail!("No Save files or Profiles were found.")
+    files.insert(pos, file);
+}
+
+/// What has happened so far when loading a saves folder as a stream.
+#[derive(Debug, Clone)]
+pub enum DirectoryLoadEvent {
+    /// The folder was listed, these are the names of the files that will be read sorted by name.
+    Listed(Vec<String>),
+    FileRead(LoadedFile),
+    Finished,
+    Failed(String),
+}
+
+/// Loads a saves folder while reporting each file as it's read, `id` is changed to start loading
+/// the same folder again.
+#[derive(Debug, Clone)]
+pub struct DirectoryLoad {
+    pub id: u64,
+    pub dir: PathBuf,
+}
+
+impl<H, I> iced_native::subscription::Recipe<H, I> for DirectoryLoad
+where
+    H: std::hash::Hasher,
+{
+    type Output = (u64, DirectoryLoadEvent);
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.id.hash(state);
+        self.dir.hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Self::Output> {
+        let DirectoryLoad { id, dir } = *self;
+
+        stream::once(async move { list_save_files(&dir).await })
+            .flat_map(|paths| match paths {
+                Ok(paths) => stream::once(future::ready(DirectoryLoadEvent::Listed(
+                    paths.iter().map(|p| file_name_of(p)).collect(),
+                )))
+                .chain(read_files(paths).map(DirectoryLoadEvent::FileRead))
+                .chain(stream::once(future::ready(DirectoryLoadEvent::Finished)))
+                .boxed(),
+                Err(e) => {
+                    stream::once(future::ready(DirectoryLoadEvent::Failed(e.to_string()))).boxed()
+                }
+            })
+            .map(move |event| (id, event))
+            .boxed()
+    }
+}
+
+pub async fn load_files_in_directory(dir: PathBuf) -> Result<LoadedFiles<PathBuf>> {
+    let loaded_files = read_files_in_directory(dir).await?;
+
+    ensure_files_read(&loaded_files.1, &loaded_files.2)?;
+
+    Ok(loaded_files)
+}
+
+/// Reads every file in `dir` without failing when there are none, such as after deleting the last
+/// save.
+pub async fn read_files_in_directory(dir: PathBuf) -> Result<LoadedFiles<PathBuf>> {
+    let start_time = tokio::time::Instant::now();
+
+    let paths = list_save_files(&dir).await?;
+
+    let mut all_files = Vec::new();
+    let mut unreadable_files = Vec::new();
+    let mut file_fingerprints = FileFingerprints::new();
+
+    let mut loaded_files = read_files(paths);
+
+    while let Some(loaded_file) = loaded_files.next().await {
+        loaded_file.add_to(
+            &mut all_files,
+            &mut unreadable_files,
+            &mut file_fingerprints,
+        );
     }
 
     if let Some(end_time) = tokio::time::Instant::now().checked_duration_since(start_time) {
@@ -86,5 +185,132 @@ pub async fn load_files_in_directory(dir: PathBuf) -> Result<(PathBuf, Vec<Bl3Fi
         );
     }
 
-    Ok((dir, all_files))
+    Ok((dir, all_files, unreadable_files, file_fingerprints))
+}
+
+/// Fails when there are no save files or profiles in the saves folder, even unreadable ones.
+pub fn ensure_files_found(
+    files: &[Bl3FileType],
+    unreadable_files: &[UnreadableFile],
+) -> Result<()> {
+    if files.is_empty() && unreadable_files.is_empty() {
+        bail!("No Save files or Profiles were found.")
+    }
+
+    Ok(())
+}
+
+/// Fails when nothing in the saves folder could be loaded, listing why each file couldn't be read.
+pub fn ensure_files_read(files: &[Bl3FileType], unreadable_files: &[UnreadableFile]) -> Result<()> {
+    ensure_files_found(files, unreadable_files)?;
+
+    if files.is_empty() {
+        bail!(
+            "No Save files or Profiles could be read - {}",
+            unreadable_files
+                .iter()
+                .map(|f| format!("{}: {}", f.file_name, f.reason))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    Ok(())
+}
+
+async fn list_save_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = tokio::fs::read_dir(dir).await?;
+
+    let mut paths = vec![];
+
+    while let Ok(Some(entry)) = dirs.next_entry().await {
+        let path = entry.path();
+
+        if is_save_file(&path) {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Reads and parses `paths` on the rayon pool, sending on each file as soon as it's done.
+fn read_files(paths: Vec<PathBuf>) -> mpsc::UnboundedReceiver<LoadedFile> {
+    let (sender, receiver) = mpsc::unbounded();
+
+    rayon::spawn(move || {
+        paths.into_par_iter().for_each_with(sender, |sender, path| {
+            // Nothing is listening any more if the load was abandoned
+            let _ = sender.unbounded_send(read_file(path));
+        })
+    });
+
+    receiver
+}
+
+fn read_file(path: PathBuf) -> LoadedFile {
+    let name = file_name_of(&path);
+
+    let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+    let data = match std::fs::read(&path) {
+        Ok(data) => data,
+        Err(e) => {
+            error!("{}", e);
+
+            return LoadedFile {
+                file: Err(UnreadableFile {
+                    path,
+                    file_name: name,
+                    reason: e.to_string(),
+                }),
+                fingerprint: None,
+            };
+        }
+    };
+
+    // Files that can't be read are fingerprinted too so that they aren't seen as new files when
+    // watching the folder for changes
+    let fingerprint = Some((name.clone(), FileFingerprint::new(&data, modified)));
+
+    let file = Bl3FileType::from_unknown_data(&path, &data).map_err(|e| {
+        error!("{} could not be read: {:#}", name, e);
+
+        UnreadableFile {
+            path,
+            file_name: name,
+            reason: format!("{:#}", e),
+        }
+    });
+
+    LoadedFile { file, fingerprint }
+}
+
+/// Goes through each step of reading a file that wouldn't load and reports how far it got.
+pub async fn diagnose_file(path: PathBuf) -> Result<(String, Vec<String>)> {
+    let file_name = file_name_of(&path);
+
+    let data = tokio::fs::read(&path).await?;
+
+    let report = tokio_rayon::spawn(move || file_helper::diagnose(&path, &data)).await;
+
+    Ok((file_name, report))
+}
+
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Loads the saves folder again, `file_name` is passed back so that it can stay selected.
+pub async fn reload_files_in_directory(
+    dir: PathBuf,
+    file_name: String,
+) -> Result<LoadedFiles<String>> {
+    let (_, all_files, unreadable_files, file_fingerprints) = load_files_in_directory(dir).await?;
+
+    Ok((file_name, all_files, unreadable_files, file_fingerprints))
 }
@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use tracing::info;
+
+use bl3_save_edit_core::file_helper::Bl3FileType;
+use bl3_save_edit_core::parser::Platform;
+
+use crate::commands::interaction::choose_save_directory;
+use crate::commands::interaction::file_save::write_file_atomically;
+
+/// Converts every save and profile in `saves_dir` to `platform` and writes them to `output_dir`,
+/// the saves folder itself is never written to.
+pub async fn convert_dir(
+    saves_dir: PathBuf,
+    output_dir: PathBuf,
+    platform: Platform,
+) -> Result<(PathBuf, usize)> {
+    if tokio::fs::canonicalize(&saves_dir).await? == tokio::fs::canonicalize(&output_dir).await? {
+        bail!("Converted files must be written to a different folder than the saves folder.");
+    }
+
+    let (_, files, _, _) = choose_save_directory::load_files_in_directory(saves_dir).await?;
+
+    let converted = tokio_rayon::spawn(move || {
+        files
+            .into_par_iter()
+            .map(|file| {
+                file.convert_to(platform)
+                    .with_context(|| format!("failed to convert {}", file.filename()))
+            })
+            .collect::<Result<Vec<_>>>()
+    })
+    .await?;
+
+    let mut file_names = HashSet::new();
+
+    for (file_name, _) in &converted {
+        if !file_names.insert(file_name) {
+            bail!(
+                "More than one file would be named {} on {}.",
+                file_name,
+                platform
+            );
+        }
+
+        if output_dir.join(file_name).exists() {
+            bail!("{} already exists in the chosen folder.", file_name);
+        }
+    }
+
+    let count = converted.len();
+
+    for (file_name, output) in converted {
+        info!("Writing converted file: {}", file_name);
+
+        write_file_atomically(&output_dir.join(&file_name), output, |path, data| {
+            Bl3FileType::from_unknown_data(path, data).map(|_| ())
+        })
+        .await?;
+    }
+
+    Ok((output_dir, count))
+}
@@ -0,0 +1,185 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use tracing::info;
+
+use bl3_save_edit_core::bl3_profile::Bl3Profile;
+use bl3_save_edit_core::bl3_save::Bl3Save;
+use bl3_save_edit_core::file_helper::Bl3FileType;
+use bl3_save_edit_core::item_index::{remove_indexed_items, IndexedItem};
+
+use crate::commands::interaction::choose_save_directory::LoadedFiles;
+use crate::commands::interaction::file_fingerprint::{FileFingerprints, OverwriteCheck};
+use crate::commands::interaction::{choose_save_directory, file_save};
+use crate::config::BackupRetention;
+
+/// A file that duplicates were removed from, along with the file as it was loaded.
+enum ChangedFile {
+    Save {
+        existing: Bl3Save,
+        new: Bl3Save,
+    },
+    Profile {
+        existing: Bl3Profile,
+        new: Bl3Profile,
+    },
+}
+
+struct FileWrite {
+    file_name: String,
+    output_file: PathBuf,
+    output: Vec<u8>,
+    overwrite_check: OverwriteCheck,
+    changed_file: ChangedFile,
+}
+
+pub async fn remove_duplicate_items(
+    backup_dir: PathBuf,
+    backup_retention: BackupRetention,
+    saves_dir: PathBuf,
+    files: Vec<Bl3FileType>,
+    file_fingerprints: FileFingerprints,
+    items: Vec<IndexedItem>,
+) -> Result<LoadedFiles<usize>> {
+    let mut total_removed = 0;
+
+    let mut file_writes = Vec::new();
+
+    for file in files {
+        let mut new_file = file.clone();
+
+        let removed = remove_indexed_items(&mut new_file, &items)?;
+
+        if removed == 0 {
+            continue;
+        }
+
+        let file_name = new_file.filename().to_owned();
+
+        let (output, changed_file) = match (file, new_file) {
+            (Bl3FileType::PcSave(existing), Bl3FileType::PcSave(new_save))
+            | (Bl3FileType::Ps4Save(existing), Bl3FileType::Ps4Save(new_save)) => {
+                info!(
+                    "Removing {} duplicate items from save: {}",
+                    removed, file_name
+                );
+
+                let (output, new) = new_save.as_bytes()?;
+
+                (output, ChangedFile::Save { existing, new })
+            }
+            (Bl3FileType::PcProfile(existing), Bl3FileType::PcProfile(new_profile))
+            | (Bl3FileType::Ps4Profile(existing), Bl3FileType::Ps4Profile(new_profile)) => {
+                info!(
+                    "Removing {} duplicate items from profile: {}",
+                    removed, file_name
+                );
+
+                let (output, new) = new_profile.as_bytes()?;
+
+                (output, ChangedFile::Profile { existing, new })
+            }
+            _ => unreachable!("file type changed while removing duplicate items"),
+        };
+
+        let overwrite_check = file_fingerprints
+            .get(&file_name)
+            .copied()
+            .map_or(OverwriteCheck::Any, OverwriteCheck::Unchanged);
+
+        file_writes.push(FileWrite {
+            output_file: saves_dir.join(&file_name),
+            file_name,
+            output,
+            overwrite_check,
+            changed_file,
+        });
+
+        total_removed += removed;
+    }
+
+    // Check every file before writing any of them so that one changed by the game doesn't leave
+    // duplicates removed from only some of the files
+    for file_write in &file_writes {
+        file_write
+            .overwrite_check
+            .ensure(&file_write.output_file)
+            .await?;
+    }
+
+    let mut changed_file_names = Vec::new();
+
+    for file_write in file_writes {
+        let file_name = file_write.file_name.clone();
+
+        if let Err(e) = write_file(&backup_dir, backup_retention, &saves_dir, file_write).await {
+            if changed_file_names.is_empty() {
+                return Err(e);
+            }
+
+            bail!(
+                "{}, duplicate items were already removed from: {}",
+                e,
+                changed_file_names.join(", ")
+            );
+        }
+
+        changed_file_names.push(file_name);
+    }
+
+    let (_, all_files, unreadable_files, file_fingerprints) =
+        choose_save_directory::load_files_in_directory(saves_dir).await?;
+
+    Ok((
+        total_removed,
+        all_files,
+        unreadable_files,
+        file_fingerprints,
+    ))
+}
+
+async fn write_file(
+    backup_dir: &Path,
+    backup_retention: BackupRetention,
+    saves_dir: &Path,
+    file_write: FileWrite,
+) -> Result<()> {
+    let FileWrite {
+        output_file,
+        output,
+        overwrite_check,
+        changed_file,
+        ..
+    } = file_write;
+
+    match changed_file {
+        ChangedFile::Save { existing, new } => {
+            file_save::save_file(
+                backup_dir.to_path_buf(),
+                backup_retention,
+                output_file,
+                output,
+                existing,
+                new,
+                overwrite_check,
+            )
+            .await?;
+        }
+        ChangedFile::Profile { existing, new } => {
+            file_save::save_profile(
+                backup_dir.to_path_buf(),
+                backup_retention,
+                saves_dir.to_path_buf(),
+                output_file,
+                output,
+                existing,
+                new,
+                overwrite_check,
+                None,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
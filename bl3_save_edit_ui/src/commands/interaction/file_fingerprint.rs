@@ -0,0 +1,229 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::Result;
+use tracing::warn;
+
+use crate::commands::interaction::choose_save_directory;
+
+/// Fingerprints of the files in the saves folder, by file name.
+pub type FileFingerprints = HashMap<String, FileFingerprint>;
+
+/// What a file looked like when it was loaded so that we can tell if the game or another program
+/// has written to it since.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FileFingerprint {
+    modified: Option<SystemTime>,
+    hash: u64,
+}
+
+impl FileFingerprint {
+    pub fn new(data: &[u8], modified: Option<SystemTime>) -> Self {
+        FileFingerprint {
+            modified,
+            hash: hash_data(data),
+        }
+    }
+}
+
+/// What the file that is about to be written over has to look like.
+#[derive(Debug, Copy, Clone)]
+pub enum OverwriteCheck {
+    /// Write over whatever is there, for when the user chose to.
+    Any,
+    /// The file must not have changed since it was loaded.
+    Unchanged(FileFingerprint),
+    /// No file with this name was loaded so there must not be one now, such as after changing the
+    /// format of a save changes its extension.
+    Missing,
+}
+
+impl OverwriteCheck {
+    /// Returns a `FileModifiedError` if the file at `path` isn't what is expected.
+    pub async fn ensure(&self, path: &Path) -> Result<()> {
+        match self {
+            OverwriteCheck::Any => Ok(()),
+            OverwriteCheck::Unchanged(file_fingerprint) => {
+                ensure_unchanged(path, file_fingerprint).await
+            }
+            OverwriteCheck::Missing => {
+                if tokio::fs::metadata(path).await.is_ok() {
+                    return Err(FileModifiedError {
+                        file_name: file_name(path),
+                        kind: FileModifiedKind::Created,
+                    }
+                    .into());
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FileModifiedKind {
+    Changed,
+    Deleted,
+    /// A file that wasn't loaded is in the way.
+    Created,
+}
+
+#[derive(Debug)]
+pub struct FileModifiedError {
+    pub file_name: String,
+    pub kind: FileModifiedKind,
+}
+
+impl std::fmt::Display for FileModifiedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            FileModifiedKind::Changed => write!(
+                f,
+                "{} was changed by another program after it was loaded",
+                self.file_name
+            ),
+            FileModifiedKind::Deleted => {
+                write!(f, "{} was deleted after it was loaded", self.file_name)
+            }
+            FileModifiedKind::Created => write!(
+                f,
+                "{} already exists in the saves folder but it was not loaded",
+                self.file_name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FileModifiedError {}
+
+/// Returns a `FileModifiedError` if the file at `path` no longer matches `file_fingerprint`.
+async fn ensure_unchanged(path: &Path, file_fingerprint: &FileFingerprint) -> Result<()> {
+    let modified = tokio::fs::metadata(path)
+        .await
+        .and_then(|m| m.modified())
+        .ok();
+
+    let hash = if modified.is_some() && modified == file_fingerprint.modified {
+        Some(file_fingerprint.hash)
+    } else {
+        read_hash(path).await?
+    };
+
+    if hash != Some(file_fingerprint.hash) {
+        let kind = match hash {
+            Some(_) => FileModifiedKind::Changed,
+            None => FileModifiedKind::Deleted,
+        };
+
+        return Err(FileModifiedError {
+            file_name: file_name(path),
+            kind,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Returns the names of the save files in `dir` that were added, removed or changed since
+/// `file_fingerprints` were recorded.
+pub async fn changed_files(
+    dir: PathBuf,
+    file_fingerprints: FileFingerprints,
+) -> Result<Vec<String>> {
+    let mut dirs = tokio::fs::read_dir(&dir).await?;
+
+    let mut changed_files = vec![];
+    let mut existing_files = HashSet::new();
+
+    while let Some(entry) = dirs.next_entry().await? {
+        let path = entry.path();
+
+        if !choose_save_directory::is_save_file(&path) {
+            continue;
+        }
+
+        let file_name = match path.file_name().and_then(OsStr::to_str) {
+            Some(file_name) => file_name.to_owned(),
+            None => continue,
+        };
+
+        let is_changed = match file_fingerprints.get(&file_name) {
+            Some(file_fingerprint) => {
+                let modified = entry.metadata().await.and_then(|m| m.modified()).ok();
+
+                if modified.is_some() && modified == file_fingerprint.modified {
+                    false
+                } else {
+                    // The game may still be writing to the file, if so it will be checked again
+                    // next time
+                    match read_hash(&path).await {
+                        Ok(hash) => hash != Some(file_fingerprint.hash),
+                        Err(e) => {
+                            warn!("Failed to read file: {} - {}", path.display(), e);
+
+                            false
+                        }
+                    }
+                }
+            }
+            None => true,
+        };
+
+        if is_changed {
+            changed_files.push(file_name.clone());
+        }
+
+        existing_files.insert(file_name);
+    }
+
+    changed_files.extend(
+        file_fingerprints
+            .into_keys()
+            .filter(|file_name| !existing_files.contains(file_name)),
+    );
+
+    changed_files.sort();
+
+    Ok(changed_files)
+}
+
+/// Fingerprints the file at `path` as it is now, such as after we have written to it.
+pub async fn fingerprint(path: &Path) -> Result<FileFingerprint> {
+    let modified = tokio::fs::metadata(path)
+        .await
+        .and_then(|m| m.modified())
+        .ok();
+
+    let data = tokio::fs::read(path).await?;
+
+    Ok(FileFingerprint::new(&data, modified))
+}
+
+/// Returns `None` if the file no longer exists.
+async fn read_hash(path: &Path) -> Result<Option<u64>> {
+    match tokio::fs::read(path).await {
+        Ok(data) => Ok(Some(hash_data(&data))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn hash_data(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    data.hash(&mut hasher);
+
+    hasher.finish()
+}
@@ -1,86 +1,117 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use chrono::Local;
-use tracing::info;
+use tokio::io::AsyncWriteExt;
+use tracing::{error, info};
+use uuid::Uuid;
 
 use bl3_save_edit_core::bl3_profile::Bl3Profile;
 use bl3_save_edit_core::bl3_save::Bl3Save;
 use bl3_save_edit_core::file_helper::Bl3FileType;
 
-use crate::commands::interaction::choose_save_directory;
+use crate::commands::interaction::choose_save_directory::LoadedFiles;
+use crate::commands::interaction::file_fingerprint::{
+    FileFingerprint, FileFingerprints, OverwriteCheck,
+};
+use crate::commands::interaction::{backups, choose_save_directory, file_fingerprint};
+use crate::config::BackupRetention;
 use crate::state_mappers;
 
+// Milliseconds keep backups of the same file that are made in the same second apart
+pub const BACKUP_TIME_FORMAT: &str = "%d-%m-%Y_%H.%M.%S%.3f";
+
+/// A file that was just written and where it was written to.
+#[derive(Debug, Clone)]
+pub struct SavedFile<T> {
+    pub file: T,
+    pub output_file: PathBuf,
+    /// Other files in the saves folder were written too, such as when guardian data was added to
+    /// every save.
+    pub other_files_changed: bool,
+}
+
+impl<T> SavedFile<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> SavedFile<U> {
+        SavedFile {
+            file: f(self.file),
+            output_file: self.output_file,
+            other_files_changed: self.other_files_changed,
+        }
+    }
+}
+
+/// What has to be updated in the list of files after saving.
+#[derive(Debug, Clone)]
+pub enum FilesAfterSave {
+    /// Only the saved file changed, it has no fingerprint if it was saved outside the saves folder.
+    Saved(Bl3FileType, Option<FileFingerprint>),
+    /// Other files changed too so the saves folder was read again.
+    Reloaded(LoadedFiles<Bl3FileType>),
+}
+
 pub async fn save_file(
     backup_dir: PathBuf,
+    backup_retention: BackupRetention,
     output_file: PathBuf,
     output: Vec<u8>,
     existing_save: Bl3Save,
     new_save: Bl3Save,
-) -> Result<Bl3Save> {
-    info!(
-        "Making a backup of existing save: {}",
-        existing_save.file_name
-    );
+    overwrite_check: OverwriteCheck,
+) -> Result<SavedFile<Bl3Save>> {
+    overwrite_check.ensure(&output_file).await?;
 
-    let current_time = Local::now().format("%d-%m-%Y_%H.%M.%S");
-
-    let backup_name = format!(
-        "{}_{}-{}.sav",
-        existing_save.character_data.player_class(),
-        existing_save
-            .character_data
-            .character
-            .preferred_character_name,
-        current_time
-    );
-
-    let backup_name = sanitize_filename::sanitize(backup_name);
-
-    let (existing_save_output, _) = existing_save.as_bytes()?;
-
-    tokio::fs::write(backup_dir.join(backup_name), existing_save_output).await?;
+    backup_save(&backup_dir, backup_retention, &existing_save).await?;
 
     info!("Saving file: {}", new_save.file_name);
 
-    tokio::fs::write(output_file, output).await?;
+    let header_type = new_save.header_type;
+    let payload_format = new_save.payload_format;
+
+    write_file_atomically(&output_file, output, move |path, data| {
+        Bl3Save::verify_bytes(path, data, header_type, payload_format)
+    })
+    .await?;
 
-    Ok(new_save)
+    Ok(SavedFile {
+        file: new_save,
+        output_file,
+        other_files_changed: false,
+    })
 }
 
 pub async fn save_profile(
     backup_dir: PathBuf,
+    backup_retention: BackupRetention,
     saves_dir: PathBuf,
     output_file: PathBuf,
     output: Vec<u8>,
     existing_profile: Bl3Profile,
     new_profile: Bl3Profile,
-    guardian_data_injection_required: bool,
-) -> Result<Bl3Profile> {
-    info!(
-        "Making a backup of existing profile: {}",
-        existing_profile.file_name
-    );
+    overwrite_check: OverwriteCheck,
+    guardian_data_injection: Option<GuardianDataInjection>,
+) -> Result<SavedFile<Bl3Profile>> {
+    overwrite_check.ensure(&output_file).await?;
 
-    let current_time = Local::now().format("%d-%m-%Y_%H.%M.%S");
+    backup_profile(&backup_dir, backup_retention, &existing_profile).await?;
 
-    let backup_name = format!(
-        "{}-{}.sav",
-        existing_profile.file_name.replace(".sav", ""),
-        current_time
-    );
-
-    let backup_name = sanitize_filename::sanitize(backup_name);
-
-    let (existing_profile_output, _) = existing_profile.as_bytes()?;
+    info!("Saving profile: {}", new_profile.file_name);
 
-    tokio::fs::write(&backup_dir.join(backup_name), existing_profile_output).await?;
+    let header_type = new_profile.header_type;
+    let payload_format = new_profile.payload_format;
 
-    info!("Saving profile: {}", new_profile.file_name);
+    write_file_atomically(&output_file, output, move |path, data| {
+        Bl3Profile::verify_bytes(path, data, header_type, payload_format)
+    })
+    .await?;
 
-    tokio::fs::write(output_file, output).await?;
+    let other_files_changed = guardian_data_injection.is_some();
 
-    if guardian_data_injection_required {
+    if let Some(GuardianDataInjection {
+        saves,
+        file_fingerprints,
+    }) = guardian_data_injection
+    {
         let guardian_rank = new_profile.profile_data.guardian_rank();
 
         let guardian_tokens = new_profile.profile_data.guardian_tokens();
@@ -89,7 +120,10 @@ pub async fn save_profile(
 
         state_mappers::inject_guardian_data_into_saves(
             backup_dir,
+            backup_retention,
             saves_dir,
+            saves,
+            file_fingerprints,
             guardian_rank,
             guardian_tokens,
             guardian_rewards,
@@ -97,14 +131,240 @@ pub async fn save_profile(
         .await?;
     }
 
-    Ok(new_profile)
+    Ok(SavedFile {
+        file: new_profile,
+        output_file,
+        other_files_changed,
+    })
+}
+
+/// The saves that the profile's guardian data is added to once it's saved, each save is only
+/// overwritten if it hasn't changed since its fingerprint was taken.
+#[derive(Debug, Clone)]
+pub struct GuardianDataInjection {
+    pub saves: Vec<Bl3FileType>,
+    pub file_fingerprints: FileFingerprints,
+}
+
+#[derive(Debug)]
+pub enum AtomicWriteError {
+    /// Nothing was changed as the new file could not be written.
+    Write(std::io::Error),
+    /// Nothing was changed as the new file that was written could not be read again.
+    Validation(anyhow::Error),
+    /// The new file was written and checked but it could not replace the existing file, it is
+    /// left at `temp_file` so that it can be recovered.
+    Replace {
+        temp_file: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl std::fmt::Display for AtomicWriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AtomicWriteError::Write(e) => write!(
+                f,
+                "failed to write the new file, your existing file was not changed - {}",
+                e
+            ),
+            AtomicWriteError::Validation(e) => write!(
+                f,
+                "the new file could not be read back after writing it, your existing file was not changed - {}",
+                e
+            ),
+            AtomicWriteError::Replace { temp_file, error } => write!(
+                f,
+                "the new file was written to {} but it could not replace your existing file - {}",
+                temp_file.display(),
+                error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AtomicWriteError {}
+
+/// Writes `output` to a temporary file next to `output_file`, checks that it can be read using
+/// `validate` and then renames it over `output_file`. If anything goes wrong before the rename
+/// the existing file is never touched.
+pub async fn write_file_atomically<F>(
+    output_file: &Path,
+    output: Vec<u8>,
+    validate: F,
+) -> std::result::Result<(), AtomicWriteError>
+where
+    F: FnOnce(&Path, &[u8]) -> Result<()> + Send + 'static,
+{
+    let file_name = output_file
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let temp_file = output_file.with_file_name(format!(".{}.{}.tmp", file_name, Uuid::new_v4()));
+
+    let remove_temp_file = |temp_file: PathBuf| async move {
+        if let Err(e) = tokio::fs::remove_file(&temp_file).await {
+            error!(
+                "Failed to remove temporary file: {} - {}",
+                temp_file.display(),
+                e
+            );
+        }
+    };
+
+    if let Err(e) = write_and_sync(&temp_file, &output).await {
+        remove_temp_file(temp_file).await;
+
+        return Err(AtomicWriteError::Write(e));
+    }
+
+    // Read what actually ended up on disk rather than trusting what we meant to write
+    let validation = match tokio::fs::read(&temp_file).await {
+        Ok(written) => {
+            let output_file = output_file.to_path_buf();
+
+            tokio_rayon::spawn(move || validate(&output_file, &written)).await
+        }
+        Err(e) => Err(anyhow::Error::new(e)),
+    };
+
+    if let Err(e) = validation {
+        remove_temp_file(temp_file).await;
+
+        return Err(AtomicWriteError::Validation(e));
+    }
+
+    if let Err(error) = tokio::fs::rename(&temp_file, output_file).await {
+        return Err(AtomicWriteError::Replace { temp_file, error });
+    }
+
+    // Make sure the rename itself is on disk, not every platform lets us open a folder
+    #[cfg(not(target_os = "windows"))]
+    if let Some(output_dir) = output_file.parent() {
+        if let Ok(dir_handle) = tokio::fs::File::open(output_dir).await {
+            if let Err(e) = dir_handle.sync_all().await {
+                error!("Failed to sync folder: {} - {}", output_dir.display(), e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn write_and_sync(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut file = tokio::fs::File::create(path).await?;
+
+    file.write_all(data).await?;
+    file.sync_all().await
 }
 
+pub async fn backup_save(
+    backup_dir: &Path,
+    backup_retention: BackupRetention,
+    existing_save: &Bl3Save,
+) -> Result<()> {
+    info!(
+        "Making a backup of existing save: {}",
+        existing_save.file_name
+    );
+
+    let current_time = Local::now().format(BACKUP_TIME_FORMAT);
+
+    // Backups are grouped by the save's file name, the class and character name after the time
+    // only make the backup easier to find
+    let backup_name = format!(
+        "{}-{}-{}_{}.{}",
+        Path::new(&existing_save.file_name)
+            .file_stem()
+            .map(|s| s.to_string_lossy())
+            .unwrap_or_default(),
+        current_time,
+        existing_save.character_data.player_class(),
+        existing_save
+            .character_data
+            .character
+            .preferred_character_name,
+        existing_save.payload_format.extension()
+    );
+
+    let backup_name = sanitize_filename::sanitize(backup_name);
+
+    let (existing_save_output, _) = existing_save.as_bytes()?;
+
+    backups::write_backup(
+        backup_dir,
+        backup_retention,
+        backup_name,
+        existing_save_output,
+    )
+    .await
+}
+
+pub async fn backup_profile(
+    backup_dir: &Path,
+    backup_retention: BackupRetention,
+    existing_profile: &Bl3Profile,
+) -> Result<()> {
+    info!(
+        "Making a backup of existing profile: {}",
+        existing_profile.file_name
+    );
+
+    let current_time = Local::now().format(BACKUP_TIME_FORMAT);
+
+    let backup_name = format!(
+        "{}-{}.{}",
+        Path::new(&existing_profile.file_name)
+            .file_stem()
+            .map(|s| s.to_string_lossy())
+            .unwrap_or_default(),
+        current_time,
+        existing_profile.payload_format.extension()
+    );
+
+    let backup_name = sanitize_filename::sanitize(backup_name);
+
+    let (existing_profile_output, _) = existing_profile.as_bytes()?;
+
+    backups::write_backup(
+        backup_dir,
+        backup_retention,
+        backup_name,
+        existing_profile_output,
+    )
+    .await
+}
+
+/// Only the saved file is fingerprinted again unless other files were written too, reading the
+/// whole saves folder again is slow when there are a lot of big saves.
 pub async fn load_files_after_save(
     saves_dir: PathBuf,
-    file_saved: Bl3FileType,
-) -> Result<(Bl3FileType, Vec<Bl3FileType>)> {
-    let (_, all_files) = choose_save_directory::load_files_in_directory(saves_dir).await?;
+    saved_file: SavedFile<Bl3FileType>,
+) -> Result<FilesAfterSave> {
+    let SavedFile {
+        file,
+        output_file,
+        other_files_changed,
+    } = saved_file;
+
+    if other_files_changed {
+        let (_, all_files, unreadable_files, file_fingerprints) =
+            choose_save_directory::load_files_in_directory(saves_dir).await?;
+
+        return Ok(FilesAfterSave::Reloaded((
+            file,
+            all_files,
+            unreadable_files,
+            file_fingerprints,
+        )));
+    }
+
+    let file_fingerprint = if output_file.parent() == Some(saves_dir.as_path()) {
+        Some(file_fingerprint::fingerprint(&output_file).await?)
+    } else {
+        None
+    };
 
-    Ok((file_saved, all_files))
+    Ok(FilesAfterSave::Saved(file, file_fingerprint))
 }
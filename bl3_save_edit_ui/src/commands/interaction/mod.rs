@@ -2,9 +2,15 @@ use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 
+pub mod backups;
 pub mod choose_save_directory;
+pub mod convert_saves;
+pub mod duplicate_items;
+pub mod file_fingerprint;
 pub mod file_save;
 pub mod manage_save;
+pub mod save_slots;
+pub mod saves_dir_watch;
 pub mod settings;
 
 #[cfg(not(target_os = "macos"))]
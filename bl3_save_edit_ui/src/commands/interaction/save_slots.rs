@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use tracing::info;
+
+use bl3_save_edit_core::bl3_save::Bl3Save;
+
+use crate::commands::interaction::choose_save_directory::LoadedFiles;
+use crate::commands::interaction::file_save::write_file_atomically;
+use crate::commands::interaction::manage_save::general::generate_random_guid;
+use crate::commands::interaction::{choose_save_directory, file_save};
+use crate::config::BackupRetention;
+
+/// What was done to the saves folder, `file_name` is the file to select afterwards if any.
+#[derive(Debug, Clone)]
+pub struct SlotChange {
+    pub summary: String,
+    pub file_name: Option<String>,
+}
+
+/// Copies `save` into the lowest free slot with a new GUID so that the game sees it as another
+/// character.
+pub async fn clone_save(
+    saves_dir: PathBuf,
+    save: Bl3Save,
+    used_slots: Vec<u32>,
+) -> Result<LoadedFiles<SlotChange>> {
+    let save_game_id = next_free_slot(&saves_dir, &save, &used_slots);
+
+    let mut new_save = save.with_slot(save_game_id);
+
+    new_save.character_data.character.save_game_guid = generate_random_guid();
+
+    info!("Cloning save: {} to {}", save.file_name, new_save.file_name);
+
+    write_save(&saves_dir, &new_save).await?;
+
+    let summary = format!(
+        "Cloned {} to slot {} ({}).",
+        save.character_data.character.preferred_character_name, save_game_id, new_save.file_name
+    );
+
+    reload(saves_dir, summary, Some(new_save.file_name)).await
+}
+
+/// Moves `save` to slot `save_game_id`, a backup is made before the old file is removed.
+pub async fn renumber_save(
+    backup_dir: PathBuf,
+    backup_retention: BackupRetention,
+    saves_dir: PathBuf,
+    save: Bl3Save,
+    save_game_id: u32,
+    used_slots: Vec<u32>,
+) -> Result<LoadedFiles<SlotChange>> {
+    if save_game_id == 0 {
+        bail!("Slot must be 1 or greater.");
+    }
+
+    if save_game_id == save.character_data.character.save_game_id {
+        bail!("{} is already in slot {}.", save.file_name, save_game_id);
+    }
+
+    if used_slots.contains(&save_game_id) {
+        bail!(
+            "Slot {} is already used by another character.",
+            save_game_id
+        );
+    }
+
+    let new_save = save.with_slot(save_game_id);
+
+    if saves_dir.join(&new_save.file_name).exists() {
+        bail!("{} already exists in the saves folder.", new_save.file_name);
+    }
+
+    file_save::backup_save(&backup_dir, backup_retention, &save).await?;
+
+    info!("Moving save: {} to {}", save.file_name, new_save.file_name);
+
+    write_save(&saves_dir, &new_save).await?;
+
+    tokio::fs::remove_file(saves_dir.join(&save.file_name)).await?;
+
+    let summary = format!(
+        "Moved {} to slot {} ({}).",
+        save.character_data.character.preferred_character_name, save_game_id, new_save.file_name
+    );
+
+    reload(saves_dir, summary, Some(new_save.file_name)).await
+}
+
+/// Removes `save` from the saves folder, it is backed up first so that it can be restored from
+/// the backups folder.
+pub async fn delete_save(
+    backup_dir: PathBuf,
+    backup_retention: BackupRetention,
+    saves_dir: PathBuf,
+    save: Bl3Save,
+) -> Result<LoadedFiles<SlotChange>> {
+    file_save::backup_save(&backup_dir, backup_retention, &save).await?;
+
+    info!("Deleting save: {}", save.file_name);
+
+    tokio::fs::remove_file(saves_dir.join(&save.file_name)).await?;
+
+    let summary = format!(
+        "Deleted {} ({}), a backup of it was made in the backups folder.",
+        save.character_data.character.preferred_character_name, save.file_name
+    );
+
+    reload(saves_dir, summary, None).await
+}
+
+/// The lowest slot that isn't used by another save and wouldn't overwrite a file, the game itself
+/// starts counting from 1.
+fn next_free_slot(saves_dir: &Path, save: &Bl3Save, used_slots: &[u32]) -> u32 {
+    (1..)
+        .find(|id| {
+            !used_slots.contains(id) && !saves_dir.join(save.with_slot(*id).file_name).exists()
+        })
+        .expect("ran out of save slots")
+}
+
+async fn write_save(saves_dir: &Path, save: &Bl3Save) -> Result<()> {
+    let (output, _) = save.as_bytes()?;
+
+    let header_type = save.header_type;
+    let payload_format = save.payload_format;
+
+    write_file_atomically(
+        &saves_dir.join(&save.file_name),
+        output,
+        move |path, data| Bl3Save::verify_bytes(path, data, header_type, payload_format),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn reload(
+    saves_dir: PathBuf,
+    summary: String,
+    file_name: Option<String>,
+) -> Result<LoadedFiles<SlotChange>> {
+    // Deleting the last save can leave nothing to load
+    let (_, all_files, unreadable_files, file_fingerprints) =
+        choose_save_directory::read_files_in_directory(saves_dir).await?;
+
+    Ok((
+        SlotChange { summary, file_name },
+        all_files,
+        unreadable_files,
+        file_fingerprints,
+    ))
+}
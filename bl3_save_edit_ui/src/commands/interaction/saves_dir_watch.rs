@@ -0,0 +1,95 @@
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::channel::mpsc;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::warn;
+
+use crate::commands::interaction::choose_save_directory;
+
+// The game writes a file in a few steps, changes this close together are checked once
+const SETTLE_TIME: Duration = Duration::from_millis(500);
+
+// Only used when the saves folder can't be watched
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Outputs whenever a save file in `dir` may have been added, removed or changed by another
+/// program. The folder is polled instead if it can't be watched.
+#[derive(Debug, Clone)]
+pub struct SavesDirWatch {
+    pub dir: PathBuf,
+}
+
+impl<H, I> iced_native::subscription::Recipe<H, I> for SavesDirWatch
+where
+    H: std::hash::Hasher,
+{
+    type Output = ();
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.dir.hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Self::Output> {
+        match watch(&self.dir) {
+            Ok((watcher, changes)) => {
+                stream::unfold((watcher, changes), |(watcher, mut changes)| async move {
+                    changes.next().await?;
+
+                    tokio::time::sleep(SETTLE_TIME).await;
+
+                    while let Ok(Some(_)) = changes.try_next() {}
+
+                    Some(((), (watcher, changes)))
+                })
+                .boxed()
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to watch saves folder: {}, checking it every {} seconds instead - {}",
+                    self.dir.display(),
+                    POLL_INTERVAL.as_secs(),
+                    e
+                );
+
+                stream::unfold((), |_| async {
+                    tokio::time::sleep(POLL_INTERVAL).await;
+
+                    Some(((), ()))
+                })
+                .boxed()
+            }
+        }
+    }
+}
+
+/// The watcher has to be kept alive for as long as changes are received.
+fn watch(dir: &Path) -> Result<(RecommendedWatcher, mpsc::UnboundedReceiver<()>)> {
+    let (sender, changes) = mpsc::unbounded();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        match res {
+            // Reading files, such as when we fingerprint them, is reported too
+            Ok(event) if matches!(event.kind, EventKind::Access(_)) => (),
+            Ok(event) => {
+                if event
+                    .paths
+                    .iter()
+                    .any(|p| choose_save_directory::is_save_file(p))
+                {
+                    let _ = sender.unbounded_send(());
+                }
+            }
+            Err(e) => warn!("Failed to watch saves folder: {}", e),
+        }
+    })?;
+
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    Ok((watcher, changes))
+}
@@ -24,6 +24,37 @@ pub struct Bl3Config {
     saves_dir: PathBuf,
     #[serde(default = "default_scale_factor")]
     ui_scale_factor: f64,
+    #[serde(default)]
+    backup_retention: BackupRetention,
+}
+
+/// Controls which backups are deleted after a new backup is made, backups are grouped by the file
+/// they were made from.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupRetention {
+    /// Nothing is deleted unless this is turned on in the settings.
+    pub delete_old_backups: bool,
+    /// The most recent backups that are always kept.
+    pub keep_last: usize,
+    /// Also keep the latest backup from each of this many days.
+    pub keep_daily: usize,
+    /// Also keep the latest backup from each of this many weeks.
+    pub keep_weekly: usize,
+    /// The oldest backups are deleted until all backups fit in this size, 0 means no limit.
+    pub max_total_size_mb: u64,
+}
+
+impl std::default::Default for BackupRetention {
+    fn default() -> Self {
+        Self {
+            delete_old_backups: false,
+            keep_last: 10,
+            keep_daily: 7,
+            keep_weekly: 4,
+            max_total_size_mb: 1024,
+        }
+    }
 }
 
 fn default_scale_factor() -> f64 {
@@ -69,6 +100,7 @@ impl Bl3Config {
                 backup_dir,
                 saves_dir: Default::default(),
                 ui_scale_factor: default_scale_factor(),
+                backup_retention: BackupRetention::default(),
             }
         }
     }
@@ -108,6 +140,14 @@ impl Bl3Config {
         self.backup_dir = dir;
     }
 
+    pub fn backup_retention(&self) -> BackupRetention {
+        self.backup_retention
+    }
+
+    pub fn set_backup_retention(&mut self, backup_retention: BackupRetention) {
+        self.backup_retention = backup_retention;
+    }
+
     pub fn saves_dir(&self) -> &PathBuf {
         &self.saves_dir
     }
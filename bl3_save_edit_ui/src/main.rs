@@ -89,6 +89,8 @@ fn main() -> Result<()> {
         },
         antialiasing: true,
         text_multithreading: true,
+        // Bl3Application::should_exit decides when to close so that unsaved changes aren't lost
+        exit_on_close_request: false,
         ..Settings::default()
     };
 
@@ -1,6 +1,9 @@
 //Save/Profile Editor
+pub const BACKUPS: &[u8] = include_bytes!("../../resources/svg/backups.svg");
+pub const DUPLICATES: &[u8] = include_bytes!("../../resources/svg/duplicates.svg");
 pub const GENERAL: &[u8] = include_bytes!("../../resources/svg/general.svg");
 pub const REFRESH: &[u8] = include_bytes!("../../resources/svg/refresh.svg");
+pub const SEARCH: &[u8] = include_bytes!("../../resources/svg/search.svg");
 pub const SETTINGS: &[u8] = include_bytes!("../../resources/svg/settings.svg");
 #[allow(unused)]
 pub const ARROW_UP: &[u8] = include_bytes!("../../resources/svg/arrow_up.svg");
@@ -0,0 +1,326 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tracing::error;
+
+use bl3_save_edit_core::bl3_profile::Bl3Profile;
+use bl3_save_edit_core::bl3_save::Bl3Save;
+use bl3_save_edit_core::file_helper::Bl3FileType;
+use bl3_save_edit_core::item_index::ItemLocation;
+
+use crate::bl3_ui::{Bl3Application, Bl3Message, InteractionMessage};
+use crate::state_mappers::{manage_profile, manage_save};
+use crate::views::item_editor::ItemEditorInteractionMessage;
+use crate::views::manage_profile::bank::ProfileBankInteractionMessage;
+use crate::views::manage_profile::ManageProfileInteractionMessage;
+use crate::views::manage_save::inventory::SaveInventoryInteractionMessage;
+use crate::views::manage_save::ManageSaveInteractionMessage;
+
+// Every entry is a copy of the whole file
+const MAX_HISTORY_LEN: usize = 50;
+
+// Edits of the same kind this close together, such as typing into a text box, are undone in one go
+const MERGE_EDITS_WITHIN: Duration = Duration::from_millis(1000);
+
+/// What an edit changes, worked out from the message that makes it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum EditKind {
+    /// Changing a value outside of the item editor, such as typing into a text box.
+    Field(u64),
+    /// Changing the item at this position in the editor.
+    Item(
+        ItemLocation,
+        usize,
+        mem::Discriminant<ItemEditorInteractionMessage>,
+    ),
+    /// Adding or removing items, these are always undone on their own.
+    Items,
+}
+
+/// An edit that is still going on, it's added to the history once an edit of another kind is
+/// made or the editor is undone.
+#[derive(Debug)]
+struct PendingEdit {
+    kind: EditKind,
+    before: EditSnapshot,
+    last_edited: Instant,
+}
+
+/// The editor mapped back to the file it is editing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditSnapshot {
+    Save(Bl3Save),
+    Profile(Bl3Profile),
+}
+
+/// Snapshots of the editor from before each edit. Snapshots are only compared when an edit is
+/// added so an entry may be the same as the editor is now, these are skipped over when undoing.
+#[derive(Debug, Default)]
+pub struct EditHistory {
+    undo_stack: Vec<EditSnapshot>,
+    redo_stack: Vec<EditSnapshot>,
+    pending_edit: Option<PendingEdit>,
+    // What the editor was set to by the last undo or redo, redo is only possible while the editor
+    // hasn't been changed since
+    last_restored: Option<EditSnapshot>,
+}
+
+impl EditHistory {
+    /// Adds the pending edit to the history if the editor has changed since it started.
+    fn finish_pending_edit(&mut self, current: &EditSnapshot) {
+        let before = match self.pending_edit.take() {
+            Some(pending_edit) => pending_edit.before,
+            None => return,
+        };
+
+        if before == *current || self.undo_stack.last() == Some(&before) {
+            return;
+        }
+
+        self.undo_stack.push(before);
+
+        if self.undo_stack.len() > MAX_HISTORY_LEN {
+            self.undo_stack.remove(0);
+        }
+    }
+}
+
+fn field_kind<T, M>(tab_message: &T, message: &M) -> EditKind {
+    let mut hasher = DefaultHasher::new();
+
+    mem::discriminant(tab_message).hash(&mut hasher);
+    mem::discriminant(message).hash(&mut hasher);
+
+    EditKind::Field(hasher.finish())
+}
+
+/// What `message` would edit, `None` if it can't change the file such as switching tabs,
+/// selecting an item or searching.
+fn edit_kind(main_state: &Bl3Application, message: &Bl3Message) -> Option<EditKind> {
+    match message {
+        Bl3Message::Interaction(InteractionMessage::ManageSaveInteraction(msg)) => match msg {
+            ManageSaveInteractionMessage::TabBar(_)
+            | ManageSaveInteractionMessage::SaveFilePressed => None,
+            ManageSaveInteractionMessage::General(m) => Some(field_kind(msg, m)),
+            ManageSaveInteractionMessage::Character(m) => Some(field_kind(msg, m)),
+            ManageSaveInteractionMessage::Currency(m) => Some(field_kind(msg, m)),
+            ManageSaveInteractionMessage::Vehicle(m) => Some(field_kind(msg, m)),
+            ManageSaveInteractionMessage::Inventory(SaveInventoryInteractionMessage::Editor(m)) => {
+                item_edit_kind(main_state, ItemLocation::Inventory, m)
+            }
+        },
+        Bl3Message::Interaction(InteractionMessage::ManageProfileInteraction(msg)) => match msg {
+            ManageProfileInteractionMessage::TabBar(_)
+            | ManageProfileInteractionMessage::SaveProfilePressed => None,
+            ManageProfileInteractionMessage::General(m) => Some(field_kind(msg, m)),
+            ManageProfileInteractionMessage::Profile(m) => Some(field_kind(msg, m)),
+            ManageProfileInteractionMessage::Keys(m) => Some(field_kind(msg, m)),
+            ManageProfileInteractionMessage::Bank(ProfileBankInteractionMessage::Editor(m)) => {
+                item_edit_kind(main_state, ItemLocation::Bank, m)
+            }
+        },
+        _ => None,
+    }
+}
+
+fn item_edit_kind(
+    main_state: &Bl3Application,
+    location: ItemLocation,
+    message: &ItemEditorInteractionMessage,
+) -> Option<EditKind> {
+    match message {
+        ItemEditorInteractionMessage::ItemListLootlemonImportPressed(_)
+        | ItemEditorInteractionMessage::CreateItemPressed
+        | ItemEditorInteractionMessage::ImportItemFromSerialPressed
+        | ItemEditorInteractionMessage::SetAllItemLevelsPressed
+        | ItemEditorInteractionMessage::DeleteItem(_)
+        | ItemEditorInteractionMessage::DuplicateItem(_) => Some(EditKind::Items),
+        ItemEditorInteractionMessage::ReorderCurrentPartsMoveUpPressed
+        | ItemEditorInteractionMessage::ReorderCurrentPartsMoveDownPressed
+        | ItemEditorInteractionMessage::ReorderCurrentPartsMoveTopPressed
+        | ItemEditorInteractionMessage::ReorderCurrentPartsMoveBottomPressed
+        | ItemEditorInteractionMessage::AvailablePartPressed(_)
+        | ItemEditorInteractionMessage::AvailableAnointmentPressed(_)
+        | ItemEditorInteractionMessage::CurrentPartPressed(..)
+        | ItemEditorInteractionMessage::CurrentAnointmentPressed(_)
+        | ItemEditorInteractionMessage::ItemLevel(_)
+        | ItemEditorInteractionMessage::BalanceInputSelected(_)
+        | ItemEditorInteractionMessage::InvDataInputSelected(_)
+        | ItemEditorInteractionMessage::ManufacturerInputSelected(_) => Some(EditKind::Item(
+            location,
+            selected_item(main_state, location),
+            mem::discriminant(message),
+        )),
+        _ => None,
+    }
+}
+
+/// Called before `message` is handled. The editor is only mapped back to the file when an edit of
+/// a new kind starts, so typing into a text box doesn't re-encode the file on every key press.
+pub fn before_message(main_state: &mut Bl3Application, message: &Bl3Message) {
+    let kind = match edit_kind(main_state, message) {
+        Some(kind) => kind,
+        None => return,
+    };
+
+    let now = Instant::now();
+
+    if let Some(pending_edit) = &mut main_state.edit_history.pending_edit {
+        if kind != EditKind::Items
+            && pending_edit.kind == kind
+            && now.duration_since(pending_edit.last_edited) < MERGE_EDITS_WITHIN
+        {
+            pending_edit.last_edited = now;
+
+            return;
+        }
+    }
+
+    // This is also what the editor looks like after the previous edit
+    let before = match snapshot_editor(main_state) {
+        Ok(before) => before,
+        Err(e) => {
+            error!("Failed to record edit for undo: {}", e);
+
+            main_state.edit_history.pending_edit = None;
+
+            return;
+        }
+    };
+
+    let edit_history = &mut main_state.edit_history;
+
+    edit_history.finish_pending_edit(&before);
+
+    edit_history.pending_edit = Some(PendingEdit {
+        kind,
+        before,
+        last_edited: now,
+    });
+}
+
+fn selected_item(main_state: &Bl3Application, location: ItemLocation) -> usize {
+    match location {
+        ItemLocation::Bank => {
+            main_state
+                .manage_profile_state
+                .profile_view_state
+                .bank_state
+                .item_editor_state
+                .selected_item_index
+        }
+        _ => {
+            main_state
+                .manage_save_state
+                .save_view_state
+                .inventory_state
+                .item_editor_state
+                .selected_item_index
+        }
+    }
+}
+
+pub fn undo(main_state: &mut Bl3Application) -> Result<bool> {
+    let current = snapshot_editor(main_state)?;
+
+    let edit_history = &mut main_state.edit_history;
+
+    edit_history.finish_pending_edit(&current);
+
+    if edit_history.last_restored.as_ref() != Some(&current) {
+        edit_history.redo_stack.clear();
+    }
+
+    let previous = loop {
+        match edit_history.undo_stack.pop() {
+            Some(previous) if previous == current => continue,
+            previous => break previous,
+        }
+    };
+
+    match previous {
+        Some(previous) => {
+            edit_history.redo_stack.push(current);
+
+            restore_snapshot(main_state, previous)?;
+
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+pub fn redo(main_state: &mut Bl3Application) -> Result<bool> {
+    let current = snapshot_editor(main_state)?;
+
+    let edit_history = &mut main_state.edit_history;
+
+    edit_history.finish_pending_edit(&current);
+
+    // Anything that could be redone was thrown away by editing after the last undo
+    if edit_history.last_restored.as_ref() != Some(&current) {
+        edit_history.redo_stack.clear();
+
+        return Ok(false);
+    }
+
+    match edit_history.redo_stack.pop() {
+        Some(next) => {
+            edit_history.undo_stack.push(current);
+
+            restore_snapshot(main_state, next)?;
+
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn snapshot_editor(main_state: &mut Bl3Application) -> Result<EditSnapshot> {
+    match *main_state.loaded_files_selected {
+        Bl3FileType::PcSave(_) | Bl3FileType::Ps4Save(_) => {
+            let mut save = main_state.manage_save_state.current_file.clone();
+
+            manage_save::map_all_states_to_save(&mut main_state.manage_save_state, &mut save)?;
+
+            Ok(EditSnapshot::Save(save))
+        }
+        Bl3FileType::PcProfile(_) | Bl3FileType::Ps4Profile(_) => {
+            let mut profile = main_state.manage_profile_state.current_file.clone();
+
+            manage_profile::map_all_states_to_profile(
+                &mut main_state.manage_profile_state,
+                &mut profile,
+            )?;
+
+            Ok(EditSnapshot::Profile(profile))
+        }
+    }
+}
+
+/// Maps `snapshot` to the editor. It also becomes `current_file` as the items in the editor refer
+/// to the items in `current_file` by their index.
+fn restore_snapshot(main_state: &mut Bl3Application, snapshot: EditSnapshot) -> Result<()> {
+    match snapshot {
+        EditSnapshot::Save(save) => {
+            main_state.manage_save_state.current_file = save;
+
+            manage_save::map_save_to_all_states(&mut main_state.manage_save_state)?;
+        }
+        EditSnapshot::Profile(profile) => {
+            main_state.manage_profile_state.current_file = profile;
+
+            manage_profile::map_profile_to_all_states(&mut main_state.manage_profile_state)?;
+        }
+    }
+
+    // Take the snapshot from the editor rather than using the one that was restored as mapping it
+    // may not give back exactly the same file
+    main_state.edit_history.pending_edit = None;
+    main_state.edit_history.last_restored = Some(snapshot_editor(main_state)?);
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use rayon::slice::ParallelSliceMut;
-use tracing::info;
+use tracing::{debug, info};
 
 use bl3_save_edit_core::bl3_profile::Bl3Profile;
 
@@ -95,7 +95,7 @@ pub fn map_bank_state_to_profile(
 
                 profile.profile_data.replace_bank_item(i, edited_item)?;
             } else {
-                info!("Keeping existing bank item at index: {}", i);
+                debug!("Keeping existing bank item at index: {}", i);
             }
         } else {
             // Otherwise insert our new item in this slot
@@ -14,6 +14,17 @@ pub fn map_profile_to_general_state(manage_profile_state: &mut ManageProfileStat
         .profile_view_state
         .general_state
         .profile_type_selected = profile.header_type;
+
+    manage_profile_state
+        .profile_view_state
+        .general_state
+        .payload_format_selected = profile.payload_format;
+
+    manage_profile_state
+        .profile_view_state
+        .general_state
+        .header_state
+        .map_header(profile.header());
 }
 
 pub fn map_general_state_to_profile(
@@ -30,4 +41,17 @@ pub fn map_general_state_to_profile(
         .profile_view_state
         .general_state
         .profile_type_selected;
+
+    profile.payload_format = manage_profile_state
+        .profile_view_state
+        .general_state
+        .payload_format_selected;
+
+    profile.set_header(
+        manage_profile_state
+            .profile_view_state
+            .general_state
+            .header_state
+            .header(),
+    );
 }
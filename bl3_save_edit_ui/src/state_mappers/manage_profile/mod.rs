@@ -10,6 +10,19 @@ pub mod general;
 pub mod keys;
 pub mod profile;
 
+/// Maps `current_file` to every tab of the profile editor.
+pub fn map_profile_to_all_states(manage_profile_state: &mut ManageProfileState) -> Result<()> {
+    manage_profile::general::map_profile_to_general_state(manage_profile_state);
+
+    manage_profile::profile::map_profile_to_profile_state(manage_profile_state);
+
+    manage_profile::keys::map_profile_to_keys_state(manage_profile_state);
+
+    manage_profile::bank::map_profile_to_bank_state(manage_profile_state)?;
+
+    Ok(())
+}
+
 pub fn map_all_states_to_profile(
     manage_profile_state: &mut ManageProfileState,
     current_file: &mut Bl3Profile,
@@ -20,6 +20,17 @@ pub fn map_save_to_general_state(manage_save_state: &mut ManageSaveState) {
         .save_view_state
         .general_state
         .save_type_selected = save.header_type;
+
+    manage_save_state
+        .save_view_state
+        .general_state
+        .payload_format_selected = save.payload_format;
+
+    manage_save_state
+        .save_view_state
+        .general_state
+        .header_state
+        .map_header(save.header());
 }
 
 pub fn map_general_state_to_save(manage_save_state: &mut ManageSaveState, save: &mut Bl3Save) {
@@ -42,4 +53,17 @@ pub fn map_general_state_to_save(manage_save_state: &mut ManageSaveState, save:
         .save_view_state
         .general_state
         .save_type_selected;
+
+    save.payload_format = manage_save_state
+        .save_view_state
+        .general_state
+        .payload_format_selected;
+
+    save.set_header(
+        manage_save_state
+            .save_view_state
+            .general_state
+            .header_state
+            .header(),
+    );
 }
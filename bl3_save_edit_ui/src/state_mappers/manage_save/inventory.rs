@@ -1,6 +1,6 @@
 use anyhow::Result;
 use rayon::slice::ParallelSliceMut;
-use tracing::info;
+use tracing::{debug, info};
 
 use bl3_save_edit_core::bl3_save::Bl3Save;
 
@@ -94,7 +94,7 @@ pub fn map_inventory_state_to_save(
                 save.character_data
                     .replace_inventory_item(i as i32, i, edited_item)?;
             } else {
-                info!("Keeping existing item at index: {}", i);
+                debug!("Keeping existing item at index: {}", i);
             }
         } else {
             // Otherwise insert our new item in this slot
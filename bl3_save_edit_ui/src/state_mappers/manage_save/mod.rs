@@ -11,6 +11,21 @@ pub mod general;
 pub mod inventory;
 pub mod vehicle;
 
+/// Maps `current_file` to every tab of the save editor.
+pub fn map_save_to_all_states(manage_save_state: &mut ManageSaveState) -> Result<()> {
+    manage_save::general::map_save_to_general_state(manage_save_state);
+
+    manage_save::character::map_save_to_character_state(manage_save_state);
+
+    manage_save::inventory::map_save_to_inventory_state(manage_save_state)?;
+
+    manage_save::currency::map_save_to_currency_state(manage_save_state);
+
+    manage_save::vehicle::map_save_to_vehicle_state(manage_save_state);
+
+    Ok(())
+}
+
 pub fn map_all_states_to_save(
     manage_save_state: &mut ManageSaveState,
     current_file: &mut Bl3Save,
@@ -1,38 +1,68 @@
 use std::mem;
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 
 use bl3_save_edit_core::bl3_profile::guardian_reward::GuardianRewardData;
+use bl3_save_edit_core::file_diff::FileDiff;
 use bl3_save_edit_core::file_helper::Bl3FileType;
+use bl3_save_edit_core::item_index::{IndexedItem, ItemLocation};
 
 use crate::bl3_ui::Bl3Application;
 use crate::bl3_ui::ViewState;
 use crate::commands::interaction;
-use crate::commands::interaction::choose_save_directory;
+use crate::commands::interaction::file_fingerprint::{FileFingerprints, OverwriteCheck};
+use crate::config::BackupRetention;
+use crate::state_mappers::edit_history::EditHistory;
 use crate::views::manage_profile::main::ProfileTabBarView;
 use crate::views::manage_profile::ManageProfileView;
 use crate::views::manage_save::main::SaveTabBarView;
 use crate::views::manage_save::ManageSaveView;
 
+pub mod edit_history;
 pub mod manage_profile;
 pub mod manage_save;
 
 pub fn map_loaded_file_to_state(main_state: &mut Bl3Application) -> Result<()> {
+    // Only the summary of a save is read when the folder is loaded, the rest is read when opening
+    if let Err(e) = main_state.loaded_files_selected.load_details() {
+        main_state.unselect_unopenable_file(format!("it can't be opened: {:#}", e));
+
+        return Err(e);
+    }
+
+    main_state.edit_history = EditHistory::default();
+
+    // A comparison is against an item from the file that was open before
+    main_state
+        .manage_save_state
+        .save_view_state
+        .inventory_state
+        .item_editor_state
+        .item_compare = None;
+
+    main_state
+        .manage_profile_state
+        .profile_view_state
+        .bank_state
+        .item_editor_state
+        .item_compare = None;
+
+    let copy_from_files = copy_from_files(main_state);
+
     match &*main_state.loaded_files_selected {
         Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
             //This file will be the one that gets modified when we press save.
             main_state.manage_save_state.current_file = save.clone();
 
-            manage_save::general::map_save_to_general_state(&mut main_state.manage_save_state);
-
-            manage_save::character::map_save_to_character_state(&mut main_state.manage_save_state);
+            manage_save::map_save_to_all_states(&mut main_state.manage_save_state)?;
 
-            manage_save::inventory::map_save_to_inventory_state(&mut main_state.manage_save_state)?;
-
-            manage_save::currency::map_save_to_currency_state(&mut main_state.manage_save_state);
-
-            manage_save::vehicle::map_save_to_vehicle_state(&mut main_state.manage_save_state);
+            main_state
+                .manage_save_state
+                .save_view_state
+                .general_state
+                .header_state
+                .copy_from_files = copy_from_files;
 
             if mem::discriminant(&main_state.view_state)
                 != mem::discriminant(&ViewState::ManageSave(ManageSaveView::TabBar(
@@ -46,17 +76,14 @@ pub fn map_loaded_file_to_state(main_state: &mut Bl3Application) -> Result<()> {
         Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
             main_state.manage_profile_state.current_file = profile.clone();
 
-            manage_profile::general::map_profile_to_general_state(
-                &mut main_state.manage_profile_state,
-            );
-
-            manage_profile::profile::map_profile_to_profile_state(
-                &mut main_state.manage_profile_state,
-            );
-
-            manage_profile::keys::map_profile_to_keys_state(&mut main_state.manage_profile_state);
+            manage_profile::map_profile_to_all_states(&mut main_state.manage_profile_state)?;
 
-            manage_profile::bank::map_profile_to_bank_state(&mut main_state.manage_profile_state)?;
+            main_state
+                .manage_profile_state
+                .profile_view_state
+                .general_state
+                .header_state
+                .copy_from_files = copy_from_files;
 
             if mem::discriminant(&main_state.view_state)
                 != mem::discriminant(&ViewState::ManageProfile(ManageProfileView::TabBar(
@@ -72,18 +99,168 @@ pub fn map_loaded_file_to_state(main_state: &mut Bl3Application) -> Result<()> {
     Ok(())
 }
 
+/// Updates the files a header can be copied from, for when more files have been loaded without
+/// mapping the selected file again.
+pub fn map_copy_from_files(main_state: &mut Bl3Application) {
+    let copy_from_files = copy_from_files(main_state);
+
+    main_state
+        .manage_save_state
+        .save_view_state
+        .general_state
+        .header_state
+        .copy_from_files = copy_from_files.clone();
+
+    main_state
+        .manage_profile_state
+        .profile_view_state
+        .general_state
+        .header_state
+        .copy_from_files = copy_from_files;
+}
+
+fn copy_from_files(main_state: &Bl3Application) -> Vec<String> {
+    main_state
+        .loaded_files
+        .iter()
+        .map(|f| f.filename().to_owned())
+        .filter(|f| f != main_state.loaded_files_selected.filename())
+        .collect()
+}
+
+/// Switches back to the editor for the currently selected file without mapping it again, so any
+/// unsaved changes are kept.
+pub fn return_to_editor(main_state: &mut Bl3Application) {
+    main_state.view_state = match *main_state.loaded_files_selected {
+        Bl3FileType::PcSave(_) | Bl3FileType::Ps4Save(_) => {
+            ViewState::ManageSave(ManageSaveView::TabBar(SaveTabBarView::General))
+        }
+        Bl3FileType::PcProfile(_) | Bl3FileType::Ps4Profile(_) => {
+            ViewState::ManageProfile(ManageProfileView::TabBar(ProfileTabBarView::General))
+        }
+    };
+}
+
+/// The changes that saving the editor would make to the selected file as it was loaded, `None` if
+/// saving would write the file exactly as it was loaded. The diff is only used to describe the
+/// changes, it can be empty when something changed that it doesn't list.
+pub fn unsaved_changes(main_state: &mut Bl3Application) -> Result<Option<FileDiff>> {
+    // Compare against the file that was loaded, `current_file` has deleted items removed and is
+    // replaced when undoing
+    match &*main_state.loaded_files_selected {
+        Bl3FileType::PcSave(loaded_save) | Bl3FileType::Ps4Save(loaded_save) => {
+            let mut current_file = main_state.manage_save_state.current_file.clone();
+
+            manage_save::map_all_states_to_save(
+                &mut main_state.manage_save_state,
+                &mut current_file,
+            )?;
+
+            if **loaded_save == current_file {
+                return Ok(None);
+            }
+
+            Ok(Some(loaded_save.diff(&current_file)))
+        }
+        Bl3FileType::PcProfile(loaded_profile) | Bl3FileType::Ps4Profile(loaded_profile) => {
+            let mut current_file = main_state.manage_profile_state.current_file.clone();
+
+            manage_profile::map_all_states_to_profile(
+                &mut main_state.manage_profile_state,
+                &mut current_file,
+            )?;
+
+            // Profiles are only equal by their data, the header is written too
+            if **loaded_profile == current_file
+                && loaded_profile.header() == current_file.header()
+                && loaded_profile.header_type == current_file.header_type
+                && loaded_profile.payload_format == current_file.payload_format
+            {
+                return Ok(None);
+            }
+
+            Ok(Some(loaded_profile.diff(&current_file)))
+        }
+    }
+}
+
+/// Describes `changes` for a prompt, with a line for changes the diff doesn't list.
+pub fn describe_unsaved_changes(changes: &FileDiff) -> Vec<String> {
+    if changes.is_empty() {
+        vec!["Changes that can't be listed here".to_owned()]
+    } else {
+        changes.summary()
+    }
+}
+
+/// Whether the editor has changes to the selected file that haven't been saved yet, if the editor
+/// can't be mapped back to the file then it is treated as changed.
+pub fn has_unsaved_changes(main_state: &mut Bl3Application) -> bool {
+    unsaved_changes(main_state)
+        .map(|changes| changes.is_some())
+        .unwrap_or(true)
+}
+
+pub fn jump_to_item(main_state: &mut Bl3Application, indexed_item: &IndexedItem) -> Result<()> {
+    if main_state.loaded_files_selected.filename() != indexed_item.file_name {
+        let file = main_state
+            .loaded_files
+            .iter()
+            .find(|f| f.filename() == indexed_item.file_name)
+            .cloned()
+            .with_context(|| format!("{} is no longer loaded", indexed_item.file_name))?;
+
+        main_state.loaded_files_selected = Box::new(file);
+
+        map_loaded_file_to_state(main_state)?;
+    }
+
+    match indexed_item.location {
+        ItemLocation::Inventory => {
+            main_state
+                .manage_save_state
+                .save_view_state
+                .inventory_state
+                .item_editor_state
+                .select_item(indexed_item.slot, &indexed_item.item)?;
+
+            main_state.view_state =
+                ViewState::ManageSave(ManageSaveView::TabBar(SaveTabBarView::Inventory));
+        }
+        ItemLocation::Bank => {
+            main_state
+                .manage_profile_state
+                .profile_view_state
+                .bank_state
+                .item_editor_state
+                .select_item(indexed_item.slot, &indexed_item.item)?;
+
+            main_state.view_state =
+                ViewState::ManageProfile(ManageProfileView::TabBar(ProfileTabBarView::Bank));
+        }
+        ItemLocation::GunRack | ItemLocation::LostLoot => {
+            bail!("{} items can not be edited", indexed_item.location)
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn inject_guardian_data_into_saves(
     backup_dir: PathBuf,
+    backup_retention: BackupRetention,
     saves_dir: PathBuf,
+    saves: Vec<Bl3FileType>,
+    file_fingerprints: FileFingerprints,
     guardian_rank: i32,
     guardian_tokens: i32,
     guardian_rewards: &[GuardianRewardData],
 ) -> Result<()> {
-    let (_, all_files) = choose_save_directory::load_files_in_directory(saves_dir.clone()).await?;
-
-    for file in all_files {
+    for file in saves {
         match file {
-            Bl3FileType::PcSave(mut s) | Bl3FileType::Ps4Save(mut s) => {
+            Bl3FileType::PcSave(existing_save) | Bl3FileType::Ps4Save(existing_save) => {
+                let mut s = existing_save.clone();
+
                 s.character_data
                     .set_guardian_rank(guardian_rank, Some(guardian_tokens));
 
@@ -95,14 +272,19 @@ pub async fn inject_guardian_data_into_saves(
 
                 let (output, new_save) = s.as_bytes()?;
 
-                let existing_save = s.clone();
+                let overwrite_check = file_fingerprints
+                    .get(&new_save.file_name)
+                    .copied()
+                    .map_or(OverwriteCheck::Any, OverwriteCheck::Unchanged);
 
                 interaction::file_save::save_file(
                     backup_dir.clone(),
+                    backup_retention,
                     output_file,
                     output,
                     existing_save,
                     new_save,
+                    overwrite_check,
                 )
                 .await?;
             }
@@ -0,0 +1,492 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::{
+    button, scrollable, Alignment, Button, Color, Column, Container, Length, Row, Scrollable, Text,
+};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use bl3_save_edit_core::file_diff::FileDiff;
+use bl3_save_edit_core::file_helper::Bl3FileType;
+use bl3_save_edit_core::protobuf_diff::{self, ProtobufFieldChange};
+
+use crate::bl3_ui::{Bl3Message, InteractionMessage};
+use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiStyleNoBorder};
+use crate::commands::interaction::backups::Backup;
+use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
+use crate::views::settings::SettingsInteractionMessage;
+use crate::views::InteractionExt;
+
+const DEFAULT_PROFILE_FILE_NAME: &str = "profile.sav";
+
+#[derive(Debug, Default)]
+pub struct BackupsState {
+    pub groups: Vec<BackupGroup>,
+    pub is_loading: bool,
+    pub is_restoring: bool,
+    pub comparison: Option<BackupComparison>,
+    back_button_state: button::State,
+    open_folder_button_state: button::State,
+    scrollable_state: scrollable::State,
+}
+
+/// All of the backups of a single save or profile.
+#[derive(Debug, Default)]
+pub struct BackupGroup {
+    pub title: String,
+    /// The file in the saves folder that a backup from this group is restored over.
+    pub output_file_name: String,
+    pub backups: Vec<BackupEntry>,
+}
+
+#[derive(Debug)]
+pub struct BackupEntry {
+    pub backup: Backup,
+    compare_button_state: button::State,
+    restore_button_state: button::State,
+}
+
+/// What changed in a file since it was backed up.
+#[derive(Debug, Default)]
+pub struct BackupComparison {
+    pub title: String,
+    pub diff: FileDiff,
+    /// Every field of the save or profile that changed, including ones the editor doesn't show.
+    pub field_changes: Vec<ProtobufFieldChange>,
+    summary: Vec<String>,
+    close_button_state: button::State,
+    copy_button_state: button::State,
+    scrollable_state: scrollable::State,
+}
+
+impl BackupsState {
+    pub fn map_backups(&mut self, backups: Vec<Backup>, loaded_files: &[Bl3FileType]) {
+        let mut groups: Vec<(String, BackupGroup)> = Vec::new();
+
+        // Backups are already sorted newest first so the title of each group comes from the
+        // latest backup
+        for backup in backups {
+            let (key, title, output_file_name) = match &backup.file {
+                Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+                    let character = &save.character_data.character;
+
+                    let output_file_name = loaded_files
+                        .iter()
+                        .find(|f| match f {
+                            Bl3FileType::PcSave(s) | Bl3FileType::Ps4Save(s) => {
+                                s.character_data.character.save_game_guid
+                                    == character.save_game_guid
+                            }
+                            _ => false,
+                        })
+                        .map(|f| f.filename().to_owned())
+                        .unwrap_or_else(|| format!("{:x}.sav", character.save_game_id));
+
+                    (
+                        character.save_game_guid.to_owned(),
+                        format!(
+                            "{} - {}",
+                            character.preferred_character_name,
+                            save.character_data.player_class()
+                        ),
+                        output_file_name,
+                    )
+                }
+                Bl3FileType::PcProfile(_) | Bl3FileType::Ps4Profile(_) => {
+                    let output_file_name = loaded_files
+                        .iter()
+                        .find(|f| {
+                            matches!(f, Bl3FileType::PcProfile(_) | Bl3FileType::Ps4Profile(_))
+                        })
+                        .map(|f| f.filename().to_owned())
+                        .unwrap_or_else(|| DEFAULT_PROFILE_FILE_NAME.to_owned());
+
+                    ("profile".to_owned(), "Profile".to_owned(), output_file_name)
+                }
+            };
+
+            let entry = BackupEntry {
+                backup,
+                compare_button_state: button::State::default(),
+                restore_button_state: button::State::default(),
+            };
+
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.backups.push(entry),
+                None => groups.push((
+                    key,
+                    BackupGroup {
+                        title,
+                        output_file_name,
+                        backups: vec![entry],
+                    },
+                )),
+            }
+        }
+
+        self.groups = groups.into_iter().map(|(_, g)| g).collect();
+        self.comparison = None;
+        self.scrollable_state.snap_to(0.0);
+    }
+
+    /// Compares a backup against the file in the saves folder that it would be restored over.
+    pub fn compare(
+        &mut self,
+        group_id: usize,
+        backup_id: usize,
+        loaded_files: &[Bl3FileType],
+    ) -> Result<()> {
+        let (group, backup) = self
+            .backup(group_id, backup_id)
+            .context("backup no longer exists")?;
+
+        let current_file = loaded_files
+            .iter()
+            .find(|f| f.filename() == group.output_file_name)
+            .with_context(|| format!("{} is not in the saves folder", group.output_file_name))?;
+
+        let (diff, field_changes) = match (&backup.file, current_file) {
+            (
+                Bl3FileType::PcSave(backup_save) | Bl3FileType::Ps4Save(backup_save),
+                Bl3FileType::PcSave(current_save) | Bl3FileType::Ps4Save(current_save),
+            ) => (
+                backup_save.diff(current_save),
+                protobuf_diff::diff_messages(
+                    &backup_save.character_data.character,
+                    &current_save.character_data.character,
+                ),
+            ),
+            (
+                Bl3FileType::PcProfile(backup_profile) | Bl3FileType::Ps4Profile(backup_profile),
+                Bl3FileType::PcProfile(current_profile) | Bl3FileType::Ps4Profile(current_profile),
+            ) => (
+                backup_profile.diff(current_profile),
+                protobuf_diff::diff_messages(
+                    &backup_profile.profile_data.profile,
+                    &current_profile.profile_data.profile,
+                ),
+            ),
+            _ => bail!("{} is not the same type of file", group.output_file_name),
+        };
+
+        let title = format!(
+            "Changes to {} since {}",
+            group.output_file_name,
+            backup.created.format("%d-%m-%Y %H:%M:%S")
+        );
+
+        self.comparison = Some(BackupComparison {
+            title,
+            summary: diff.summary(),
+            diff,
+            field_changes,
+            ..BackupComparison::default()
+        });
+
+        Ok(())
+    }
+
+    pub fn backup(&self, group_id: usize, backup_id: usize) -> Option<(&BackupGroup, &Backup)> {
+        self.groups
+            .get(group_id)
+            .and_then(|g| g.backups.get(backup_id).map(|b| (g, &b.backup)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum BackupsInteractionMessage {
+    BackPressed,
+    RestorePressed(usize, usize),
+    ComparePressed(usize, usize),
+    CloseComparisonPressed,
+    CopyComparisonPressed,
+}
+
+fn backup_summary(file: &Bl3FileType) -> String {
+    match file {
+        Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+            let character_data = &save.character_data;
+
+            format!(
+                "Level {} - {} Money - {} Eridium - {} Items",
+                character_data.player_level(),
+                character_data.money(),
+                character_data.eridium(),
+                character_data.inventory_items().len()
+            )
+        }
+        Bl3FileType::PcProfile(profile) | Bl3FileType::Ps4Profile(profile) => {
+            let profile_data = &profile.profile_data;
+
+            format!(
+                "Guardian Rank {} - {} Golden Keys - {} Bank Items",
+                profile_data.guardian_rank(),
+                profile_data.golden_keys(),
+                profile_data.bank_items().len()
+            )
+        }
+    }
+}
+
+pub fn view(backups_state: &mut BackupsState) -> Container<Bl3Message> {
+    let is_restoring = backups_state.is_restoring;
+
+    let back_button = Button::new(
+        &mut backups_state.back_button_state,
+        Text::new("Back").font(JETBRAINS_MONO_BOLD).size(17),
+    )
+    .on_press(InteractionMessage::BackupsInteraction(
+        BackupsInteractionMessage::BackPressed,
+    ))
+    .padding(10)
+    .style(Bl3UiStyle);
+
+    let open_folder_button = Button::new(
+        &mut backups_state.open_folder_button_state,
+        Text::new("Open Backups Folder")
+            .font(JETBRAINS_MONO_BOLD)
+            .size(17),
+    )
+    .on_press(InteractionMessage::SettingsInteraction(
+        SettingsInteractionMessage::OpenBackupDir,
+    ))
+    .padding(10)
+    .style(Bl3UiStyle);
+
+    let title_row = Row::new()
+        .push(back_button.into_element())
+        .push(
+            Text::new("Backups")
+                .font(JETBRAINS_MONO_BOLD)
+                .size(18)
+                .color(Color::from_rgb8(242, 203, 5))
+                .width(Length::Fill),
+        )
+        .push(open_folder_button.into_element())
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+    let description = Text::new(
+        "Restoring a backup replaces the file in your saves folder, a backup of that file is made first.",
+    )
+    .font(JETBRAINS_MONO)
+    .size(16)
+    .color(Color::from_rgb8(220, 220, 220));
+
+    let mut groups_column = Column::new().spacing(20);
+
+    if backups_state.is_loading || backups_state.groups.is_empty() {
+        groups_column = groups_column.push(
+            Container::new(
+                Text::new(if backups_state.is_loading {
+                    "Loading backups..."
+                } else {
+                    "No backups were found."
+                })
+                .font(JETBRAINS_MONO)
+                .size(17)
+                .color(Color::from_rgb8(220, 220, 220)),
+            )
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center),
+        );
+    } else {
+        for (group_id, group) in backups_state.groups.iter_mut().enumerate() {
+            let group_title = Text::new(format!(
+                "{} - {} ({} backups)",
+                group.title,
+                group.output_file_name,
+                group.backups.len()
+            ))
+            .font(JETBRAINS_MONO_BOLD)
+            .size(17)
+            .color(Color::from_rgb8(242, 203, 5));
+
+            let mut group_column = Column::new().push(group_title).spacing(10);
+
+            for (backup_id, entry) in group.backups.iter_mut().enumerate() {
+                let backup_details = Column::new()
+                    .push(
+                        Text::new(entry.backup.created.format("%d-%m-%Y %H:%M:%S").to_string())
+                            .font(JETBRAINS_MONO_BOLD)
+                            .size(16)
+                            .color(Color::from_rgb8(220, 220, 220)),
+                    )
+                    .push(
+                        Text::new(backup_summary(&entry.backup.file))
+                            .font(JETBRAINS_MONO)
+                            .size(15)
+                            .color(Color::from_rgb8(160, 160, 160)),
+                    )
+                    .spacing(5)
+                    .width(Length::Fill);
+
+                let compare_button = Button::new(
+                    &mut entry.compare_button_state,
+                    Text::new("Compare").font(JETBRAINS_MONO_BOLD).size(16),
+                )
+                .on_press(InteractionMessage::BackupsInteraction(
+                    BackupsInteractionMessage::ComparePressed(group_id, backup_id),
+                ))
+                .padding(5)
+                .style(Bl3UiStyle);
+
+                let mut restore_button = Button::new(
+                    &mut entry.restore_button_state,
+                    Text::new("Restore").font(JETBRAINS_MONO_BOLD).size(16),
+                )
+                .padding(5)
+                .style(Bl3UiStyle);
+
+                if !is_restoring {
+                    restore_button =
+                        restore_button.on_press(InteractionMessage::BackupsInteraction(
+                            BackupsInteractionMessage::RestorePressed(group_id, backup_id),
+                        ));
+                }
+
+                let backup_row = Row::new()
+                    .push(backup_details)
+                    .push(compare_button.into_element())
+                    .push(restore_button.into_element())
+                    .spacing(10)
+                    .align_items(Alignment::Center);
+
+                group_column = group_column.push(
+                    Container::new(backup_row)
+                        .padding(10)
+                        .width(Length::Fill)
+                        .style(Bl3UiStyleNoBorder),
+                );
+            }
+
+            groups_column = groups_column.push(
+                Container::new(group_column)
+                    .padding(15)
+                    .width(Length::Fill)
+                    .style(Bl3UiStyle),
+            );
+        }
+    }
+
+    let mut all_contents = Column::new().push(title_row).push(description).spacing(20);
+
+    all_contents = match &mut backups_state.comparison {
+        Some(comparison) => all_contents.push(comparison_view(comparison)),
+        None => all_contents.push(
+            Scrollable::new(&mut backups_state.scrollable_state)
+                .push(groups_column)
+                .height(Length::Fill),
+        ),
+    };
+
+    Container::new(all_contents)
+        .padding(30)
+        .width(Length::Fill)
+        .height(Length::Fill)
+}
+
+impl BackupComparison {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        #[derive(Serialize)]
+        struct ComparisonJson<'a> {
+            #[serde(flatten)]
+            diff: &'a FileDiff,
+            field_changes: &'a [ProtobufFieldChange],
+        }
+
+        serde_json::to_string_pretty(&ComparisonJson {
+            diff: &self.diff,
+            field_changes: &self.field_changes,
+        })
+    }
+}
+
+fn comparison_view(comparison: &mut BackupComparison) -> Container<Bl3Message> {
+    let close_button = Button::new(
+        &mut comparison.close_button_state,
+        Text::new("Close").font(JETBRAINS_MONO_BOLD).size(16),
+    )
+    .on_press(InteractionMessage::BackupsInteraction(
+        BackupsInteractionMessage::CloseComparisonPressed,
+    ))
+    .padding(5)
+    .style(Bl3UiStyle);
+
+    let copy_button = Button::new(
+        &mut comparison.copy_button_state,
+        Text::new("Copy as JSON").font(JETBRAINS_MONO_BOLD).size(16),
+    )
+    .on_press(InteractionMessage::BackupsInteraction(
+        BackupsInteractionMessage::CopyComparisonPressed,
+    ))
+    .padding(5)
+    .style(Bl3UiStyle);
+
+    let title_row = Row::new()
+        .push(
+            Text::new(&comparison.title)
+                .font(JETBRAINS_MONO_BOLD)
+                .size(17)
+                .color(Color::from_rgb8(242, 203, 5))
+                .width(Length::Fill),
+        )
+        .push(copy_button.into_element())
+        .push(close_button.into_element())
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+    let mut changes_column = Column::new().spacing(5);
+
+    if comparison.summary.is_empty() && comparison.field_changes.is_empty() {
+        changes_column = changes_column.push(
+            Text::new("Nothing has changed since this backup.")
+                .font(JETBRAINS_MONO)
+                .size(16)
+                .color(Color::from_rgb8(220, 220, 220)),
+        );
+    }
+
+    for line in &comparison.summary {
+        changes_column = changes_column.push(
+            Text::new(line)
+                .font(JETBRAINS_MONO)
+                .size(16)
+                .color(Color::from_rgb8(220, 220, 220)),
+        );
+    }
+
+    if !comparison.field_changes.is_empty() {
+        changes_column = changes_column.push(
+            Text::new("Changed Fields")
+                .font(JETBRAINS_MONO_BOLD)
+                .size(16)
+                .color(Color::from_rgb8(242, 203, 5)),
+        );
+    }
+
+    for field_change in &comparison.field_changes {
+        changes_column = changes_column.push(
+            Text::new(field_change.to_string())
+                .font(JETBRAINS_MONO)
+                .size(14)
+                .color(Color::from_rgb8(180, 180, 180)),
+        );
+    }
+
+    let contents = Column::new()
+        .push(title_row)
+        .push(
+            Scrollable::new(&mut comparison.scrollable_state)
+                .push(changes_column)
+                .height(Length::Fill),
+        )
+        .spacing(15);
+
+    Container::new(contents)
+        .padding(15)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(Bl3UiStyle)
+}
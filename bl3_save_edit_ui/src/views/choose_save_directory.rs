@@ -1,25 +1,26 @@
 use std::path::PathBuf;
 
 use iced::alignment::{Horizontal, Vertical};
-use iced::{button, Alignment, Button, Color, Column, Container, Length, Text};
+use iced::{
+    button, pick_list, Alignment, Button, Color, Column, Container, Element, Length, PickList, Text,
+};
 
-use bl3_save_edit_core::file_helper::Bl3FileType;
-
-use crate::bl3_ui::{Bl3Message, InteractionMessage, MessageResult};
-use crate::bl3_ui_style::Bl3UiStyle;
+use crate::bl3_ui::{Bl3Message, InteractionMessage, LoadedFileEntry, MessageResult};
+use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiUnreadableStyle};
+use crate::commands::interaction::choose_save_directory::UnreadableFile;
 use crate::resources::fonts::JETBRAINS_MONO;
 use crate::views::InteractionExt;
 
 #[derive(Debug, Default)]
 pub struct ChooseSaveDirectoryState {
     choose_dir_button_state: button::State,
+    unreadable_files_selector: pick_list::State<LoadedFileEntry>,
     pub choose_dir_window_open: bool,
 }
 
 #[derive(Debug, Clone)]
 pub enum ChooseSaveMessage {
     ChooseDirCompleted(MessageResult<PathBuf>),
-    FilesLoaded(MessageResult<(PathBuf, Vec<Bl3FileType>)>),
 }
 
 #[derive(Debug, Clone)]
@@ -27,7 +28,30 @@ pub enum ChooseSaveInteractionMessage {
     ChooseDirPressed,
 }
 
-pub fn view(choose_save_directory_state: &mut ChooseSaveDirectoryState) -> Container<Bl3Message> {
+/// Lists the files that couldn't be read, picking one shows why.
+pub fn unreadable_files_picklist<'a>(
+    state: &'a mut pick_list::State<LoadedFileEntry>,
+    unreadable_files: &[UnreadableFile],
+) -> Element<'a, Bl3Message> {
+    let entries = unreadable_files
+        .iter()
+        .cloned()
+        .map(LoadedFileEntry::Unreadable)
+        .collect::<Vec<_>>();
+
+    PickList::new(state, entries, None, InteractionMessage::LoadedFileSelected)
+        .placeholder(format!("{} unreadable", unreadable_files.len()))
+        .font(JETBRAINS_MONO)
+        .text_size(17)
+        .padding(10)
+        .style(Bl3UiUnreadableStyle)
+        .into_element()
+}
+
+pub fn view<'a>(
+    choose_save_directory_state: &'a mut ChooseSaveDirectoryState,
+    unreadable_files: &[UnreadableFile],
+) -> Container<'a, Bl3Message> {
     let dir_button_text = Text::new("Select Borderlands 3 Save/Profile folder")
         .font(JETBRAINS_MONO)
         .size(20)
@@ -49,12 +73,27 @@ pub fn view(choose_save_directory_state: &mut ChooseSaveDirectoryState) -> Conta
         ));
     }
 
-    let contents = Column::new()
+    let mut contents = Column::new()
         .push(dir_button_text)
         .push(dir_button.into_element())
         .spacing(20)
         .align_items(Alignment::Center);
 
+    // A folder where nothing could be read is still listed so that the reasons can be seen
+    if !unreadable_files.is_empty() {
+        contents = contents
+            .push(
+                Text::new("None of the files in the saves folder could be read:")
+                    .font(JETBRAINS_MONO)
+                    .size(17)
+                    .color(Color::from_rgb8(220, 220, 220)),
+            )
+            .push(unreadable_files_picklist(
+                &mut choose_save_directory_state.unreadable_files_selector,
+                unreadable_files,
+            ));
+    }
+
     Container::new(contents)
         .width(Length::Fill)
         .height(Length::Fill)
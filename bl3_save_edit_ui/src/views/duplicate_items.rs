@@ -0,0 +1,246 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::{
+    button, scrollable, Alignment, Button, Color, Column, Container, Length, Row, Scrollable, Text,
+};
+
+use bl3_save_edit_core::file_helper::Bl3FileType;
+use bl3_save_edit_core::item_index::{IndexedItem, ItemIndex};
+
+use crate::bl3_ui::{Bl3Message, InteractionMessage};
+use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiStyleNoBorder};
+use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
+use crate::views::item_editor::list_item_contents;
+use crate::views::InteractionExt;
+
+#[derive(Debug, Default)]
+pub struct DuplicateItemsState {
+    pub owners: Vec<DuplicateItemsOwner>,
+    pub is_removing: bool,
+    back_button_state: button::State,
+    remove_all_button_state: button::State,
+    scrollable_state: scrollable::State,
+}
+
+#[derive(Debug, Default)]
+pub struct DuplicateItemsOwner {
+    pub file_name: String,
+    pub owner: String,
+    pub duplicates: Vec<DuplicateItem>,
+    remove_button_state: button::State,
+}
+
+#[derive(Debug)]
+pub struct DuplicateItem {
+    pub extra: IndexedItem,
+    pub kept: IndexedItem,
+}
+
+impl DuplicateItemsState {
+    pub fn map_files(&mut self, files: &[Bl3FileType]) {
+        let mut owners: Vec<DuplicateItemsOwner> = Vec::new();
+
+        for group in ItemIndex::from_files(files).duplicates() {
+            for extra in group.extras {
+                let duplicate = DuplicateItem {
+                    extra,
+                    kept: group.kept.clone(),
+                };
+
+                match owners.iter_mut().find(|o| {
+                    o.file_name == duplicate.extra.file_name && o.owner == duplicate.extra.owner
+                }) {
+                    Some(owner) => owner.duplicates.push(duplicate),
+                    None => owners.push(DuplicateItemsOwner {
+                        file_name: duplicate.extra.file_name.clone(),
+                        owner: duplicate.extra.owner.clone(),
+                        duplicates: vec![duplicate],
+                        ..DuplicateItemsOwner::default()
+                    }),
+                }
+            }
+        }
+
+        owners.sort_by(|a, b| a.owner.cmp(&b.owner).then(a.file_name.cmp(&b.file_name)));
+
+        self.owners = owners;
+    }
+
+    /// All of the extra copies, optionally only the ones belonging to `file_name`.
+    pub fn extras(&self, file_name: Option<&str>) -> Vec<IndexedItem> {
+        self.owners
+            .iter()
+            .filter(|o| file_name.map(|f| f == o.file_name).unwrap_or(true))
+            .flat_map(|o| o.duplicates.iter().map(|d| d.extra.clone()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum DuplicateItemsInteractionMessage {
+    BackPressed,
+    RemoveExtrasPressed(String),
+    RemoveAllExtrasPressed,
+    /// Remove the extras from one file, or every file if `None`, after being asked about unsaved
+    /// changes.
+    RemoveExtrasConfirmed(Option<String>),
+}
+
+pub fn view(duplicate_items_state: &mut DuplicateItemsState) -> Container<Bl3Message> {
+    let is_removing = duplicate_items_state.is_removing;
+
+    let total_extras = duplicate_items_state
+        .owners
+        .iter()
+        .map(|o| o.duplicates.len())
+        .sum::<usize>();
+
+    let back_button = Button::new(
+        &mut duplicate_items_state.back_button_state,
+        Text::new("Back").font(JETBRAINS_MONO_BOLD).size(17),
+    )
+    .on_press(InteractionMessage::DuplicateItemsInteraction(
+        DuplicateItemsInteractionMessage::BackPressed,
+    ))
+    .padding(10)
+    .style(Bl3UiStyle);
+
+    let mut remove_all_button = Button::new(
+        &mut duplicate_items_state.remove_all_button_state,
+        Text::new(if is_removing {
+            "Removing...".to_string()
+        } else {
+            format!("Remove all extra copies ({})", total_extras)
+        })
+        .font(JETBRAINS_MONO_BOLD)
+        .size(17),
+    )
+    .padding(10)
+    .style(Bl3UiStyle);
+
+    if !is_removing && total_extras > 0 {
+        remove_all_button =
+            remove_all_button.on_press(InteractionMessage::DuplicateItemsInteraction(
+                DuplicateItemsInteractionMessage::RemoveAllExtrasPressed,
+            ));
+    }
+
+    let title_row = Row::new()
+        .push(back_button.into_element())
+        .push(
+            Text::new("Duplicate Items")
+                .font(JETBRAINS_MONO_BOLD)
+                .size(18)
+                .color(Color::from_rgb8(242, 203, 5))
+                .width(Length::Fill),
+        )
+        .push(remove_all_button.into_element())
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+    let description = Text::new(
+        "Items with the same balance and parts as another item, only the highest level copy is kept. A backup is made of each file before any items are removed.",
+    )
+    .font(JETBRAINS_MONO)
+    .size(16)
+    .color(Color::from_rgb8(220, 220, 220));
+
+    let mut owners_column = Column::new().spacing(20);
+
+    if duplicate_items_state.owners.is_empty() {
+        owners_column = owners_column.push(
+            Container::new(
+                Text::new("No duplicate items were found.")
+                    .font(JETBRAINS_MONO)
+                    .size(17)
+                    .color(Color::from_rgb8(220, 220, 220)),
+            )
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center),
+        );
+    }
+
+    for owner in duplicate_items_state.owners.iter_mut() {
+        let mut remove_button = Button::new(
+            &mut owner.remove_button_state,
+            Text::new(format!("Remove extra copies ({})", owner.duplicates.len()))
+                .font(JETBRAINS_MONO_BOLD)
+                .size(16),
+        )
+        .padding(5)
+        .style(Bl3UiStyle);
+
+        if !is_removing {
+            remove_button = remove_button.on_press(InteractionMessage::DuplicateItemsInteraction(
+                DuplicateItemsInteractionMessage::RemoveExtrasPressed(owner.file_name.clone()),
+            ));
+        }
+
+        let owner_title = Row::new()
+            .push(
+                Text::new(format!("{} - {}", owner.owner, owner.file_name))
+                    .font(JETBRAINS_MONO_BOLD)
+                    .size(17)
+                    .color(Color::from_rgb8(242, 203, 5))
+                    .width(Length::Fill),
+            )
+            .push(remove_button.into_element())
+            .align_items(Alignment::Center);
+
+        let mut owner_column = Column::new().push(owner_title).spacing(10);
+
+        for duplicate in &owner.duplicates {
+            let kept_text = Text::new(format!(
+                "Keeping Level {} copy in {} ({})",
+                duplicate.kept.item.level(),
+                duplicate.kept.owner,
+                duplicate.kept.location,
+            ))
+            .font(JETBRAINS_MONO)
+            .size(15)
+            .color(Color::from_rgb8(160, 160, 160));
+
+            let duplicate_row = Row::new()
+                .push(
+                    Container::new(list_item_contents::view(&duplicate.extra.item).into_element())
+                        .width(Length::FillPortion(3)),
+                )
+                .push(
+                    Container::new(kept_text)
+                        .width(Length::FillPortion(2))
+                        .align_x(Horizontal::Right),
+                )
+                .spacing(10)
+                .align_items(Alignment::Center);
+
+            owner_column = owner_column.push(
+                Container::new(duplicate_row)
+                    .padding(10)
+                    .width(Length::Fill)
+                    .style(Bl3UiStyleNoBorder),
+            );
+        }
+
+        owners_column = owners_column.push(
+            Container::new(owner_column)
+                .padding(15)
+                .width(Length::Fill)
+                .style(Bl3UiStyle),
+        );
+    }
+
+    let all_contents = Column::new()
+        .push(title_row)
+        .push(description)
+        .push(
+            Scrollable::new(&mut duplicate_items_state.scrollable_state)
+                .push(owners_column)
+                .height(Length::Fill),
+        )
+        .spacing(20);
+
+    Container::new(all_contents)
+        .padding(30)
+        .width(Length::Fill)
+        .height(Length::Fill)
+}
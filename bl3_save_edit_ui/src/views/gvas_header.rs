@@ -0,0 +1,288 @@
+use iced::{
+    pick_list, text_input, tooltip, Alignment, Color, Column, Container, Length, PickList, Row,
+    Text, Tooltip,
+};
+
+use bl3_save_edit_core::models::{CustomFormatData, GvasHeader};
+
+use crate::bl3_ui::{Bl3Message, InteractionMessage};
+use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiTooltipStyle};
+use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
+use crate::views::InteractionExt;
+use crate::widgets::labelled_element::LabelledElement;
+use crate::widgets::number_input::NumberInput;
+use crate::widgets::text_input_limited::TextInputLimited;
+
+#[derive(Debug, Default)]
+pub struct GvasHeaderState {
+    pub save_game_version_input: u32,
+    save_game_version_input_state: text_input::State,
+    pub package_version_input: u32,
+    package_version_input_state: text_input::State,
+    pub engine_major_input: u16,
+    engine_major_input_state: text_input::State,
+    pub engine_minor_input: u16,
+    engine_minor_input_state: text_input::State,
+    pub engine_patch_input: u16,
+    engine_patch_input_state: text_input::State,
+    pub engine_build_input: u32,
+    engine_build_input_state: text_input::State,
+    pub build_id_input: String,
+    build_id_input_state: text_input::State,
+    pub custom_format_version: u32,
+    pub custom_format_data: Vec<CustomFormatData>,
+    /// The other files in the saves folder that a header can be copied from.
+    pub copy_from_files: Vec<String>,
+    copy_from_selector: pick_list::State<String>,
+}
+
+impl GvasHeaderState {
+    pub fn map_header(&mut self, header: GvasHeader) {
+        self.save_game_version_input = header.save_game_version;
+        self.package_version_input = header.package_version;
+        self.engine_major_input = header.engine_major;
+        self.engine_minor_input = header.engine_minor;
+        self.engine_patch_input = header.engine_patch;
+        self.engine_build_input = header.engine_build;
+        self.build_id_input = header.build_id;
+        self.custom_format_version = header.custom_format_version;
+        self.custom_format_data = header.custom_format_data;
+    }
+
+    pub fn header(&self) -> GvasHeader {
+        GvasHeader {
+            save_game_version: self.save_game_version_input,
+            package_version: self.package_version_input,
+            engine_major: self.engine_major_input,
+            engine_minor: self.engine_minor_input,
+            engine_patch: self.engine_patch_input,
+            engine_build: self.engine_build_input,
+            build_id: self.build_id_input.clone(),
+            custom_format_version: self.custom_format_version,
+            custom_format_data: self.custom_format_data.clone(),
+        }
+    }
+
+    /// Applies a change to one of the inputs, copying from another file is handled by the caller
+    /// as it needs the loaded files.
+    pub fn update(&mut self, message: GvasHeaderInteractionMessage) {
+        match message {
+            GvasHeaderInteractionMessage::SaveGameVersion(v) => self.save_game_version_input = v,
+            GvasHeaderInteractionMessage::PackageVersion(v) => self.package_version_input = v,
+            GvasHeaderInteractionMessage::EngineMajor(v) => self.engine_major_input = v,
+            GvasHeaderInteractionMessage::EngineMinor(v) => self.engine_minor_input = v,
+            GvasHeaderInteractionMessage::EnginePatch(v) => self.engine_patch_input = v,
+            GvasHeaderInteractionMessage::EngineBuild(v) => self.engine_build_input = v,
+            GvasHeaderInteractionMessage::BuildId(build_id) => self.build_id_input = build_id,
+            GvasHeaderInteractionMessage::CopyFromSelected(_) => (),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum GvasHeaderInteractionMessage {
+    SaveGameVersion(u32),
+    PackageVersion(u32),
+    EngineMajor(u16),
+    EngineMinor(u16),
+    EnginePatch(u16),
+    EngineBuild(u32),
+    BuildId(String),
+    CopyFromSelected(String),
+}
+
+pub fn view<F>(header_state: &mut GvasHeaderState, interaction_message: F) -> Container<Bl3Message>
+where
+    F: Fn(GvasHeaderInteractionMessage) -> InteractionMessage + 'static + Copy,
+{
+    let title = Text::new("Header")
+        .font(JETBRAINS_MONO_BOLD)
+        .size(17)
+        .color(Color::from_rgb8(242, 203, 5));
+
+    let build_id = LabelledElement::create(
+        "Build ID",
+        Length::Units(160),
+        TextInputLimited::new(
+            &mut header_state.build_id_input_state,
+            "OAK-PATCHWIN641-118",
+            &header_state.build_id_input,
+            500,
+            move |s| interaction_message(GvasHeaderInteractionMessage::BuildId(s)),
+        )
+        .0
+        .font(JETBRAINS_MONO)
+        .padding(10)
+        .size(17)
+        .style(Bl3UiStyle)
+        .into_element(),
+    )
+    .spacing(15)
+    .align_items(Alignment::Center);
+
+    let versions = Row::new()
+        .push(
+            LabelledElement::create(
+                "Save Version",
+                Length::Units(160),
+                NumberInput::new(
+                    &mut header_state.save_game_version_input_state,
+                    header_state.save_game_version_input,
+                    0,
+                    None,
+                    move |v| interaction_message(GvasHeaderInteractionMessage::SaveGameVersion(v)),
+                )
+                .0
+                .font(JETBRAINS_MONO)
+                .padding(10)
+                .size(17)
+                .style(Bl3UiStyle)
+                .into_element(),
+            )
+            .spacing(15)
+            .width(Length::FillPortion(1))
+            .align_items(Alignment::Center),
+        )
+        .push(
+            LabelledElement::create(
+                "Package Version",
+                Length::Units(160),
+                NumberInput::new(
+                    &mut header_state.package_version_input_state,
+                    header_state.package_version_input,
+                    0,
+                    None,
+                    move |v| interaction_message(GvasHeaderInteractionMessage::PackageVersion(v)),
+                )
+                .0
+                .font(JETBRAINS_MONO)
+                .padding(10)
+                .size(17)
+                .style(Bl3UiStyle)
+                .into_element(),
+            )
+            .spacing(15)
+            .width(Length::FillPortion(1))
+            .align_items(Alignment::Center),
+        )
+        .spacing(20);
+
+    let engine_version_inputs = Row::new()
+        .push(
+            NumberInput::new(
+                &mut header_state.engine_major_input_state,
+                header_state.engine_major_input,
+                0,
+                None,
+                move |v| interaction_message(GvasHeaderInteractionMessage::EngineMajor(v)),
+            )
+            .0
+            .font(JETBRAINS_MONO)
+            .padding(10)
+            .size(17)
+            .style(Bl3UiStyle)
+            .into_element(),
+        )
+        .push(
+            NumberInput::new(
+                &mut header_state.engine_minor_input_state,
+                header_state.engine_minor_input,
+                0,
+                None,
+                move |v| interaction_message(GvasHeaderInteractionMessage::EngineMinor(v)),
+            )
+            .0
+            .font(JETBRAINS_MONO)
+            .padding(10)
+            .size(17)
+            .style(Bl3UiStyle)
+            .into_element(),
+        )
+        .push(
+            NumberInput::new(
+                &mut header_state.engine_patch_input_state,
+                header_state.engine_patch_input,
+                0,
+                None,
+                move |v| interaction_message(GvasHeaderInteractionMessage::EnginePatch(v)),
+            )
+            .0
+            .font(JETBRAINS_MONO)
+            .padding(10)
+            .size(17)
+            .style(Bl3UiStyle)
+            .into_element(),
+        )
+        .push(
+            NumberInput::new(
+                &mut header_state.engine_build_input_state,
+                header_state.engine_build_input,
+                0,
+                None,
+                move |v| interaction_message(GvasHeaderInteractionMessage::EngineBuild(v)),
+            )
+            .0
+            .font(JETBRAINS_MONO)
+            .padding(10)
+            .size(17)
+            .style(Bl3UiStyle)
+            .into_element(),
+        )
+        .spacing(10);
+
+    let engine_version =
+        LabelledElement::create("Engine Version", Length::Units(160), engine_version_inputs)
+            .spacing(15)
+            .align_items(Alignment::Center);
+
+    let copy_from = LabelledElement::create(
+        "Copy From",
+        Length::Units(160),
+        Tooltip::new(
+            PickList::new(
+                &mut header_state.copy_from_selector,
+                &header_state.copy_from_files[..],
+                None,
+                move |f| interaction_message(GvasHeaderInteractionMessage::CopyFromSelected(f)),
+            )
+            .font(JETBRAINS_MONO)
+            .text_size(17)
+            .width(Length::Fill)
+            .padding(10)
+            .style(Bl3UiStyle)
+            .into_element(),
+            "Copy the whole header from another file, such as one saved by the current game build",
+            tooltip::Position::Top,
+        )
+        .gap(10)
+        .padding(10)
+        .font(JETBRAINS_MONO)
+        .size(17)
+        .style(Bl3UiTooltipStyle),
+    )
+    .spacing(15)
+    .align_items(Alignment::Center);
+
+    let custom_format_data = Text::new(format!(
+        "Custom format version {} with {} entries, these are only changed by copying a header.",
+        header_state.custom_format_version,
+        header_state.custom_format_data.len()
+    ))
+    .font(JETBRAINS_MONO)
+    .size(15)
+    .color(Color::from_rgb8(160, 160, 160));
+
+    let all_contents = Column::new()
+        .push(title)
+        .push(build_id)
+        .push(versions)
+        .push(engine_version)
+        .push(copy_from)
+        .push(custom_format_data)
+        .spacing(15);
+
+    Container::new(all_contents)
+        .padding(15)
+        .width(Length::Fill)
+        .style(Bl3UiStyle)
+}
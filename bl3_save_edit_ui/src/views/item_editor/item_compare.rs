@@ -0,0 +1,195 @@
+use iced::alignment::Horizontal;
+use iced::{
+    button, scrollable, Alignment, Button, Color, Column, Container, Length, Row, Scrollable, Text,
+};
+
+use bl3_save_edit_core::bl3_item::{Bl3Item, Bl3Part};
+
+use crate::bl3_ui::{Bl3Message, InteractionMessage};
+use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiStyleNoBorder};
+use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
+use crate::views::item_editor::item_button_style::ItemEditorListButtonStyle;
+use crate::views::item_editor::{list_item_contents, ItemEditorInteractionMessage};
+use crate::views::InteractionExt;
+
+#[derive(Debug, Default)]
+pub struct ItemCompare {
+    pub item: Bl3Item,
+    pub source: String,
+    close_button_state: button::State,
+    scrollable_state: scrollable::State,
+}
+
+impl ItemCompare {
+    pub fn new<T: AsRef<str>>(item: Bl3Item, source: T) -> Self {
+        ItemCompare {
+            item,
+            source: source.as_ref().to_owned(),
+            ..Self::default()
+        }
+    }
+
+    pub fn view<'a, F>(
+        &'a mut self,
+        selected_item: &'a Bl3Item,
+        interaction_message: F,
+    ) -> Container<'a, Bl3Message>
+    where
+        F: Fn(ItemEditorInteractionMessage) -> InteractionMessage + 'static + Copy,
+    {
+        let title_row = Row::new()
+            .push(
+                Text::new(format!("Comparing with {}", self.source))
+                    .font(JETBRAINS_MONO_BOLD)
+                    .size(18)
+                    .color(Color::from_rgb8(242, 203, 5))
+                    .width(Length::Fill),
+            )
+            .push(
+                Button::new(
+                    &mut self.close_button_state,
+                    Text::new("Close")
+                        .font(JETBRAINS_MONO_BOLD)
+                        .size(16)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .on_press(interaction_message(
+                    ItemEditorInteractionMessage::CloseComparePressed,
+                ))
+                .padding(5)
+                .width(Length::Units(85))
+                .style(ItemEditorListButtonStyle)
+                .into_element(),
+            )
+            .align_items(Alignment::Center);
+
+        let items_row = Row::new()
+            .push(compare_column("Selected Item", selected_item))
+            .push(compare_column("Compared Item", &self.item))
+            .spacing(20);
+
+        let diff = selected_item.diff(&self.item);
+
+        let mut differences = Column::new().spacing(10);
+
+        if diff.is_empty() {
+            differences = differences.push(
+                Container::new(
+                    Text::new("These items are identical.")
+                        .font(JETBRAINS_MONO_BOLD)
+                        .size(17)
+                        .color(Color::from_rgb8(220, 220, 220)),
+                )
+                .width(Length::Fill)
+                .align_x(Horizontal::Center),
+            );
+        } else {
+            if let Some((old, new)) = &diff.balance {
+                differences = differences.push(changed_text("Balance", old, new));
+            }
+
+            if let Some((old, new)) = &diff.manufacturer {
+                differences = differences.push(changed_text("Manufacturer", old, new));
+            }
+
+            if let Some((old, new)) = &diff.level {
+                differences = differences.push(changed_text("Level", old, new));
+            }
+
+            if let Some((old, new)) = &diff.rarity {
+                differences = differences.push(changed_text("Rarity", old, new));
+            }
+
+            for category in &diff.parts {
+                differences = differences.push(category_title(&category.category));
+                differences = push_parts(differences, &category.added, &category.removed);
+            }
+
+            if !diff.anointments_added.is_empty() || !diff.anointments_removed.is_empty() {
+                differences = differences.push(category_title("Anointments"));
+                differences = push_parts(
+                    differences,
+                    &diff.anointments_added,
+                    &diff.anointments_removed,
+                );
+            }
+        }
+
+        let all_contents = Column::new()
+            .push(title_row)
+            .push(items_row)
+            .push(
+                Container::new(
+                    Scrollable::new(&mut self.scrollable_state)
+                        .push(differences)
+                        .height(Length::Fill),
+                )
+                .padding(1)
+                .height(Length::Fill),
+            )
+            .spacing(20);
+
+        Container::new(all_contents).padding(20).style(Bl3UiStyle)
+    }
+}
+
+fn compare_column<'a>(title: &str, item: &'a Bl3Item) -> Container<'a, Bl3Message> {
+    let contents = Column::new()
+        .push(
+            Text::new(title)
+                .font(JETBRAINS_MONO)
+                .size(15)
+                .color(Color::from_rgb8(160, 160, 160)),
+        )
+        .push(list_item_contents::view(item))
+        .spacing(10);
+
+    Container::new(contents.into_element())
+        .padding(9)
+        .width(Length::FillPortion(1))
+        .style(Bl3UiStyleNoBorder)
+}
+
+fn changed_text<T: std::fmt::Display>(label: &str, old: T, new: T) -> Text {
+    Text::new(format!("{}: {} ➜ {}", label, old, new))
+        .font(JETBRAINS_MONO)
+        .size(16)
+        .color(Color::from_rgb8(220, 220, 220))
+}
+
+fn category_title(category: &str) -> Text {
+    Text::new(category)
+        .font(JETBRAINS_MONO_BOLD)
+        .size(17)
+        .color(Color::from_rgb8(242, 203, 5))
+}
+
+fn push_parts<'a>(
+    mut column: Column<'a, Bl3Message>,
+    added: &[Bl3Part],
+    removed: &[Bl3Part],
+) -> Column<'a, Bl3Message> {
+    for part in added {
+        column = column.push(
+            Text::new(format!("+ {}", part_name(part)))
+                .font(JETBRAINS_MONO)
+                .size(16)
+                .color(Color::from_rgb8(149, 240, 171)),
+        );
+    }
+
+    for part in removed {
+        column = column.push(
+            Text::new(format!("- {}", part_name(part)))
+                .font(JETBRAINS_MONO)
+                .size(16)
+                .color(Color::from_rgb8(240, 149, 149)),
+        );
+    }
+
+    column
+}
+
+fn part_name(part: &Bl3Part) -> &str {
+    part.short_ident.as_ref().unwrap_or(&part.ident)
+}
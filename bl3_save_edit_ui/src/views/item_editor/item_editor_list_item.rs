@@ -12,6 +12,7 @@ use crate::views::item_editor::editor::Editor;
 use crate::views::item_editor::item_button_style::{
     ItemEditorButtonStyle, ItemEditorListButtonStyle, ItemEditorListNegativeButtonStyle,
 };
+use crate::views::item_editor::item_compare::ItemCompare;
 use crate::views::item_editor::{list_item_contents, ItemEditorInteractionMessage};
 use crate::views::InteractionExt;
 
@@ -22,6 +23,7 @@ pub struct ItemEditorListItem {
     list_button_state: button::State,
     duplicate_button_state: button::State,
     share_button_state: button::State,
+    compare_button_state: button::State,
     delete_button_state: button::State,
     pub editor: Editor,
 }
@@ -50,12 +52,13 @@ impl ItemEditorListItem {
         Ok(())
     }
 
-    pub fn view<F>(
-        &mut self,
+    pub fn view<'a, F>(
+        &'a mut self,
         id: usize,
         is_active: bool,
+        item_compare: Option<&'a mut ItemCompare>,
         interaction_message: F,
-    ) -> (Element<Bl3Message>, Option<Container<Bl3Message>>)
+    ) -> (Element<'a, Bl3Message>, Option<Container<'a, Bl3Message>>)
     where
         F: Fn(ItemEditorInteractionMessage) -> InteractionMessage + 'static + Copy,
     {
@@ -90,6 +93,21 @@ impl ItemEditorListItem {
                 .width(Length::Units(85))
                 .style(ItemEditorListButtonStyle),
             )
+            .push(
+                Button::new(
+                    &mut self.compare_button_state,
+                    Text::new("Compare")
+                        .font(JETBRAINS_MONO_BOLD)
+                        .size(16)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .on_press(interaction_message(
+                    ItemEditorInteractionMessage::CompareItem(id),
+                ))
+                .padding(5)
+                .width(Length::Units(85))
+                .style(ItemEditorListButtonStyle),
+            )
             .push(
                 Button::new(
                     &mut self.delete_button_state,
@@ -111,7 +129,10 @@ impl ItemEditorListItem {
         let item_content = list_item_contents::view(&self.item).push(action_row);
 
         let item_editor = if is_active {
-            Some(self.editor.view(&self.item, interaction_message))
+            match item_compare {
+                Some(item_compare) => Some(item_compare.view(&self.item, interaction_message)),
+                None => Some(self.editor.view(&self.item, interaction_message)),
+            }
         } else {
             None
         };
@@ -17,6 +17,7 @@ pub struct ItemEditorLootlemonItem {
     pub link: String,
     pub import_button_state: button::State,
     pub open_lootlemon_button_state: button::State,
+    pub compare_button_state: button::State,
 }
 
 impl ItemEditorLootlemonItem {
@@ -64,6 +65,21 @@ impl ItemEditorLootlemonItem {
                 .width(Length::Units(165))
                 .style(ItemEditorListButtonStyle),
             )
+            .push(
+                Button::new(
+                    &mut self.compare_button_state,
+                    Text::new("Compare")
+                        .font(JETBRAINS_MONO_BOLD)
+                        .size(16)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .on_press(interaction_message(
+                    ItemEditorInteractionMessage::ItemListLootlemonComparePressed(self.id),
+                ))
+                .padding(5)
+                .width(Length::Units(85))
+                .style(ItemEditorListButtonStyle),
+            )
             .width(Length::Fill)
             .spacing(10);
 
@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use derivative::Derivative;
 use heck::TitleCase;
 use iced::alignment::{Horizontal, Vertical};
@@ -32,6 +32,7 @@ use crate::commands::interaction;
 use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
 use crate::util;
 use crate::util::ErrorExt;
+use crate::views::item_editor::item_compare::ItemCompare;
 use crate::views::item_editor::item_editor_list_item::ItemEditorListItem;
 use crate::views::item_editor::item_editor_lootlemon_item::ItemEditorLootlemonItem;
 use crate::views::item_editor::parts_tab_bar::{AvailablePartType, CurrentPartType};
@@ -45,6 +46,7 @@ use crate::widgets::text_input_limited::TextInputLimited;
 pub mod editor;
 pub mod extra_part_info;
 pub mod item_button_style;
+pub mod item_compare;
 pub mod item_editor_list_item;
 pub mod item_editor_lootlemon_item;
 pub mod list_item_contents;
@@ -74,6 +76,8 @@ pub struct ItemEditorState {
     pub item_list_tab_type: ItemListTabType,
     pub item_list_items_tab_button_state: button::State,
     pub item_list_lootlemon_tab_button_state: button::State,
+    pub compare_serial_button_state: button::State,
+    pub item_compare: Option<ItemCompare>,
 }
 
 #[derive(Debug)]
@@ -169,6 +173,29 @@ impl ItemEditorState {
             .position(|i| i.item == previous_item)
             .unwrap_or(0)
     }
+
+    /// Selects the item that was loaded from `original_index`, if the items have been modified
+    /// since loading then the first identical item is selected instead.
+    pub fn select_item(&mut self, original_index: usize, item: &Bl3Item) -> Result<()> {
+        let position = self
+            .items
+            .iter()
+            .position(|i| i.index == original_index && i.item == *item)
+            .or_else(|| self.items.iter().position(|i| i.item == *item))
+            .context("item no longer exists")?;
+
+        self.selected_item_index = position;
+        self.item_list_tab_type = ItemListTabType::Items;
+        self.search_items_input.clear();
+        self.item_compare = None;
+
+        let list_len = self.items.len().saturating_sub(1).max(1);
+
+        self.item_list_scrollable_state
+            .snap_to(position as f32 / list_len as f32);
+
+        self.map_current_item_if_exists_to_editor_state()
+    }
 }
 
 pub trait ItemEditorStateExt {
@@ -270,6 +297,7 @@ pub enum ItemEditorInteractionMessage {
     ItemListLootlemonImportPressed(usize),
     ItemListLootlemonOpenWebsitePressed(usize),
     ItemListLootlemonOpenWebsiteCompleted(MessageResult<()>),
+    ItemListLootlemonComparePressed(usize),
     ShowAllAvailablePartsSelected(bool),
     AvailablePartsSearchInputChanged(String),
     AvailablePartsTabPressed,
@@ -295,6 +323,9 @@ pub enum ItemEditorInteractionMessage {
     DeleteItem(usize),
     DuplicateItem(usize),
     ShareItem(usize),
+    CompareItem(usize),
+    CompareItemFromSerialPressed,
+    CloseComparePressed,
     BalanceInputSelected(BalancePart),
     BalanceSearchInputChanged(String),
     InvDataInputSelected(InvDataPart),
@@ -320,6 +351,10 @@ impl ItemEditorInteractionMessage {
 
         match self {
             ItemEditorInteractionMessage::ItemPressed(item_index) => {
+                if item_index != item_editor_state.selected_item_index {
+                    item_editor_state.item_compare = None;
+                }
+
                 item_editor_state.selected_item_index = item_index;
 
                 item_editor_state
@@ -405,6 +440,21 @@ impl ItemEditorInteractionMessage {
                     notification = Some(Notification::new(msg, NotificationSentiment::Negative));
                 }
             }
+            ItemEditorInteractionMessage::ItemListLootlemonComparePressed(id) => {
+                if let Some(lootlemon_item) = item_editor_state.lootlemon_items.items.get(id) {
+                    item_editor_state.item_compare = Some(ItemCompare::new(
+                        lootlemon_item.item.clone(),
+                        "Lootlemon Item",
+                    ));
+                } else {
+                    let msg = format!(
+                        "Failed to compare Lootlemon item: couldn't find an item with index {}.",
+                        id
+                    );
+
+                    notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+                }
+            }
             ItemEditorInteractionMessage::ShowAllAvailablePartsSelected(selected) => {
                 item_editor_state
                     .map_current_item_if_exists(|i| {
@@ -887,6 +937,35 @@ impl ItemEditorInteractionMessage {
                     notification = Some(Notification::new(msg, NotificationSentiment::Negative));
                 }
             }
+            ItemEditorInteractionMessage::CompareItem(id) => {
+                if let Some(item) = item_editor_state.items.get(id) {
+                    item_editor_state.item_compare =
+                        Some(ItemCompare::new(item.item.clone(), "Item"));
+                } else {
+                    let msg = format!(
+                        "Failed to compare item number {}: could not find this item to compare.",
+                        id
+                    );
+
+                    notification = Some(Notification::new(msg, NotificationSentiment::Negative));
+                }
+            }
+            ItemEditorInteractionMessage::CompareItemFromSerialPressed => {
+                let item_serial = item_editor_state.import_serial_input.trim();
+
+                match Bl3Item::from_serial_base64(item_serial) {
+                    Ok(item) => {
+                        item_editor_state.item_compare =
+                            Some(ItemCompare::new(item, "Imported Serial"));
+                    }
+                    Err(e) => {
+                        e.handle_ui_error("Failed to compare serial", &mut notification);
+                    }
+                }
+            }
+            ItemEditorInteractionMessage::CloseComparePressed => {
+                item_editor_state.item_compare = None;
+            }
             ItemEditorInteractionMessage::BalanceInputSelected(balance_selected) => {
                 item_editor_state
                     .map_current_item_if_exists_result(|i| i.item.set_balance(balance_selected))
@@ -1027,6 +1106,18 @@ where
             .style(Bl3UiStyle)
             .into_element(),
         )
+        .push(
+            Button::new(
+                &mut item_editor_state.compare_serial_button_state,
+                Text::new("Compare").font(JETBRAINS_MONO_BOLD).size(17),
+            )
+            .on_press(interaction_message(
+                ItemEditorInteractionMessage::CompareItemFromSerialPressed,
+            ))
+            .padding(10)
+            .style(Bl3UiStyle)
+            .into_element(),
+        )
         .align_items(Alignment::Center);
 
     let create_item_button = Container::new(
@@ -1191,6 +1282,8 @@ where
 
     let mut item_editor = None;
 
+    let mut item_compare = item_editor_state.item_compare.as_mut();
+
     let mut inventory_item_categories = HashSet::new();
 
     // Keeping this here as we want the "editor" to show in both ItemListTabType views
@@ -1223,7 +1316,10 @@ where
 
             let is_active = i == selected_item_index;
 
-            let (list_item_button, curr_item_editor) = item.view(i, is_active, interaction_message);
+            let item_compare = if is_active { item_compare.take() } else { None };
+
+            let (list_item_button, curr_item_editor) =
+                item.view(i, is_active, item_compare, interaction_message);
 
             // Check if the curr item index is in our filtered_items to decide whether to show the
             // list item button or not.
@@ -1393,87 +1489,87 @@ pub fn get_filtered_items(
     items: &[ItemEditorListItem],
     lootlemon_items: &[ItemEditorLootlemonItem],
 ) -> Vec<(usize, Bl3Item)> {
-    let filter_items = |item: &Bl3Item| -> bool {
-        let search_items_query = search_items_query.trim();
-
-        if search_items_query.is_empty() {
-            return true;
-        }
-
-        // Handle this scenario explicitly as we want to search one if the other doesn't exist
-        let balance_part_to_search = if let Some(name) = &item.balance_part().name {
-            Some(name.to_lowercase())
-        } else {
-            item.balance_part()
-                .short_ident
-                .as_ref()
-                .map(|short_ident| short_ident.to_lowercase())
-        };
-
-        balance_part_to_search
-            .map(|n| n.contains(&search_items_query))
-            .unwrap_or(false)
-            || item
-                .manufacturer_part()
-                .short_ident
-                .as_ref()
-                .map(|mp| {
-                    mp.to_title_case()
-                        .to_lowercase()
-                        .contains(&search_items_query)
-                })
-                .unwrap_or(false)
-            || "favorite".contains(search_items_query)
-                && item
-                    .flags
-                    .map(|f| f.contains(ItemFlags::FAVORITE))
-                    .unwrap_or(false)
-            || "junk".contains(search_items_query)
-                && item
-                    .flags
-                    .map(|f| f.contains(ItemFlags::JUNK))
-                    .unwrap_or(false)
-            || format!("level {}", item.level().to_string()).contains(&search_items_query)
-            || item
-                .item_type
-                .to_string()
-                .to_lowercase()
-                .contains(&search_items_query)
-            || item
-                .item_parts
-                .as_ref()
-                .map(|ip| {
-                    ip.rarity
-                        .to_string()
-                        .to_lowercase()
-                        .contains(&search_items_query)
-                        || ip
-                            .weapon_type
-                            .as_ref()
-                            .map(|wt| wt.to_string().to_lowercase().contains(&search_items_query))
-                            .unwrap_or(false)
-                })
-                .unwrap_or(false)
-    };
-
     match item_list_tab_type {
         ItemListTabType::Items => items
             .par_iter()
             .enumerate()
             .map(|(i, item)| (i, &item.item))
-            .filter(|(_, item)| filter_items(item))
+            .filter(|(_, item)| item_matches_search(item, search_items_query))
             .map(|(i, item)| (i, item.clone()))
             .collect::<Vec<_>>(),
         ItemListTabType::Lootlemon => lootlemon_items
             .par_iter()
             .enumerate()
             .map(|(i, item)| (i, &item.item))
-            .filter(|(_, item)| filter_items(item))
+            .filter(|(_, item)| item_matches_search(item, search_items_query))
             .map(|(i, item)| (i, item.clone()))
             .collect::<Vec<_>>(),
     }
 }
 
+pub fn item_matches_search(item: &Bl3Item, search_items_query: &str) -> bool {
+    let search_items_query = search_items_query.trim();
+
+    if search_items_query.is_empty() {
+        return true;
+    }
+
+    // Handle this scenario explicitly as we want to search one if the other doesn't exist
+    let balance_part_to_search = if let Some(name) = &item.balance_part().name {
+        Some(name.to_lowercase())
+    } else {
+        item.balance_part()
+            .short_ident
+            .as_ref()
+            .map(|short_ident| short_ident.to_lowercase())
+    };
+
+    balance_part_to_search
+        .map(|n| n.contains(&search_items_query))
+        .unwrap_or(false)
+        || item
+            .manufacturer_part()
+            .short_ident
+            .as_ref()
+            .map(|mp| {
+                mp.to_title_case()
+                    .to_lowercase()
+                    .contains(&search_items_query)
+            })
+            .unwrap_or(false)
+        || "favorite".contains(search_items_query)
+            && item
+                .flags
+                .map(|f| f.contains(ItemFlags::FAVORITE))
+                .unwrap_or(false)
+        || "junk".contains(search_items_query)
+            && item
+                .flags
+                .map(|f| f.contains(ItemFlags::JUNK))
+                .unwrap_or(false)
+        || format!("level {}", item.level().to_string()).contains(&search_items_query)
+        || item
+            .item_type
+            .to_string()
+            .to_lowercase()
+            .contains(&search_items_query)
+        || item
+            .item_parts
+            .as_ref()
+            .map(|ip| {
+                ip.rarity
+                    .to_string()
+                    .to_lowercase()
+                    .contains(&search_items_query)
+                    || ip
+                        .weapon_type
+                        .as_ref()
+                        .map(|wt| wt.to_string().to_lowercase().contains(&search_items_query))
+                        .unwrap_or(false)
+            })
+            .unwrap_or(false)
+}
+
 pub fn sort_items(a: &Bl3Item, b: &Bl3Item) -> Ordering {
     a.item_type
         .cmp(&b.item_type)
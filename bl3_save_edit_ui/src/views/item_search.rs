@@ -0,0 +1,253 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::{
+    button, scrollable, text_input, tooltip, Alignment, Button, Color, Column, Container, Length,
+    Row, Scrollable, Text, Tooltip,
+};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use bl3_save_edit_core::file_helper::Bl3FileType;
+use bl3_save_edit_core::item_index::{IndexedItem, ItemIndex, ItemLocation};
+
+use crate::bl3_ui::{Bl3Message, InteractionMessage};
+use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiStyleNoBorder, Bl3UiTooltipStyle};
+use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
+use crate::views::item_editor::{item_matches_search, list_item_contents};
+use crate::views::{InteractionExt, NO_SEARCH_RESULTS_FOUND_MESSAGE};
+use crate::widgets::text_input_limited::TextInputLimited;
+
+#[derive(Debug, Default)]
+pub struct ItemSearchState {
+    index: ItemIndex,
+    pub search_input: String,
+    search_input_state: text_input::State,
+    results: Vec<ItemSearchResult>,
+    back_button_state: button::State,
+    scrollable_state: scrollable::State,
+}
+
+#[derive(Debug)]
+pub struct ItemSearchResult {
+    pub indexed_item: IndexedItem,
+    compare_button_state: button::State,
+    jump_button_state: button::State,
+}
+
+impl ItemSearchState {
+    pub fn map_files(&mut self, files: &[Bl3FileType]) {
+        self.index = ItemIndex::from_files(files);
+        self.search_input_state.focus();
+
+        self.update_results();
+    }
+
+    pub fn set_search_input(&mut self, search_input: String) {
+        self.search_input = search_input.to_lowercase();
+
+        self.update_results();
+    }
+
+    pub fn result(&self, id: usize) -> Option<&IndexedItem> {
+        self.results.get(id).map(|r| &r.indexed_item)
+    }
+
+    fn update_results(&mut self) {
+        let search_input = &self.search_input;
+
+        self.results = self
+            .index
+            .items()
+            .par_iter()
+            .filter(|i| item_matches_search(&i.item, search_input))
+            .map(|i| ItemSearchResult {
+                indexed_item: i.clone(),
+                compare_button_state: button::State::default(),
+                jump_button_state: button::State::default(),
+            })
+            .collect();
+
+        self.scrollable_state.snap_to(0.0);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ItemSearchInteractionMessage {
+    BackPressed,
+    SearchInputChanged(String),
+    CompareItemPressed(usize),
+    JumpToItemPressed(usize),
+}
+
+/// Where `indexed_item` is, such as `1.sav - Inventory slot 3`.
+pub fn location_text(indexed_item: &IndexedItem) -> String {
+    format!(
+        "{} - {} slot {}",
+        indexed_item.file_name,
+        indexed_item.location,
+        indexed_item.slot + 1
+    )
+}
+
+pub fn view(item_search_state: &mut ItemSearchState) -> Container<Bl3Message> {
+    let number_of_items = item_search_state.index.items().len();
+
+    let back_button = Button::new(
+        &mut item_search_state.back_button_state,
+        Text::new("Back").font(JETBRAINS_MONO_BOLD).size(17),
+    )
+    .on_press(InteractionMessage::ItemSearchInteraction(
+        ItemSearchInteractionMessage::BackPressed,
+    ))
+    .padding(10)
+    .style(Bl3UiStyle);
+
+    let search_input = TextInputLimited::new(
+        &mut item_search_state.search_input_state,
+        &format!("Search {} items across all files...", number_of_items),
+        &item_search_state.search_input,
+        500,
+        |s| {
+            InteractionMessage::ItemSearchInteraction(
+                ItemSearchInteractionMessage::SearchInputChanged(s),
+            )
+        },
+    )
+    .0
+    .font(JETBRAINS_MONO)
+    .padding(10)
+    .size(18)
+    .style(Bl3UiStyle)
+    .into_element();
+
+    let title_row = Row::new()
+        .push(back_button.into_element())
+        .push(search_input)
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+    let results_text = Text::new(format!(
+        "Found {} items in Inventories, Gun Racks, the Bank and Lost Loot",
+        item_search_state.results.len()
+    ))
+    .font(JETBRAINS_MONO)
+    .size(16)
+    .color(Color::from_rgb8(220, 220, 220));
+
+    let mut results_column = Column::new().spacing(10);
+
+    if item_search_state.results.is_empty() {
+        results_column = results_column.push(
+            Container::new(
+                Text::new(NO_SEARCH_RESULTS_FOUND_MESSAGE)
+                    .font(JETBRAINS_MONO_BOLD)
+                    .size(17)
+                    .color(Color::from_rgb8(220, 220, 220)),
+            )
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center),
+        );
+    }
+
+    for (id, result) in item_search_state.results.iter_mut().enumerate() {
+        let indexed_item = &result.indexed_item;
+
+        let location_column = Column::new()
+            .push(
+                Text::new(&indexed_item.owner)
+                    .font(JETBRAINS_MONO_BOLD)
+                    .size(16)
+                    .color(Color::from_rgb8(242, 203, 5)),
+            )
+            .push(
+                Text::new(location_text(indexed_item))
+                    .font(JETBRAINS_MONO)
+                    .size(15)
+                    .color(Color::from_rgb8(160, 160, 160)),
+            )
+            .spacing(5)
+            .align_items(Alignment::End);
+
+        // Any item can be compared, including ones in the Gun Rack and Lost Loot
+        let compare_button = Tooltip::new(
+            Button::new(
+                &mut result.compare_button_state,
+                Text::new("Compare")
+                    .font(JETBRAINS_MONO_BOLD)
+                    .size(16)
+                    .horizontal_alignment(Horizontal::Center),
+            )
+            .on_press(InteractionMessage::ItemSearchInteraction(
+                ItemSearchInteractionMessage::CompareItemPressed(id),
+            ))
+            .padding(5)
+            .width(Length::Units(85))
+            .style(Bl3UiStyle)
+            .into_element(),
+            "Compare with the selected item in the editor",
+            tooltip::Position::Top,
+        )
+        .gap(10)
+        .padding(10)
+        .font(JETBRAINS_MONO)
+        .size(17)
+        .style(Bl3UiTooltipStyle);
+
+        let mut jump_button = Button::new(
+            &mut result.jump_button_state,
+            Text::new("Open")
+                .font(JETBRAINS_MONO_BOLD)
+                .size(16)
+                .horizontal_alignment(Horizontal::Center),
+        )
+        .padding(5)
+        .width(Length::Units(85))
+        .style(Bl3UiStyle);
+
+        // Only Inventory and Bank items can be opened in the item editor
+        if matches!(
+            indexed_item.location,
+            ItemLocation::Inventory | ItemLocation::Bank
+        ) {
+            jump_button = jump_button.on_press(InteractionMessage::ItemSearchInteraction(
+                ItemSearchInteractionMessage::JumpToItemPressed(id),
+            ));
+        }
+
+        let result_row = Row::new()
+            .push(
+                Container::new(list_item_contents::view(&indexed_item.item).into_element())
+                    .width(Length::FillPortion(3)),
+            )
+            .push(
+                Container::new(location_column)
+                    .width(Length::FillPortion(2))
+                    .align_x(Horizontal::Right),
+            )
+            .push(compare_button)
+            .push(jump_button.into_element())
+            .spacing(15)
+            .align_items(Alignment::Center);
+
+        results_column = results_column.push(
+            Container::new(result_row)
+                .padding(10)
+                .width(Length::Fill)
+                .style(Bl3UiStyleNoBorder),
+        );
+    }
+
+    let all_contents = Column::new()
+        .push(title_row)
+        .push(results_text)
+        .push(
+            Scrollable::new(&mut item_search_state.scrollable_state)
+                .push(results_column)
+                .height(Length::Fill),
+        )
+        .spacing(20);
+
+    Container::new(all_contents)
+        .padding(30)
+        .width(Length::Fill)
+        .height(Length::Fill)
+}
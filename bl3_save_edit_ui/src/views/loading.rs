@@ -4,8 +4,8 @@ use iced::{Color, Container, Length, Text};
 use crate::bl3_ui::Bl3Message;
 use crate::resources::fonts::JETBRAINS_MONO;
 
-pub fn view<'a>() -> Container<'a, Bl3Message> {
-    let loading_text = Text::new("Loading...")
+pub fn view<'a>(loading_text: String) -> Container<'a, Bl3Message> {
+    let loading_text = Text::new(loading_text)
         .font(JETBRAINS_MONO)
         .size(20)
         .color(Color::from_rgb8(220, 220, 220));
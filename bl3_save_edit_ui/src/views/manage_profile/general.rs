@@ -3,11 +3,12 @@ use iced::{
     Tooltip,
 };
 
-use bl3_save_edit_core::parser::HeaderType;
+use bl3_save_edit_core::parser::{HeaderType, PayloadFormat};
 
 use crate::bl3_ui::{Bl3Message, InteractionMessage};
 use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiTooltipStyle};
 use crate::resources::fonts::JETBRAINS_MONO;
+use crate::views::gvas_header::{self, GvasHeaderInteractionMessage, GvasHeaderState};
 use crate::views::manage_profile::ManageProfileInteractionMessage;
 use crate::views::InteractionExt;
 use crate::widgets::labelled_element::LabelledElement;
@@ -18,11 +19,16 @@ pub struct GeneralState {
     pub filename_input_state: text_input::State,
     pub profile_type_selector: pick_list::State<HeaderType>,
     pub profile_type_selected: HeaderType,
+    pub payload_format_selector: pick_list::State<PayloadFormat>,
+    pub payload_format_selected: PayloadFormat,
+    pub header_state: GvasHeaderState,
 }
 
 #[derive(Debug, Clone)]
 pub enum ProfileGeneralInteractionMessage {
     ProfileTypeSelected(HeaderType),
+    PayloadFormatSelected(PayloadFormat),
+    Header(GvasHeaderInteractionMessage),
 }
 
 pub fn view(general_state: &mut GeneralState) -> Container<Bl3Message> {
@@ -89,7 +95,55 @@ pub fn view(general_state: &mut GeneralState) -> Container<Bl3Message> {
     .height(Length::Units(36))
     .style(Bl3UiStyle);
 
-    let all_contents = Column::new().push(file).push(profile_type).spacing(20);
+    let profile_format = Container::new(
+        LabelledElement::create(
+            "Save Format",
+            Length::Units(110),
+            Tooltip::new(
+                PickList::new(
+                    &mut general_state.payload_format_selector,
+                    &PayloadFormat::ALL[..],
+                    Some(general_state.payload_format_selected),
+                    |f| {
+                        InteractionMessage::ManageProfileInteraction(
+                            ManageProfileInteractionMessage::General(
+                                ProfileGeneralInteractionMessage::PayloadFormatSelected(f),
+                            ),
+                        )
+                    },
+                )
+                .font(JETBRAINS_MONO)
+                .text_size(17)
+                .width(Length::Fill)
+                .padding(10)
+                .style(Bl3UiStyle)
+                .into_element(),
+                "Decrypted and protobuf files can be read by other tools and protoc --decode",
+                tooltip::Position::Top,
+            )
+            .gap(10)
+            .padding(10)
+            .font(JETBRAINS_MONO)
+            .size(17)
+            .style(Bl3UiTooltipStyle),
+        )
+        .spacing(15)
+        .align_items(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .height(Length::Units(36))
+    .style(Bl3UiStyle);
+
+    let all_contents = Column::new()
+        .push(file)
+        .push(profile_type)
+        .push(profile_format)
+        .push(gvas_header::view(&mut general_state.header_state, |m| {
+            InteractionMessage::ManageProfileInteraction(ManageProfileInteractionMessage::General(
+                ProfileGeneralInteractionMessage::Header(m),
+            ))
+        }))
+        .spacing(20);
 
     Container::new(all_contents).padding(30)
 }
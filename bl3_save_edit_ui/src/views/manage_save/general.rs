@@ -3,11 +3,12 @@ use iced::{
     Row, Text, TextInput, Tooltip,
 };
 
-use bl3_save_edit_core::parser::HeaderType;
+use bl3_save_edit_core::parser::{HeaderType, PayloadFormat};
 
 use crate::bl3_ui::{Bl3Message, InteractionMessage};
 use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiTooltipStyle};
 use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
+use crate::views::gvas_header::{self, GvasHeaderInteractionMessage, GvasHeaderState};
 use crate::views::manage_save::ManageSaveInteractionMessage;
 use crate::views::InteractionExt;
 use crate::widgets::labelled_element::LabelledElement;
@@ -25,6 +26,9 @@ pub struct GeneralState {
     pub generate_guid_button_state: button::State,
     pub save_type_selector: pick_list::State<HeaderType>,
     pub save_type_selected: HeaderType,
+    pub payload_format_selector: pick_list::State<PayloadFormat>,
+    pub payload_format_selected: PayloadFormat,
+    pub header_state: GvasHeaderState,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +37,8 @@ pub enum SaveGeneralInteractionMessage {
     Slot(u32),
     GenerateGuidPressed,
     SaveTypeSelected(HeaderType),
+    PayloadFormatSelected(PayloadFormat),
+    Header(GvasHeaderInteractionMessage),
 }
 
 pub fn view(general_state: &mut GeneralState) -> Container<Bl3Message> {
@@ -193,11 +199,56 @@ pub fn view(general_state: &mut GeneralState) -> Container<Bl3Message> {
     .height(Length::Units(36))
     .style(Bl3UiStyle);
 
+    let save_format = Container::new(
+        LabelledElement::create(
+            "Save Format",
+            Length::Units(90),
+            Tooltip::new(
+                PickList::new(
+                    &mut general_state.payload_format_selector,
+                    &PayloadFormat::ALL[..],
+                    Some(general_state.payload_format_selected),
+                    |f| {
+                        InteractionMessage::ManageSaveInteraction(
+                            ManageSaveInteractionMessage::General(
+                                SaveGeneralInteractionMessage::PayloadFormatSelected(f),
+                            ),
+                        )
+                    },
+                )
+                .font(JETBRAINS_MONO)
+                .text_size(17)
+                .width(Length::Fill)
+                .padding(10)
+                .style(Bl3UiStyle)
+                .into_element(),
+                "Decrypted and protobuf files can be read by other tools and protoc --decode",
+                tooltip::Position::Top,
+            )
+            .gap(10)
+            .padding(10)
+            .font(JETBRAINS_MONO)
+            .size(17)
+            .style(Bl3UiTooltipStyle),
+        )
+        .spacing(15)
+        .align_items(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .height(Length::Units(36))
+    .style(Bl3UiStyle);
+
     let all_contents = Column::new()
         .push(file)
         .push(save_guid)
         .push(save_slot)
         .push(save_type)
+        .push(save_format)
+        .push(gvas_header::view(&mut general_state.header_state, |m| {
+            InteractionMessage::ManageSaveInteraction(ManageSaveInteractionMessage::General(
+                SaveGeneralInteractionMessage::Header(m),
+            ))
+        }))
         .spacing(20);
 
     Container::new(all_contents).padding(30)
@@ -0,0 +1,234 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::{
+    button, scrollable, text_input, Alignment, Button, Color, Column, Container, Length, Row,
+    Scrollable, Text,
+};
+
+use bl3_save_edit_core::file_helper::Bl3FileType;
+
+use crate::bl3_ui::{Bl3Message, InteractionMessage};
+use crate::bl3_ui_style::{Bl3UiStyle, Bl3UiStyleNoBorder};
+use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
+use crate::views::InteractionExt;
+use crate::widgets::number_input::NumberInput;
+
+#[derive(Debug, Default)]
+pub struct SaveSlotsState {
+    pub slots: Vec<SaveSlot>,
+    pub is_changing: bool,
+    back_button_state: button::State,
+    scrollable_state: scrollable::State,
+}
+
+#[derive(Debug, Default)]
+pub struct SaveSlot {
+    pub file_name: String,
+    pub title: String,
+    pub save_game_id: u32,
+    pub slot_input: u32,
+    slot_input_state: text_input::State,
+    clone_button_state: button::State,
+    move_button_state: button::State,
+    delete_button_state: button::State,
+}
+
+impl SaveSlotsState {
+    pub fn map_files(&mut self, files: &[Bl3FileType]) {
+        let mut slots = files
+            .iter()
+            .filter_map(|f| match f {
+                Bl3FileType::PcSave(save) | Bl3FileType::Ps4Save(save) => {
+                    let character = &save.character_data.character;
+
+                    Some(SaveSlot {
+                        file_name: save.file_name.clone(),
+                        title: format!(
+                            "{} - Level {} {}",
+                            character.preferred_character_name,
+                            save.character_data.player_level(),
+                            save.character_data.player_class()
+                        ),
+                        save_game_id: character.save_game_id,
+                        slot_input: character.save_game_id,
+                        ..SaveSlot::default()
+                    })
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        slots.sort_by_key(|s| s.save_game_id);
+
+        self.slots = slots;
+    }
+
+    pub fn slot(&self, file_name: &str) -> Option<&SaveSlot> {
+        self.slots.iter().find(|s| s.file_name == file_name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SaveSlotsInteractionMessage {
+    BackPressed,
+    SlotInputChanged(String, u32),
+    ClonePressed(String),
+    MovePressed(String),
+    MoveConfirmed(String),
+    DeletePressed(String),
+    DeleteConfirmed(String),
+}
+
+pub fn view(save_slots_state: &mut SaveSlotsState) -> Container<Bl3Message> {
+    let is_changing = save_slots_state.is_changing;
+
+    let back_button = Button::new(
+        &mut save_slots_state.back_button_state,
+        Text::new("Back").font(JETBRAINS_MONO_BOLD).size(17),
+    )
+    .on_press(InteractionMessage::SaveSlotsInteraction(
+        SaveSlotsInteractionMessage::BackPressed,
+    ))
+    .padding(10)
+    .style(Bl3UiStyle);
+
+    let title_row = Row::new()
+        .push(back_button.into_element())
+        .push(
+            Text::new("Save Slots")
+                .font(JETBRAINS_MONO_BOLD)
+                .size(18)
+                .color(Color::from_rgb8(242, 203, 5))
+                .width(Length::Fill),
+        )
+        .spacing(15)
+        .align_items(Alignment::Center);
+
+    let description = Text::new(
+        "Cloning copies a character into the next free slot as a new character. A backup is made before a character is moved or deleted.",
+    )
+    .font(JETBRAINS_MONO)
+    .size(16)
+    .color(Color::from_rgb8(220, 220, 220));
+
+    let mut slots_column = Column::new().spacing(10);
+
+    if save_slots_state.slots.is_empty() {
+        slots_column = slots_column.push(
+            Container::new(
+                Text::new("No saves were found.")
+                    .font(JETBRAINS_MONO)
+                    .size(17)
+                    .color(Color::from_rgb8(220, 220, 220)),
+            )
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center),
+        );
+    }
+
+    for slot in save_slots_state.slots.iter_mut() {
+        let slot_details = Column::new()
+            .push(
+                Text::new(&slot.title)
+                    .font(JETBRAINS_MONO_BOLD)
+                    .size(17)
+                    .color(Color::from_rgb8(242, 203, 5)),
+            )
+            .push(
+                Text::new(format!("Slot {} - {}", slot.save_game_id, slot.file_name))
+                    .font(JETBRAINS_MONO)
+                    .size(15)
+                    .color(Color::from_rgb8(160, 160, 160)),
+            )
+            .spacing(5)
+            .width(Length::Fill);
+
+        let file_name = slot.file_name.clone();
+
+        let slot_input = NumberInput::new(
+            &mut slot.slot_input_state,
+            slot.slot_input,
+            1,
+            None,
+            move |v| {
+                InteractionMessage::SaveSlotsInteraction(
+                    SaveSlotsInteractionMessage::SlotInputChanged(file_name.clone(), v),
+                )
+            },
+        )
+        .0
+        .font(JETBRAINS_MONO)
+        .padding(5)
+        .size(16)
+        .width(Length::Units(80))
+        .style(Bl3UiStyle);
+
+        let mut clone_button = Button::new(
+            &mut slot.clone_button_state,
+            Text::new("Clone").font(JETBRAINS_MONO_BOLD).size(16),
+        )
+        .padding(5)
+        .style(Bl3UiStyle);
+
+        let mut move_button = Button::new(
+            &mut slot.move_button_state,
+            Text::new("Move").font(JETBRAINS_MONO_BOLD).size(16),
+        )
+        .padding(5)
+        .style(Bl3UiStyle);
+
+        let mut delete_button = Button::new(
+            &mut slot.delete_button_state,
+            Text::new("Delete").font(JETBRAINS_MONO_BOLD).size(16),
+        )
+        .padding(5)
+        .style(Bl3UiStyle);
+
+        if !is_changing {
+            clone_button = clone_button.on_press(InteractionMessage::SaveSlotsInteraction(
+                SaveSlotsInteractionMessage::ClonePressed(slot.file_name.clone()),
+            ));
+
+            if slot.slot_input != slot.save_game_id {
+                move_button = move_button.on_press(InteractionMessage::SaveSlotsInteraction(
+                    SaveSlotsInteractionMessage::MovePressed(slot.file_name.clone()),
+                ));
+            }
+
+            delete_button = delete_button.on_press(InteractionMessage::SaveSlotsInteraction(
+                SaveSlotsInteractionMessage::DeletePressed(slot.file_name.clone()),
+            ));
+        }
+
+        let slot_row = Row::new()
+            .push(slot_details)
+            .push(clone_button.into_element())
+            .push(slot_input.into_element())
+            .push(move_button.into_element())
+            .push(delete_button.into_element())
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        slots_column = slots_column.push(
+            Container::new(slot_row)
+                .padding(10)
+                .width(Length::Fill)
+                .style(Bl3UiStyleNoBorder),
+        );
+    }
+
+    let all_contents = Column::new()
+        .push(title_row)
+        .push(description)
+        .push(
+            Scrollable::new(&mut save_slots_state.scrollable_state)
+                .push(slots_column)
+                .height(Length::Fill),
+        )
+        .spacing(20);
+
+    Container::new(all_contents)
+        .padding(30)
+        .width(Length::Fill)
+        .height(Length::Fill)
+}
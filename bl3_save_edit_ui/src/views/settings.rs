@@ -1,14 +1,19 @@
 use std::path::PathBuf;
 
 use iced::{
-    button, text_input, Alignment, Button, Color, Column, Container, Length, Row, Text, TextInput,
+    button, pick_list, text_input, Alignment, Button, Checkbox, Color, Column, Container, Length,
+    PickList, Row, Text, TextInput,
 };
 
+use bl3_save_edit_core::parser::Platform;
+
 use crate::bl3_ui::{Bl3Message, InteractionMessage, MessageResult};
 use crate::bl3_ui_style::Bl3UiStyle;
+use crate::config::BackupRetention;
 use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
 use crate::views::InteractionExt;
 use crate::widgets::labelled_element::LabelledElement;
+use crate::widgets::number_input::NumberInput;
 
 #[derive(Debug, Default)]
 pub struct SettingsState {
@@ -25,9 +30,18 @@ pub struct SettingsState {
     pub open_saves_dir_button_state: button::State,
     pub change_saves_dir_button_state: button::State,
     pub choose_saves_dir_window_open: bool,
+    pub convert_platform_selector: pick_list::State<Platform>,
+    pub convert_platform_selected: Platform,
+    pub convert_saves_button_state: button::State,
+    pub converting_saves: bool,
     pub decrease_ui_scale_button_state: button::State,
     pub increase_ui_scale_button_state: button::State,
     pub ui_scale_factor: f64,
+    pub backup_retention: BackupRetention,
+    pub backup_keep_last_input_state: text_input::State,
+    pub backup_keep_daily_input_state: text_input::State,
+    pub backup_keep_weekly_input_state: text_input::State,
+    pub backup_max_total_size_input_state: text_input::State,
 }
 
 #[derive(Debug, Clone)]
@@ -42,8 +56,17 @@ pub enum SettingsInteractionMessage {
     OpenSavesDirCompleted(MessageResult<()>),
     ChangeSavesDir,
     ChangeSavesDirCompleted(MessageResult<PathBuf>),
+    ConvertPlatformSelected(Platform),
+    ConvertSaves,
+    ConvertSavesOutputChosen(MessageResult<PathBuf>),
+    ConvertSavesCompleted(MessageResult<(PathBuf, usize)>),
     DecreaseUIScale,
     IncreaseUIScale,
+    DeleteOldBackups(bool),
+    BackupKeepLast(usize),
+    BackupKeepDaily(usize),
+    BackupKeepWeekly(usize),
+    BackupMaxTotalSize(u64),
 }
 
 pub fn view(settings_state: &mut SettingsState) -> Container<Bl3Message> {
@@ -199,6 +222,55 @@ pub fn view(settings_state: &mut SettingsState) -> Container<Bl3Message> {
     .height(Length::Units(36))
     .style(Bl3UiStyle);
 
+    let mut convert_saves_button = Button::new(
+        &mut settings_state.convert_saves_button_state,
+        Text::new("Convert Folder")
+            .font(JETBRAINS_MONO_BOLD)
+            .size(17),
+    )
+    .padding(10)
+    .style(Bl3UiStyle);
+
+    if !settings_state.converting_saves {
+        convert_saves_button = convert_saves_button.on_press(
+            InteractionMessage::SettingsInteraction(SettingsInteractionMessage::ConvertSaves),
+        );
+    }
+
+    let convert_saves = Container::new(
+        Row::new()
+            .push(
+                LabelledElement::create(
+                    "Convert saves to",
+                    Length::Units(140),
+                    PickList::new(
+                        &mut settings_state.convert_platform_selector,
+                        &Platform::ALL[..],
+                        Some(settings_state.convert_platform_selected),
+                        |p| {
+                            InteractionMessage::SettingsInteraction(
+                                SettingsInteractionMessage::ConvertPlatformSelected(p),
+                            )
+                        },
+                    )
+                    .font(JETBRAINS_MONO)
+                    .text_size(17)
+                    .width(Length::Fill)
+                    .padding(10)
+                    .style(Bl3UiStyle)
+                    .into_element(),
+                )
+                .spacing(15)
+                .width(Length::FillPortion(9))
+                .align_items(Alignment::Center),
+            )
+            .push(convert_saves_button.into_element())
+            .align_items(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .height(Length::Units(36))
+    .style(Bl3UiStyle);
+
     let ui_scale = Container::new(
         LabelledElement::create(
             "UI Scale",
@@ -243,10 +315,149 @@ pub fn view(settings_state: &mut SettingsState) -> Container<Bl3Message> {
     )
     .style(Bl3UiStyle);
 
+    let backup_retention = settings_state.backup_retention;
+
+    let delete_old_backups = Checkbox::new(
+        backup_retention.delete_old_backups,
+        "Delete old backups",
+        |c| {
+            InteractionMessage::SettingsInteraction(SettingsInteractionMessage::DeleteOldBackups(c))
+        },
+    )
+    .size(20)
+    .font(JETBRAINS_MONO)
+    .text_color(Color::from_rgb8(220, 220, 220))
+    .text_size(17)
+    .style(Bl3UiStyle)
+    .into_element();
+
+    let backup_keep_last = LabelledElement::create(
+        "Latest",
+        Length::Units(65),
+        NumberInput::new(
+            &mut settings_state.backup_keep_last_input_state,
+            backup_retention.keep_last,
+            1,
+            None,
+            |v| {
+                InteractionMessage::SettingsInteraction(SettingsInteractionMessage::BackupKeepLast(
+                    v,
+                ))
+            },
+        )
+        .0
+        .font(JETBRAINS_MONO)
+        .padding(10)
+        .size(17)
+        .style(Bl3UiStyle)
+        .into_element(),
+    )
+    .spacing(15)
+    .width(Length::FillPortion(1))
+    .align_items(Alignment::Center);
+
+    let backup_keep_daily = LabelledElement::create(
+        "Days",
+        Length::Units(45),
+        NumberInput::new(
+            &mut settings_state.backup_keep_daily_input_state,
+            backup_retention.keep_daily,
+            0,
+            None,
+            |v| {
+                InteractionMessage::SettingsInteraction(
+                    SettingsInteractionMessage::BackupKeepDaily(v),
+                )
+            },
+        )
+        .0
+        .font(JETBRAINS_MONO)
+        .padding(10)
+        .size(17)
+        .style(Bl3UiStyle)
+        .into_element(),
+    )
+    .spacing(15)
+    .width(Length::FillPortion(1))
+    .align_items(Alignment::Center);
+
+    let backup_keep_weekly = LabelledElement::create(
+        "Weeks",
+        Length::Units(55),
+        NumberInput::new(
+            &mut settings_state.backup_keep_weekly_input_state,
+            backup_retention.keep_weekly,
+            0,
+            None,
+            |v| {
+                InteractionMessage::SettingsInteraction(
+                    SettingsInteractionMessage::BackupKeepWeekly(v),
+                )
+            },
+        )
+        .0
+        .font(JETBRAINS_MONO)
+        .padding(10)
+        .size(17)
+        .style(Bl3UiStyle)
+        .into_element(),
+    )
+    .spacing(15)
+    .width(Length::FillPortion(1))
+    .align_items(Alignment::Center);
+
+    let backup_max_total_size = LabelledElement::create(
+        "Max MB",
+        Length::Units(65),
+        NumberInput::new(
+            &mut settings_state.backup_max_total_size_input_state,
+            backup_retention.max_total_size_mb,
+            0,
+            None,
+            |v| {
+                InteractionMessage::SettingsInteraction(
+                    SettingsInteractionMessage::BackupMaxTotalSize(v),
+                )
+            },
+        )
+        .0
+        .font(JETBRAINS_MONO)
+        .padding(10)
+        .size(17)
+        .style(Bl3UiStyle)
+        .into_element(),
+    )
+    .spacing(15)
+    .width(Length::FillPortion(1))
+    .align_items(Alignment::Center);
+
+    let backups_kept = Container::new(
+        LabelledElement::create(
+            "Backups kept",
+            Length::Units(140),
+            Row::new()
+                .push(delete_old_backups)
+                .push(backup_keep_last)
+                .push(backup_keep_daily)
+                .push(backup_keep_weekly)
+                .push(backup_max_total_size)
+                .spacing(20)
+                .align_items(Alignment::Center),
+        )
+        .spacing(15)
+        .width(Length::Fill)
+        .align_items(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .height(Length::Units(36))
+    .style(Bl3UiStyle);
+
     let all_contents = Column::new()
         .push(config_dir)
         .push(backup_dir)
+        .push(backups_kept)
         .push(saves_dir)
+        .push(convert_saves)
         .push(ui_scale)
         .spacing(20);
 
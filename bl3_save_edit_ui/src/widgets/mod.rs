@@ -1,5 +1,6 @@
 pub mod labelled_element;
 pub mod notification;
 pub mod number_input;
+pub mod prompt;
 pub mod text_input_limited;
 pub mod text_margin;
@@ -0,0 +1,121 @@
+use iced::alignment::{Horizontal, Vertical};
+use iced::{
+    button, scrollable, Alignment, Button, Color, Column, Container, Length, Row, Scrollable, Text,
+};
+
+use crate::bl3_ui::Bl3Message;
+use crate::bl3_ui_style::Bl3UiStyle;
+use crate::resources::fonts::{JETBRAINS_MONO, JETBRAINS_MONO_BOLD};
+
+/// A message that is shown under the menu bar until one of its actions or Cancel is pressed.
+#[derive(Debug, Default)]
+pub struct Prompt {
+    message: String,
+    details: Vec<String>,
+    actions: Vec<PromptAction>,
+    cancel_button_state: button::State,
+    details_scrollable_state: scrollable::State,
+}
+
+#[derive(Debug)]
+struct PromptAction {
+    label: String,
+    on_press: Bl3Message,
+    button_state: button::State,
+}
+
+impl Prompt {
+    pub fn new<T: AsRef<str>>(message: T) -> Self {
+        Prompt {
+            message: message.as_ref().to_owned(),
+            ..Prompt::default()
+        }
+    }
+
+    /// Lines shown under the message, such as what is about to change.
+    pub fn details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+
+        self
+    }
+
+    pub fn action<T: AsRef<str>>(mut self, label: T, on_press: Bl3Message) -> Self {
+        self.actions.push(PromptAction {
+            label: label.as_ref().to_owned(),
+            on_press,
+            button_state: button::State::default(),
+        });
+
+        self
+    }
+
+    pub fn view(&mut self) -> Container<Bl3Message> {
+        let mut contents_row = Row::new()
+            .push(
+                Container::new(
+                    Text::new(&self.message)
+                        .font(JETBRAINS_MONO_BOLD)
+                        .size(18)
+                        .color(Color::from_rgb8(242, 203, 5)),
+                )
+                .width(Length::Fill),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        for action in &mut self.actions {
+            contents_row = contents_row.push(
+                Button::new(
+                    &mut action.button_state,
+                    Text::new(&action.label).font(JETBRAINS_MONO_BOLD).size(17),
+                )
+                .on_press(Bl3Message::PromptActionPressed(Box::new(
+                    action.on_press.clone(),
+                )))
+                .padding(10)
+                .style(Bl3UiStyle),
+            );
+        }
+
+        contents_row = contents_row.push(
+            Button::new(
+                &mut self.cancel_button_state,
+                Text::new("Cancel").font(JETBRAINS_MONO_BOLD).size(17),
+            )
+            .on_press(Bl3Message::ClearPrompt)
+            .padding(10)
+            .style(Bl3UiStyle),
+        );
+
+        let mut contents = Column::new().push(contents_row).spacing(15);
+
+        if !self.details.is_empty() {
+            let details = self.details.iter().fold(
+                Scrollable::new(&mut self.details_scrollable_state)
+                    .spacing(5)
+                    .max_height(200),
+                |details, line| {
+                    details.push(
+                        Text::new(line)
+                            .font(JETBRAINS_MONO)
+                            .size(16)
+                            .color(Color::from_rgb8(220, 220, 220)),
+                    )
+                },
+            );
+
+            contents = contents.push(details);
+        }
+
+        Container::new(
+            Container::new(contents)
+                .width(Length::Fill)
+                .padding(20)
+                .style(Bl3UiStyle),
+        )
+        .align_x(Horizontal::Center)
+        .align_y(Vertical::Center)
+        .width(Length::Fill)
+        .padding(1)
+    }
+}